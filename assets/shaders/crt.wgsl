@@ -0,0 +1,37 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader FullscreenVertexOutput
+
+@group(0) @binding(0)
+var screen_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var texture_sampler: sampler;
+
+struct CrtSettings {
+    scanline_intensity: f32,
+    curvature: f32,
+    vignette: f32,
+}
+@group(0) @binding(2)
+var<uniform> settings: CrtSettings;
+
+const PI: f32 = 3.14159265;
+// Scanlines repeat every this many screen pixels
+const SCANLINE_PERIOD: f32 = 3.0;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // Push the image out from the center like the glass of a tube, more so towards the corners
+    let centered = in.uv * 2.0 - 1.0;
+    let bent = centered * (1.0 + settings.curvature * dot(centered, centered) * 0.25);
+    let uv = bent * 0.5 + 0.5;
+    // Sampled before any branching, sampling has to happen in uniform control flow
+    var color = textureSample(screen_texture, texture_sampler, uv).rgb;
+
+    let row = uv.y * f32(textureDimensions(screen_texture).y);
+    let scanline = 0.5 + 0.5 * cos(row * 2.0 * PI / SCANLINE_PERIOD);
+    color *= 1.0 - settings.scanline_intensity * scanline;
+    color *= 1.0 - settings.vignette * dot(bent, bent) * 0.5;
+
+    // The bent screen no longer reaches its corners, those are left black
+    let outside = any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0));
+    return vec4<f32>(select(color, vec3<f32>(0.0), outside), 1.0);
+}
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    app_state::AppState,
+    breaker::Level,
+    levels::Levels,
+    settings::GameSettings,
+    walls::{BOTTOM_WALL, LEFT_WALL, RIGHT_WALL, TOP_WALL},
+};
+
+// Behind everything in the arena, including the dev heatmap overlay
+const BACKGROUND_Z: f32 = -1.;
+
+// Draws each level's background image behind the arena. It is only a sprite, with no Collider,
+// so it never takes part in collisions
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundImages>()
+            .add_systems(OnEnter(AppState::InGame), preload_backgrounds)
+            .add_systems(
+                Update,
+                show_level_background.run_if(state_exists_and_equals(AppState::InGame)),
+            );
+    }
+}
+
+// The scenery a level is played in front of, chosen in its level definition
+#[derive(Component, Clone, Copy, Default, PartialEq, Eq, Debug, Deserialize)]
+pub enum LevelBackground {
+    #[default]
+    Starfield,
+    Cityscape,
+    SynthwaveGrid,
+}
+
+impl LevelBackground {
+    const ALL: [LevelBackground; 3] = [
+        LevelBackground::Starfield,
+        LevelBackground::Cityscape,
+        LevelBackground::SynthwaveGrid,
+    ];
+
+    fn texture_path(&self) -> &'static str {
+        match self {
+            LevelBackground::Starfield => "images/backgrounds/starfield.png",
+            LevelBackground::Cityscape => "images/backgrounds/cityscape.png",
+            LevelBackground::SynthwaveGrid => "images/backgrounds/synthwave_grid.png",
+        }
+    }
+}
+
+// Every background's image, loaded when the game starts so changing levels never waits on one
+#[derive(Resource, Default)]
+pub struct BackgroundImages(Vec<(LevelBackground, Handle<Image>)>);
+
+impl BackgroundImages {
+    fn get(&self, background: LevelBackground) -> Option<Handle<Image>> {
+        self.0
+            .iter()
+            .find(|(loaded, _)| *loaded == background)
+            .map(|(_, handle)| handle.clone())
+    }
+}
+
+pub fn preload_backgrounds(
+    mut images: ResMut<BackgroundImages>,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if !settings.visual.level_backgrounds || !images.0.is_empty() {
+        return;
+    }
+    images.0 = LevelBackground::ALL
+        .iter()
+        .map(|background| (*background, asset_server.load(background.texture_path())))
+        .collect();
+}
+
+// Swaps in the current level's background whenever it differs from the one shown, which covers
+// the next level as well as a new run after everything was despawned
+pub fn show_level_background(
+    mut commands: Commands,
+    background_q: Query<(Entity, &LevelBackground)>,
+    images: Res<BackgroundImages>,
+    levels: Res<Levels>,
+    level: Res<Level>,
+    settings: Res<GameSettings>,
+) {
+    let wanted = settings
+        .visual
+        .level_backgrounds
+        .then(|| levels.get(**level).background);
+    let mut shown = false;
+    for (ent, background) in background_q.iter() {
+        if Some(*background) == wanted {
+            shown = true;
+        } else {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+    let (Some(background), false) = (wanted, shown) else {
+        return;
+    };
+    let Some(texture) = images.get(background) else {
+        return; // Not preloaded, the setting was turned on mid run
+    };
+    commands.spawn((
+        SpriteBundle {
+            texture,
+            sprite: Sprite {
+                custom_size: Some(Vec2::new(RIGHT_WALL - LEFT_WALL, TOP_WALL - BOTTOM_WALL)),
+                ..default()
+            },
+            transform: Transform::from_xyz(
+                (LEFT_WALL + RIGHT_WALL) / 2.,
+                (BOTTOM_WALL + TOP_WALL) / 2.,
+                BACKGROUND_Z,
+            ),
+            ..default()
+        },
+        background,
+        Name::new("LevelBackground"),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{levels::LevelDefinition, misc::test_utils::test_app};
+
+    fn background_app() -> App {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .visual
+            .level_backgrounds = true;
+        let levels = [LevelBackground::Starfield, LevelBackground::Cityscape]
+            .into_iter()
+            .map(|background| LevelDefinition {
+                background,
+                ..default()
+            })
+            .collect();
+        let images = LevelBackground::ALL
+            .iter()
+            .map(|background| (*background, Handle::default()))
+            .collect();
+        app.insert_resource(Levels::from_definitions(levels))
+            .insert_resource(BackgroundImages(images))
+            .insert_resource(Level(1))
+            .add_systems(Update, show_level_background);
+        app
+    }
+
+    fn shown_backgrounds(app: &mut App) -> Vec<(LevelBackground, f32)> {
+        let mut background_q = app.world.query::<(&LevelBackground, &Transform)>();
+        background_q
+            .iter(&app.world)
+            .map(|(background, tform)| (*background, tform.translation.z))
+            .collect()
+    }
+
+    #[test]
+    fn the_next_level_swaps_in_its_background() {
+        let mut app = background_app();
+        app.update();
+        assert_eq!(
+            shown_backgrounds(&mut app),
+            vec![(LevelBackground::Starfield, BACKGROUND_Z)]
+        );
+
+        **app.world.resource_mut::<Level>() += 1;
+        app.update();
+        assert_eq!(
+            shown_backgrounds(&mut app),
+            vec![(LevelBackground::Cityscape, BACKGROUND_Z)]
+        );
+    }
+}
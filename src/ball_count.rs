@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::Ball,
+    catch::Held,
+    misc::labeled_display::{set_displayed_value, LabeledDisplayBundle},
+};
+
+// Marker for the ui text bundle
+#[derive(Component)]
+pub struct BallCountDisplay;
+
+#[derive(Bundle)]
+pub struct BallCountDisplayBundle {
+    display: LabeledDisplayBundle<BallCountDisplay>,
+}
+
+impl BallCountDisplayBundle {
+    pub fn new<L: Into<String>, V: Into<String>>(
+        font_size: f32,
+        label_color: Color,
+        count_color: Color,
+        label: L,
+        top_left_placement: Vec2,
+        initial_value: Option<V>,
+    ) -> Self {
+        BallCountDisplayBundle {
+            display: LabeledDisplayBundle::new(
+                BallCountDisplay,
+                "BallCount",
+                font_size,
+                label_color,
+                count_color,
+                label,
+                top_left_placement,
+                initial_value,
+            ),
+        }
+    }
+}
+
+// Counts the balls currently in play, so multiball chaos stays readable. Balls held on the paddle
+// aren't in play, so the count hides while the only ball is a served one waiting docked, and reads
+// 0 in the moment between losing the last ball and the next serve
+pub fn update_ball_count_display(
+    in_play_q: Query<(), (With<Ball>, Without<Held>)>,
+    held_q: Query<(), (With<Ball>, With<Held>)>,
+    mut text_q: Query<(&mut Text, &mut Visibility), With<BallCountDisplay>>,
+) {
+    let Ok((mut text, mut visibility)) = text_q.get_single_mut() else {
+        warn!("No ball count display to update");
+        return;
+    };
+    let in_play = in_play_q.iter().count();
+    *visibility = if in_play == 0 && !held_q.is_empty() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    set_displayed_value(&mut text, in_play.to_string());
+}
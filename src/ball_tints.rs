@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::{breaker::Ball, settings::GameSettings};
+
+// Distinct colors handed out to balls, at least as many as the default ball cap
+const BALL_TINT_PALETTE: [Color; 8] = [
+    Color::rgb(1., 0.45, 0.45),
+    Color::rgb(0.45, 0.75, 1.),
+    Color::rgb(0.5, 1., 0.5),
+    Color::rgb(1., 0.85, 0.35),
+    Color::rgb(0.85, 0.5, 1.),
+    Color::rgb(0.35, 1., 0.9),
+    Color::rgb(1., 0.6, 0.2),
+    Color::rgb(1., 0.55, 0.85),
+];
+
+// The palette slot a ball was given when it spawned
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BallTint(usize);
+
+impl BallTint {
+    pub fn color(&self) -> Color {
+        BALL_TINT_PALETTE[self.0 % BALL_TINT_PALETTE.len()]
+    }
+}
+
+// Tracks which ball owns each palette slot, so colors freed by lost balls are handed out again
+#[derive(Resource, Default)]
+pub struct BallTints {
+    owners: [Option<Entity>; BALL_TINT_PALETTE.len()],
+    shared: usize, // Counts the slots handed out while every slot was taken
+}
+
+impl BallTints {
+    // Gives the ball the first free slot. Once the palette runs out the slots are shared in turn
+    pub fn claim(&mut self, ball: Entity) -> BallTint {
+        let slot = match self.owners.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => {
+                self.shared += 1;
+                (self.shared - 1) % BALL_TINT_PALETTE.len()
+            }
+        };
+        self.owners[slot] = Some(ball);
+        BallTint(slot)
+    }
+
+    pub fn release(&mut self, ball: Entity) {
+        for owner in self.owners.iter_mut() {
+            if *owner == Some(ball) {
+                *owner = None;
+            }
+        }
+    }
+}
+
+// The color a ball shows while no element is tinting it
+pub fn ball_color(tint: Option<&BallTint>, settings: &GameSettings, theme_ball: Color) -> Color {
+    match tint {
+        Some(tint) if settings.visual.ball_tints => tint.color(),
+        _ => theme_ball,
+    }
+}
+
+// Frees the palette slots of balls that were despawned
+pub fn release_ball_tints(mut removed: RemovedComponents<Ball>, mut tints: ResMut<BallTints>) {
+    for ball in removed.iter() {
+        tints.release(ball);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balls_spawned_together_get_different_tints() {
+        let mut tints = BallTints::default();
+        let first = tints.claim(Entity::from_raw(1));
+        let second = tints.claim(Entity::from_raw(2));
+        assert_ne!(first, second);
+        assert_ne!(first.color(), second.color());
+    }
+
+    #[test]
+    fn a_lost_ball_frees_its_tint_for_the_next_ball() {
+        let mut tints = BallTints::default();
+        let lost = tints.claim(Entity::from_raw(1));
+        tints.claim(Entity::from_raw(2));
+        tints.release(Entity::from_raw(1));
+        assert_eq!(tints.claim(Entity::from_raw(3)), lost);
+    }
+}
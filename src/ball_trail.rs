@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, Ball, BallSpeed, PhysicsSet},
+    catch::Held,
+    misc::hit_stop::hit_stop_inactive,
+    returning::settled,
+    settings::GameSettings,
+};
+
+// How long a trail segment lasts at the first level's ball speed, faster balls keep theirs longer
+const TRAIL_SECS: f32 = 0.12;
+// Segments start see-through and shrink from the ball's size as they fade
+const TRAIL_START_ALPHA: f32 = 0.5;
+const TRAIL_SCALE: f32 = 0.8;
+// No more segments are spawned while this many are alive, however many balls are in play
+const MAX_TRAIL_SEGMENTS: usize = 150;
+// Drawn just behind the ball
+const TRAIL_Z_OFFSET: f32 = 0.1;
+const TRAIL_TEXTURE_PATH: &str = "images/round-brick.png";
+
+// Leaves a fading trail behind every moving ball, growing longer with the ball's speed. Purely
+// visual, the segments have no Collider
+pub struct BallTrailPlugin;
+
+impl Plugin for BallTrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            spawn_ball_trail
+                .before(PhysicsSet)
+                .run_if(game_running)
+                .run_if(hit_stop_inactive)
+                .run_if(settled)
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        )
+        .add_systems(
+            Update,
+            fade_ball_trail
+                .run_if(game_running)
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// A copy of the ball left where it was on an earlier physics tick, despawned once faded out
+#[derive(Component)]
+pub struct TrailFade(Timer);
+
+// Runs before the ball moves each physics tick, so every segment marks a previous position. Under
+// reduce motion no trail is left at all
+pub fn spawn_ball_trail(
+    mut commands: Commands,
+    ball_q: Query<(&Transform, &Handle<ColorMaterial>), (With<Ball>, Without<Held>)>,
+    trail_q: Query<(), With<TrailFade>>,
+    mats: Res<Assets<ColorMaterial>>,
+    ball_speed: Res<BallSpeed>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    if settings.accessibility.reduce_motion {
+        return;
+    }
+    let mut segments = trail_q.iter().count();
+    let lifetime = TRAIL_SECS * **ball_speed / *BallSpeed::default();
+    for (ball_t, handle) in ball_q.iter() {
+        if segments >= MAX_TRAIL_SEGMENTS {
+            return;
+        }
+        segments += 1;
+        let color = mats.get(handle).map_or(Color::WHITE, |mat| mat.color);
+        commands.spawn((
+            SpriteBundle {
+                texture: asset_server.load(TRAIL_TEXTURE_PATH),
+                transform: Transform {
+                    translation: ball_t.translation - Vec3::Z * TRAIL_Z_OFFSET,
+                    scale: ball_t.scale * TRAIL_SCALE,
+                    ..default()
+                },
+                sprite: Sprite {
+                    custom_size: Some(Vec2::ONE),
+                    color: color.with_a(TRAIL_START_ALPHA),
+                    ..default()
+                },
+                ..default()
+            },
+            TrailFade(Timer::from_seconds(lifetime, TimerMode::Once)),
+            Name::new("BallTrail"),
+        ));
+    }
+}
+
+pub fn fade_ball_trail(
+    mut commands: Commands,
+    mut trail_q: Query<(Entity, &mut TrailFade, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (ent, mut fade, mut sprite) in trail_q.iter_mut() {
+        if fade.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+            continue;
+        }
+        let alpha = TRAIL_START_ALPHA * fade.0.percent_left();
+        sprite.color.set_a(alpha);
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{BrickDestroyed, BrickTracker, GameStateTransition},
+    bricks::{spawn_golden_bricks, BrickDimensions, BrickKind, Destroying},
+    scoreboard::Scoreboard,
+    settings::GameSettings,
+};
+
+const BONUS_ROUND_DURATION: f32 = 8.0;
+// Added on top of the normal points for hitting a brick
+const GOLDEN_BRICK_BONUS: usize = 50;
+const COUNTDOWN_FONT_SIZE: f32 = 40.;
+const COUNTDOWN_COLOR: Color = Color::rgb(1., 0.84, 0.);
+
+// The optional round of golden bricks played between levels, enabled by gameplay.bonus_round
+#[derive(Resource)]
+pub struct BonusRound {
+    timer: Timer,
+    active: bool,
+}
+
+impl Default for BonusRound {
+    fn default() -> Self {
+        BonusRound {
+            timer: Timer::from_seconds(BONUS_ROUND_DURATION, TimerMode::Once),
+            active: false,
+        }
+    }
+}
+
+impl BonusRound {
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+// Marker for the text counting down the bonus round's remaining time
+#[derive(Component)]
+pub struct BonusCountdown;
+
+// Starts the bonus round when requested, and drops it if the run is restarted or ends mid-round
+#[allow(clippy::too_many_arguments)]
+pub fn manage_bonus_round(
+    mut commands: Commands,
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut bonus_round: ResMut<BonusRound>,
+    mut brick_tracker: ResMut<BrickTracker>,
+    countdown_q: Query<Entity, With<BonusCountdown>>,
+    brick_dims: Res<BrickDimensions>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    for msg in game_msgs.iter() {
+        match msg {
+            GameStateTransition::ToBonusRound => {
+                bonus_round.active = true;
+                bonus_round.timer.reset();
+                // Golden bricks replace the cleared level in the tracker, so clearing them ends the round early
+                **brick_tracker = spawn_golden_bricks(
+                    &mut commands,
+                    &brick_dims,
+                    settings.gameplay.arena_shape,
+                    &asset_server,
+                );
+                commands.spawn((
+                    TextBundle::from_section(
+                        countdown_text(BONUS_ROUND_DURATION),
+                        TextStyle {
+                            font_size: COUNTDOWN_FONT_SIZE,
+                            color: COUNTDOWN_COLOR,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(5.),
+                        left: Val::Percent(40.),
+                        ..default()
+                    }),
+                    BonusCountdown,
+                    Name::new("BonusCountdown"),
+                ));
+            }
+            GameStateTransition::RestartLevel
+            | GameStateTransition::RestartGame
+            | GameStateTransition::ToGameOver => {
+                bonus_round.active = false;
+                for ent in countdown_q.iter() {
+                    commands.entity(ent).despawn_recursive();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn countdown_text(secs_left: f32) -> String {
+    format!("BONUS {:.0}", secs_left.ceil())
+}
+
+// Counts the bonus round down, moving on to the next level once time runs out or every golden brick is gone
+pub fn tick_bonus_round(
+    mut commands: Commands,
+    mut bonus_round: ResMut<BonusRound>,
+    mut countdown_q: Query<(Entity, &mut Text), With<BonusCountdown>>,
+    golden_q: Query<(Entity, &BrickKind), Without<Destroying>>,
+    brick_tracker: Res<BrickTracker>,
+    mut game_msgs: EventWriter<GameStateTransition>,
+    time: Res<Time>,
+) {
+    if !bonus_round.active {
+        return;
+    }
+    bonus_round.timer.tick(time.delta());
+    for (_, mut text) in countdown_q.iter_mut() {
+        text.sections[0].value = countdown_text(bonus_round.timer.remaining_secs());
+    }
+
+    if bonus_round.timer.finished() || **brick_tracker == 0 {
+        bonus_round.active = false;
+        for (ent, _) in countdown_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+        // Missed golden bricks must not carry over into the next level
+        for (ent, kind) in golden_q.iter() {
+            if *kind == BrickKind::Golden {
+                commands.entity(ent).despawn_recursive();
+            }
+        }
+        game_msgs.send(GameStateTransition::NextLevel);
+    }
+}
+
+pub fn score_golden_bricks(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut scoreboard: ResMut<Scoreboard>,
+) {
+    for destroyed in destroyed_events.iter() {
+        if destroyed.kind == BrickKind::Golden {
+            scoreboard.score += GOLDEN_BRICK_BONUS;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{advance_time, sent_events, test_app};
+
+    fn next_levels(app: &App) -> usize {
+        sent_events::<GameStateTransition>(app)
+            .into_iter()
+            .filter(|msg| matches!(msg, GameStateTransition::NextLevel))
+            .count()
+    }
+
+    #[test]
+    fn the_bonus_round_moves_on_to_the_next_level_once_its_time_is_up() {
+        let mut app = test_app();
+        // The golden bricks load their textures
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_resource::<BonusRound>()
+            .init_resource::<BrickDimensions>()
+            .insert_resource(BrickTracker(0))
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (
+                    manage_bonus_round,
+                    tick_bonus_round.after(manage_bonus_round),
+                ),
+            );
+        app.world.send_event(GameStateTransition::ToBonusRound);
+        advance_time(&mut app, 0.);
+        app.update();
+        assert!(app.world.resource::<BonusRound>().is_active());
+        assert!(**app.world.resource::<BrickTracker>() > 0);
+
+        advance_time(&mut app, BONUS_ROUND_DURATION / 2.);
+        app.update();
+        assert!(app.world.resource::<BonusRound>().is_active());
+        assert_eq!(next_levels(&app), 0);
+
+        advance_time(&mut app, BONUS_ROUND_DURATION / 2.);
+        app.update();
+        assert!(!app.world.resource::<BonusRound>().is_active());
+        assert_eq!(next_levels(&app), 1);
+    }
+}
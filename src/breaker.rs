@@ -3,23 +3,101 @@ use core::f32::consts::PI;
 use std::time::Duration;
 
 use bevy::{
+    audio::Volume,
+    ecs::system::SystemParam,
     prelude::*,
     sprite::{
         collide_aabb::{collide, Collision},
         MaterialMesh2dBundle,
     },
+    window::PrimaryWindow,
 };
 use lerp::Lerp;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     app_state::{AppState, AppStateTransition},
-    bricks::{spawn_bricks, Brick, BRICK_COLORS},
+    ball_count::{update_ball_count_display, BallCountDisplayBundle},
+    ball_tints::{ball_color, release_ball_tints, BallTints},
+    bonus_round::{manage_bonus_round, score_golden_bricks, tick_bonus_round, BonusRound},
+    breakthrough::{tick_break_streaks, track_break_streaks, BreakStreak},
+    brick_descent::{
+        descend_bricks, reset_brick_descent, slide_descending_bricks, start_brick_descent,
+        BrickDescent,
+    },
+    bricks::{
+        add_hp_bars, add_strength_labels, animate_destroying_bricks, clear_indestructible_bricks,
+        cycle_brick_colors, update_hp_bars, update_streak_labels, update_strength_labels, Brick,
+        BrickDimensions, BrickKind, BrickPalette, BrickShape, Destroying, PaddleContacts,
+        StreakProgress, EXPLOSION_REACH,
+    },
+    catch::{
+        carry_held_balls, catch_balls, launch_held_ball, release_held_balls, show_launch_prompt,
+        Held,
+    },
+    combo::{combo_meter, combo_meter_fill},
+    difficulty::Difficulty,
+    elements::{
+        split_balls, tick_ball_elements, BallElement, Element, SplitBall, ICE_SPEED_FACTOR,
+    },
+    events::GameEventsPlugin,
+    gamepad::GamepadInput,
+    ghost_ball::GhostBall,
+    grace::{show_grace_cue, start_level_grace_period, tick_grace_period, GracePeriod},
+    hardcore::hardcore_flag,
+    hazards::{
+        freeze_paddle_on_trap, reset_reversed_controls, reverse_controls_on_trap,
+        show_reversed_cue, thaw_paddle, tick_reversed_controls, Frozen, ReversedControls,
+    },
     health::{Health, HealthDisplay, HealthDisplayBundle},
-    misc::blink::{blink, Blinking},
+    hud::{apply_hud_layout, fit_hud_to_window, HudElement, HudLayout},
+    key_bindings::Action,
+    level_display::LevelDisplayBundle,
+    levels::{LevelSpawner, Levels},
+    net::{simulates_locally, RemoteMomentum, RemotePaddle},
+    misc::{
+        audio::OptionalSound,
+        blink::{blink, Blinking},
+        hit_stop::{hit_stop_inactive, tick_hit_stop, trigger_hit_stop, HitStop},
+        labeled_display::update_labeled_display,
+        particles::{spawn_particle_bursts, update_particles},
+        rng::GameRng,
+    },
+    music::MusicStem,
+    paddle_width::{reset_paddle_width, update_paddle_width, PaddleWidth},
+    physics::{ball_influence_edges, ball_influence_momentum},
+    powerups::{
+        apply_power_ups, grant_mystery_power_ups, tick_power_ups, ApplyPowerUp, SlowBall,
+        SLOW_BALL_FACTOR,
+    },
+    preview::show_preview_prompt,
+    profile::Profile,
+    returning::{return_to_start, settled, ReturningToStart},
     scoreboard::{update_scoreboard, Scoreboard, ScoreboardBundle},
+    settings::GameSettings,
+    speed::{
+        finish_level_speed, speed_display, tick_speed_record_text, update_speed_display,
+        FastestSpeed,
+    },
+    stats::{track_destroyed_bricks, track_run_time, RunStats},
+    strong_ball::{ball_damage, StrongBall},
+    theme::ThemeColors,
+    tilt::game_camera,
+    turret::{fire_turret, turret_bundle},
+    wall_bonus::wall_bonus_display,
     walls::{self, Wall},
 };
 
+// Kept here so modules importing these from breaker keep working
+pub use crate::{
+    events::{BrickDestroyed, CollisionEvent, CollisionKind, GameStateTransition, PlayerMessage},
+    physics::{
+        aabb_contact_point, ball_reflect, ball_ricochet, circle_contact_normal,
+        first_swept_contact, jitter_bounce, overlap_area, side_normal, MAX_BOUNCE_JITTER_DEGREES,
+    },
+};
+
 #[derive(Resource, Deref, DerefMut, PartialEq, Eq)]
 pub struct CurrentState(pub GameState);
 
@@ -28,6 +106,9 @@ pub enum GameState {
     Uninitialized,
     Playing,
     Paused,
+    BonusRound, // Golden bricks between levels, see bonus_round.rs
+    Preview,    // The level is shown with physics frozen until the player starts it
+    Countdown,  // Physics frozen for a few seconds before play starts, see countdown.rs
 }
 
 impl Default for GameState {
@@ -36,67 +117,270 @@ impl Default for GameState {
     }
 }
 
-// Events related to starting the game, creating levels, playing, pausing
-#[derive(Event, Clone, Debug)]
-pub enum GameStateTransition {
-    ToUninitialized,
-    ToPlayGame,
-    ToHaltGame,
-    NextLevel,
-    ToGameOver,
-    // TODO:
-    // RestartGame,
+// Run condition for gameplay systems, which run the same in the bonus round as in a level
+pub fn game_running(game_state: Res<CurrentState>) -> bool {
+    matches!(**game_state, GameState::Playing | GameState::BonusRound)
+}
+
+// The state a freshly spawned level starts in
+fn level_start_state(settings: &GameSettings) -> GameState {
+    if settings.gameplay.level_preview {
+        GameState::Preview
+    } else if settings.gameplay.countdown {
+        GameState::Countdown
+    } else {
+        GameState::Playing
+    }
 }
 
-// Events related to player health, death
-#[derive(Event, Clone)]
-pub enum PlayerMessage {
-    JustLostHealth,
+// The state asking to play moves on to. Starting the run, leaving the level preview or resuming
+// from pause counts down first, and asking to play during the countdown skips the rest of it.
+// Resuming from pause returns to the bonus round if one was underway
+fn play_state(current: &GameState, settings: &GameSettings, bonus_round: bool) -> GameState {
+    let counts_down = settings.gameplay.countdown
+        && matches!(
+            current,
+            GameState::Uninitialized | GameState::Paused | GameState::Preview
+        );
+    if counts_down {
+        GameState::Countdown
+    } else if bonus_round {
+        GameState::BonusRound
+    } else {
+        GameState::Playing
+    }
 }
 
 // The current number of bricks in the level
 #[derive(Resource, Deref, DerefMut)]
-pub struct BrickTracker(usize);
+pub struct BrickTracker(pub usize);
 
 // The current level
 #[derive(Resource, Deref, DerefMut)]
-pub struct Level(usize);
+pub struct Level(pub usize);
+
+// How fast the ball moves this level, rising with each level to ramp up the difficulty
+#[derive(Resource, Deref)]
+pub struct BallSpeed {
+    #[deref]
+    speed: f32,
+    factor: f32, // Scales every level's speed, set by the difficulty
+}
+
+impl BallSpeed {
+    pub fn new(level: usize, factor: f32) -> Self {
+        let levels_cleared = level.saturating_sub(1) as f32;
+        let speed = BALL_SPEED * factor * (1. + BALL_SPEED_PER_LEVEL * levels_cleared);
+        BallSpeed {
+            speed: speed.min(MAX_BALL_SPEED),
+            factor,
+        }
+    }
+
+    // Moves to the level's speed, keeping the difficulty's scaling
+    pub fn set_level(&mut self, level: usize) {
+        *self = BallSpeed::new(level, self.factor);
+    }
+
+    // The speed as a multiple of the first level's speed on the default difficulty
+    pub fn multiple(&self) -> f32 {
+        self.speed / BALL_SPEED
+    }
+}
+
+impl Default for BallSpeed {
+    fn default() -> Self {
+        BallSpeed::new(1, 1.)
+    }
+}
 
 // The paddle could be a resource, but making a component allows multiple
 #[derive(Component)]
 pub struct Paddle;
 
 // The paddle's movement can influence where the ball goes
-#[derive(Resource, Deref, DerefMut)]
+#[derive(Resource, Default, Deref, DerefMut)]
 pub struct PaddleMomentum(f32);
 
-#[derive(Resource, Deref, DerefMut)]
+#[derive(Resource, PartialEq, Eq)]
 pub enum ControlStyle {
     Edges,
     Momentum,
     Unaltered,
+    Positional, // Taps step the paddle between fixed slots, see PositionalControl
+}
+
+// Tuning for ControlStyle::Positional, the slots are spread evenly across the paddle's range
+#[derive(Resource)]
+pub struct PositionalControl {
+    pub slots: usize,
+    pub lerp: f32, // Share of the remaining distance to the target slot covered each physics tick
+}
+
+impl Default for PositionalControl {
+    fn default() -> Self {
+        PositionalControl {
+            slots: DEFAULT_PADDLE_SLOTS,
+            lerp: DEFAULT_PADDLE_SLOT_LERP,
+        }
+    }
+}
+
+impl PositionalControl {
+    // The paddle x position of a slot, counted from the left
+    pub fn slot_x(&self, slot: usize) -> f32 {
+        if self.slots <= 1 {
+            return (PADDLE_LEFT_BOUND + PADDLE_RIGHT_BOUND) / 2.;
+        }
+        let t = slot.min(self.slots - 1) as f32 / (self.slots - 1) as f32;
+        PADDLE_LEFT_BOUND.lerp(PADDLE_RIGHT_BOUND, t)
+    }
+
+    pub fn center_slot(&self) -> usize {
+        self.slots / 2
+    }
+}
+
+// The slot the paddle moves towards under ControlStyle::Positional
+#[derive(Resource, Deref, DerefMut)]
+pub struct PaddleTarget(usize);
+
+impl Default for PaddleTarget {
+    fn default() -> Self {
+        PaddleTarget(PositionalControl::default().center_slot())
+    }
+}
+
+// Controls what happens when the paddle reaches the side walls, chosen in the settings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaddleEdgeMode {
+    #[default]
+    Clamp, // Stop at the walls
+    Wrap, // Arcade mode, leaving one side re-enters from the other
+}
+
+impl PaddleEdgeMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaddleEdgeMode::Clamp => "Stop at Walls",
+            PaddleEdgeMode::Wrap => "Wrap Around",
+        }
+    }
+
+    pub fn next(&self) -> PaddleEdgeMode {
+        match self {
+            PaddleEdgeMode::Clamp => PaddleEdgeMode::Wrap,
+            PaddleEdgeMode::Wrap => PaddleEdgeMode::Clamp,
+        }
+    }
+}
+
+// Which schedule moves the paddle. Frame samples input every rendered frame, which feels
+// more responsive on high refresh displays, while the ball physics always stay fixed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaddleTiming {
+    #[default]
+    Fixed, // Move with the 60hz physics tick
+    Frame, // Move every Update, scaled to the frame's share of a physics tick
+}
+
+impl PaddleTiming {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaddleTiming::Fixed => "Physics Tick",
+            PaddleTiming::Frame => "Every Frame",
+        }
+    }
+
+    pub fn next(&self) -> PaddleTiming {
+        match self {
+            PaddleTiming::Fixed => PaddleTiming::Frame,
+            PaddleTiming::Frame => PaddleTiming::Fixed,
+        }
+    }
+}
+
+// What steers the paddle. The keyboard and gamepad keep working under Keyboard, while Mouse has
+// the paddle follow the cursor, switched with PADDLE_INPUT_TOGGLE_KEY
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaddleInput {
+    #[default]
+    Keyboard,
+    Mouse,
+}
+
+impl PaddleInput {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaddleInput::Keyboard => "Keyboard",
+            PaddleInput::Mouse => "Mouse",
+        }
+    }
+
+    pub fn next(&self) -> PaddleInput {
+        match self {
+            PaddleInput::Keyboard => PaddleInput::Mouse,
+            PaddleInput::Mouse => PaddleInput::Keyboard,
+        }
+    }
+}
+
+// The cursor's x position in the world under PaddleInput::Mouse, None while it is outside the
+// window so the paddle stays put
+#[derive(Resource, Default, Deref)]
+pub struct MouseTarget(Option<f32>);
+
+// Limits on how many balls can be in play, and how fast balls split off from another ball move
+#[derive(Resource)]
+pub struct BallConfig {
+    pub max_balls: usize,
+    pub split_speed: SplitSpeed,
+}
+
+impl Default for BallConfig {
+    fn default() -> Self {
+        BallConfig {
+            max_balls: DEFAULT_MAX_BALLS,
+            split_speed: SplitSpeed::Shared,
+        }
+    }
+}
+
+impl BallConfig {
+    // The velocity for a ball split off from another, following the split speed policy
+    pub fn split_velocity(&self, velocity: Vec2) -> Vec2 {
+        match self.split_speed {
+            SplitSpeed::Shared => velocity,
+            SplitSpeed::Base => velocity.normalize_or_zero(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitSpeed {
+    Shared, // Split balls keep the speed of the ball they split from
+    Base,   // Split balls start over at the base speed
 }
 
 // Likewise we're likely to have multiple balls (lol)
 #[derive(Component)]
 pub struct Ball;
 
-// The paddle and ball will have a velocity, must be a component
+// The ball and anything else moving on its own, like power up pickups, will have a velocity
 // Deref and DerefMut make accessing the contained Vec2 convenient
 #[derive(Component, Deref, DerefMut)]
-pub struct Velocity(Vec2);
+pub struct Velocity(pub Vec2);
+
+// How far the ball moved on the last physics tick, so collisions can check the path it took
+#[derive(Component, Default, Deref)]
+pub struct LastMove(Vec2);
 
 // Everything but the score needs a collider
 #[derive(Component)]
 pub struct Collider;
 
-// Events are added to an EventWriter, read multiple places by EventReaders
-// each system that reads events tracks its processed events independently
-#[derive(Event, Default)]
-pub struct CollisionEvent;
-
 #[derive(Resource)]
-struct CollisionSound(Handle<AudioSource>);
+struct CollisionSound(OptionalSound);
 
 const COLLISION_SOUND_PATH: &str = "sounds/breakout_collision.ogg";
 
@@ -105,14 +389,14 @@ pub const FIXED_TIME_TICKS_PER_SECOND: f32 = 1.0 / 60.0;
 pub const PADDLE_DIST_FROM_BOTTOM_WALL: f32 = 60.0;
 pub const PADDLE_SIZE: Vec3 = Vec3::new(120., 20., 0.);
 pub const PADDLE_MAX_INFLUENCE: f32 = PI / 2.;
-const PADDLE_LEFT_BOUND: f32 =
-    walls::LEFT_WALL + walls::WALL_THICKNESS / 2.0 + PADDLE_SIZE.x / 2.0 + PADDLE_PADDING;
-const PADDLE_RIGHT_BOUND: f32 =
-    walls::RIGHT_WALL - walls::WALL_THICKNESS / 2.0 - PADDLE_SIZE.x / 2.0 - PADDLE_PADDING;
-const PADDLE_MAX_MOMENTUM: f32 = 7.;
+const PADDLE_LEFT_BOUND: f32 = paddle_left_bound(PADDLE_SIZE.x);
+const PADDLE_RIGHT_BOUND: f32 = paddle_right_bound(PADDLE_SIZE.x);
+pub const PADDLE_MAX_MOMENTUM: f32 = 7.;
 const PADDLE_LERP: f32 = 0.10;
 const PADDLE_SPEED: f32 = 500.0;
 const PADDLE_PADDING: f32 = 10.0;
+const DEFAULT_PADDLE_SLOTS: usize = 3;
+const DEFAULT_PADDLE_SLOT_LERP: f32 = 0.2;
 const PADDLE_STARTING_POSITION_X: f32 = 0.;
 const PADDLE_STARTING_POSITION_Y: f32 = walls::BOTTOM_WALL + PADDLE_DIST_FROM_BOTTOM_WALL;
 
@@ -120,14 +404,17 @@ const BALL_STARTING_POSITION: Vec3 = Vec3::new(-150., -50., 1.);
 const BALL_SIZE: Vec3 = Vec3::new(30., 30., 0.);
 const BALL_STARTING_SPEED: f32 = 300.;
 const BALL_SPEED: f32 = 300.;
-const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, -0.5);
+// Each level after the first speeds the ball up by this fraction of its first level speed
+const BALL_SPEED_PER_LEVEL: f32 = 0.1;
+// Swept brick collisions keep fast balls from skipping bricks, so this only keeps the ball fast
+// enough to stay fun but slow enough to react to
+const MAX_BALL_SPEED: f32 = 600.;
+const DEFAULT_MAX_BALLS: usize = 8;
+// The ball's vertical speed never drops below this fraction of its total speed
+pub const MIN_BALL_VERTICAL: f32 = 0.3;
 
 const SCOREBOARD_FONT_SIZE: f32 = 40.;
-const SCOREBOARD_TEXT_PADDING: f32 = 5.;
-const HEALTH_Y_POS: f32 = 650.;
 
-const PADDLE_COLOR: Color = Color::rgb(0.3, 0.3, 0.7);
-const BALL_COLOR: Color = Color::rgb(1., 0.5, 0.5);
 const TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 1.0);
 const SCORE_COLOR: Color = Color::rgb(1.0, 0.5, 0.5);
 
@@ -135,6 +422,11 @@ const PLAYER_STARTING_HEALTH: usize = 3;
 
 const BLINK_DURATION: f64 = 1.0;
 
+// Points for each brick hit, before the combo multiplier
+const BRICK_HIT_SCORE: usize = 10;
+
+pub const PADDLE_INPUT_TOGGLE_KEY: KeyCode = KeyCode::M;
+
 pub struct BreakoutGamePlugin;
 impl Plugin for BreakoutGamePlugin {
     fn build(&self, app: &mut App) {
@@ -145,32 +437,136 @@ impl Plugin for BreakoutGamePlugin {
             .insert_resource(Health(PLAYER_STARTING_HEALTH))
             .insert_resource(PaddleMomentum(0.))
             .insert_resource(ControlStyle::Edges)
-            .add_event::<CollisionEvent>()
-            .add_event::<GameStateTransition>()
-            .add_event::<PlayerMessage>()
+            .init_resource::<RunStats>()
+            .init_resource::<BrickDimensions>()
+            .init_resource::<HitStop>()
+            .init_resource::<HudLayout>()
+            .init_resource::<GameRng>()
+            .init_resource::<SlowBall>()
+            .init_resource::<BallSpeed>()
+            .init_resource::<Difficulty>()
+            .init_resource::<PaddleContacts>()
+            .init_resource::<BallConfig>()
+            .init_resource::<BallTints>()
+            .init_resource::<BonusRound>()
+            .init_resource::<PositionalControl>()
+            .init_resource::<PaddleTarget>()
+            .init_resource::<MouseTarget>()
+            .init_resource::<ReversedControls>()
+            .init_resource::<PaddleWidth>()
+            .init_resource::<GracePeriod>()
+            .init_resource::<FastestSpeed>()
+            .init_resource::<BrickDescent>()
+            .insert_resource(GameSettings::load())
+            .insert_resource(Profile::load())
+            .insert_resource(Levels::load())
+            .add_plugins(GameEventsPlugin)
+            .add_event::<ApplyPowerUp>()
+            .add_event::<SplitBall>()
             // .add_systems(Startup, (setup, walls::setup)) // TODO: Call these manually when AS::InGame && GS::Uninitialized
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (start_brick_descent, restart_finished_run),
+            )
             // Add frame-based updates that always run while AS::InGame
             .add_systems(
                 Update,
                 (
                     // Run these regardless of if the game is currently playing
                     transition_game,
-                    manage_game.after(transition_game),
+                    (apply_difficulty, manage_game, end_run_without_health)
+                        .chain()
+                        .after(transition_game),
                     game_aux_keys_handler.after(manage_game),
+                    toggle_paddle_input,
+                    fit_hud_to_window,
+                    apply_hud_layout.after(fit_hud_to_window),
+                    release_ball_tints,
+                    (show_preview_prompt, show_launch_prompt).after(transition_game),
+                    manage_bonus_round.after(transition_game),
+                    (reset_paddle_width, reset_reversed_controls),
+                    start_level_grace_period,
+                    show_grace_cue.after(start_level_grace_period),
+                    finish_level_speed,
+                    (add_hp_bars, add_strength_labels),
+                    clear_indestructible_bricks.before(transition_game),
+                    tick_speed_record_text,
+                    return_to_start,
+                    reset_brick_descent,
+                    release_held_balls,
+                    tick_bonus_round
+                        .after(manage_bonus_round)
+                        .run_if(resource_equals(CurrentState(GameState::BonusRound))),
                     // Run these only if the game is currently playing
                 )
                     .run_if(state_exists_and_equals(AppState::InGame)),
             )
-            // Add frame-based updates that only run while GS::Playing
+            // Add frame-based updates that only run while GS::Playing or GS::BonusRound
             .add_systems(
                 Update,
                 (
                     health_handler,
                     update_scoreboard,
+                    update_ball_count_display,
                     blink,
-                    play_collision_sound,
+                    freeze_paddle_on_trap,
+                    thaw_paddle,
+                    track_run_time,
+                    track_destroyed_bricks,
+                    trigger_hit_stop,
+                    tick_hit_stop.before(trigger_hit_stop),
+                    grant_mystery_power_ups,
+                    apply_power_ups.after(grant_mystery_power_ups),
+                    tick_power_ups,
+                    update_labeled_display::<Health, HealthDisplay>
+                        .after(apply_power_ups)
+                        .after(health_handler),
+                    serve_ball.after(health_handler).run_if(simulates_locally),
+                    tick_ball_elements,
+                    split_balls,
+                    update_paddle_target.run_if(control_style_is(ControlStyle::Positional)),
+                )
+                    .run_if(game_running),
+            )
+            .add_systems(
+                Update,
+                (
+                    animate_destroying_bricks,
+                    update_streak_labels,
+                    score_golden_bricks,
+                    cycle_brick_colors,
+                    reverse_controls_on_trap,
+                    tick_reversed_controls.before(reverse_controls_on_trap),
+                    show_reversed_cue.after(reverse_controls_on_trap),
+                    track_break_streaks,
+                    tick_break_streaks.before(track_break_streaks),
+                    tick_grace_period,
+                    update_speed_display,
+                    (update_hp_bars, update_strength_labels),
+                    fire_turret.run_if(simulates_locally),
+                    descend_bricks.run_if(resource_equals(CurrentState(GameState::Playing))),
+                    slide_descending_bricks,
+                    launch_held_ball.run_if(simulates_locally),
+                    carry_held_balls.after(launch_held_ball),
+                    update_mouse_target.run_if(paddle_input_is(PaddleInput::Mouse)),
+                    spawn_particle_bursts,
+                    update_particles,
+                )
+                    .run_if(game_running),
+            )
+            // Move the paddle every frame when the lower latency paddle timing is selected
+            .add_systems(
+                Update,
+                (
+                    update_paddle_momentum.before(update_paddle),
+                    update_paddle,
                 )
-                    .run_if(resource_equals(CurrentState(GameState::Playing))),
+                    .run_if(game_running)
+                    .run_if(hit_stop_inactive)
+                    .run_if(settled)
+                    .run_if(simulates_locally)
+                    .run_if(paddle_timing_is(PaddleTiming::Frame))
+                    .in_set(PhysicsSet),
             )
             // Add 60hz physics update cycle
             .insert_resource(FixedTime::new_from_secs(FIXED_TIME_TICKS_PER_SECOND))
@@ -178,30 +574,63 @@ impl Plugin for BreakoutGamePlugin {
                 FixedUpdate,
                 // Only run these if the game is playing
                 (
-                    // apply_velocity,
-                    move_ball,
-                    update_paddle_momentum.before(update_paddle),
-                    update_paddle,
-                    check_brick_collisions.after(apply_velocity),
-                    walls::check_bottom_wall_collision.after(apply_velocity),
-                    check_paddle_collision.after(apply_velocity),
-                    check_wall_collision.after(apply_velocity),
+                    (apply_velocity, move_ball).in_set(MoveSet),
+                    update_paddle_momentum
+                        .before(update_paddle)
+                        .run_if(paddle_timing_is(PaddleTiming::Fixed)),
+                    update_paddle.run_if(paddle_timing_is(PaddleTiming::Fixed)),
+                    check_brick_collisions.after(MoveSet),
+                    walls::check_drain_collision.after(MoveSet),
+                    check_paddle_collision.after(MoveSet),
+                    catch_balls.after(MoveSet).before(check_paddle_collision),
+                    check_wall_collision.after(MoveSet),
+                    walls::check_corner_collision.after(MoveSet),
+                    update_paddle_width.before(update_paddle),
                 )
-                    .run_if(resource_equals(CurrentState(GameState::Playing))),
+                    .run_if(game_running)
+                    .run_if(hit_stop_inactive)
+                    .run_if(settled)
+                    .run_if(simulates_locally)
+                    .in_set(PhysicsSet),
+            )
+            // Hears the collisions from the same tick, so the sound delay counts physics ticks
+            .add_systems(
+                FixedUpdate,
+                play_collision_sound.after(PhysicsSet).run_if(game_running),
             );
     }
 }
 
+// The systems that move the ball and paddle, so tools can hold them still
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhysicsSet;
+
+// The physics systems moving things by their Velocity, every collision check runs after these
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MoveSet;
+
+#[allow(clippy::too_many_arguments)]
 fn transition_game(
     mut game_state: ResMut<CurrentState>,
     mut game_transition_reqs: EventReader<GameStateTransition>,
     mut commands: Commands,
-    entities_q: Query<Entity>,
-    mut ball_q: Query<&mut Transform, (With<Ball>, Without<Paddle>)>,
-    mut paddle_q: Query<&mut Transform, (With<Paddle>, Without<Ball>)>,
+    // Every entity a restart clears, which leaves out the window so the game keeps running and the
+    // music stems so the music plays on into the new run. Only the roots, despawn_recursive takes
+    // their children with them and would otherwise be asked to despawn children that are already
+    // gone
+    entities_q: Query<Entity, (Without<Window>, Without<MusicStem>, Without<Parent>)>,
+    level_entities_q: Query<Entity, Or<(With<Brick>, With<Ball>)>>,
+    ball_q: Query<(Entity, &Transform), (With<Ball>, Without<Paddle>)>,
+    mut paddle_q: Query<(Entity, &mut Transform), (With<Paddle>, Without<Ball>)>,
     mut level: ResMut<Level>,
+    mut ball_speed: ResMut<BallSpeed>,
     mut brick_tracker: ResMut<BrickTracker>,
-    asset_server: Res<AssetServer>,
+    mut stats: ResMut<RunStats>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut app_state_msgs: EventWriter<AppStateTransition>,
+    level_spawner: LevelSpawner,
+    bonus_round: Res<BonusRound>,
+    settings: Res<GameSettings>,
 ) {
     for transition in game_transition_reqs.iter() {
         info!(
@@ -216,301 +645,1064 @@ fn transition_game(
                 }
                 **game_state = GameState::Uninitialized;
             }
-            GameStateTransition::ToPlayGame => **game_state = GameState::Playing,
+            GameStateTransition::ToPlayGame => {
+                **game_state = play_state(&game_state, &settings, bonus_round.is_active());
+            }
+            GameStateTransition::ToPreview => **game_state = GameState::Preview,
             GameStateTransition::ToHaltGame => **game_state = GameState::Paused,
             GameStateTransition::NextLevel => {
-                // TODO: Detect win, display different UI
+                stats.levels_cleared += 1;
+                if level_spawner.is_final_level(**level) {
+                    // Out of levels, the run is won. Halt the physics and show the win screen
+                    stats.won = true;
+                    **game_state = GameState::Paused;
+                    app_state_msgs.send(AppStateTransition::ToGameOver);
+                    continue;
+                }
                 **level += 1; // Advance the level
                               // Spawn the next level's bricks and update te brick tracker
-                **brick_tracker = spawn_bricks(&mut commands, **level, &asset_server);
+                **brick_tracker = level_spawner.spawn_bricks(&mut commands, **level);
+                ball_speed.set_level(**level);
 
-                // Reset the ball and paddle positions
-                let mut ball = ball_q.iter_mut().next().unwrap();
-                ball.translation = BALL_STARTING_POSITION;
-                let mut paddle = paddle_q.iter_mut().next().unwrap();
-                paddle.translation =
-                    Vec3::new(PADDLE_STARTING_POSITION_X, PADDLE_STARTING_POSITION_Y, 0.);
+                // Slide the ball and paddle back to their starting positions. A ball may have
+                // just fallen, serve_ball serves a fresh one once none are left
+                if ball_q.is_empty() {
+                    warn!("No ball to reset for the next level, one will be served");
+                }
+                for (ball, tform) in ball_q.iter() {
+                    commands.entity(ball).insert(ReturningToStart::new(
+                        tform.translation,
+                        BALL_STARTING_POSITION,
+                    ));
+                }
+                if paddle_q.is_empty() {
+                    warn!("No paddle to reset for the next level");
+                }
+                for (paddle, tform) in paddle_q.iter() {
+                    commands.entity(paddle).insert(ReturningToStart::new(
+                        tform.translation,
+                        Vec3::new(PADDLE_STARTING_POSITION_X, PADDLE_STARTING_POSITION_Y, 0.),
+                    ));
+                }
 
                 // Here would be where we reset score and/or health between levels
+                **game_state = level_start_state(&settings);
             }
+            // bonus_round.rs spawns the golden bricks and ends the round with NextLevel
+            GameStateTransition::ToBonusRound => **game_state = GameState::BonusRound,
             GameStateTransition::ToGameOver => {
-                // TODO: Add game over screen
-                panic!("You lost");
+                // Halt the physics and show the end-of-run stats screen
+                **game_state = GameState::Paused;
+                app_state_msgs.send(AppStateTransition::ToGameOver);
+            }
+            GameStateTransition::RestartLevel => {
+                // Clear the remaining bricks and every ball, serve_ball then serves a fresh one
+                for ent in level_entities_q.iter() {
+                    commands.entity(ent).despawn_recursive();
+                }
+                **brick_tracker = level_spawner.spawn_bricks(&mut commands, **level);
+                for (_, mut paddle) in paddle_q.iter_mut() {
+                    paddle.translation =
+                        Vec3::new(PADDLE_STARTING_POSITION_X, PADDLE_STARTING_POSITION_Y, 0.);
+                }
+                **game_state = level_start_state(&settings);
+            }
+            GameStateTransition::RestartGame => {
+                // Delete all entities and reset the run, manage_game then sets up the first level
+                // and resets the health
+                for ent in entities_q.iter() {
+                    commands.entity(ent).despawn_recursive();
+                }
+                **level = 1;
+                *ball_speed = BallSpeed::default();
+                scoreboard.score = 0;
+                *stats = RunStats::default();
+                **game_state = GameState::Uninitialized;
             }
         }
     }
 }
 
+// Sets up the health, ball speed, and paddle width of a run starting at the chosen difficulty,
+// right before manage_game sets up its first level
+fn apply_difficulty(
+    game_state: Res<CurrentState>,
+    difficulty: Res<Difficulty>,
+    mut health: ResMut<Health>,
+    mut ball_speed: ResMut<BallSpeed>,
+    mut paddle_width: ResMut<PaddleWidth>,
+    level: Res<Level>,
+    settings: Res<GameSettings>,
+) {
+    if **game_state != GameState::Uninitialized {
+        return;
+    }
+    let starting_health = difficulty.starting_health(PLAYER_STARTING_HEALTH);
+    **health = settings.hardcore.starting_health(starting_health);
+    *ball_speed = BallSpeed::new(**level, difficulty.ball_speed_factor());
+    *paddle_width = PaddleWidth::with_full_width(PADDLE_SIZE.x * difficulty.paddle_width_factor());
+}
+
+// A run that ended in a game over is still spawned, frozen behind the game over screen. Coming
+// back in, whether by restarting or through the main menu, starts a fresh run
+fn restart_finished_run(
+    game_state: Res<CurrentState>,
+    mut game_msgs: EventWriter<GameStateTransition>,
+) {
+    if **game_state != GameState::Uninitialized {
+        game_msgs.send(GameStateTransition::RestartGame);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn manage_game(
     game_state: Res<CurrentState>,
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut mats: ResMut<Assets<ColorMaterial>>,
+    mut balls: BallSpawner,
     asset_server: Res<AssetServer>,
     mut game_state_msgs: EventWriter<GameStateTransition>,
     // mut app_state_msgs: EventWriter<AppStateTransition>,
     mut brick_tracker: ResMut<BrickTracker>,
-    health: Res<Health>,
+    mut stats: ResMut<RunStats>,
     level: Res<Level>,
+    level_spawner: LevelSpawner,
+    hud_layout: Res<HudLayout>,
+    theme: Res<ThemeColors>,
+    mut rng: ResMut<GameRng>,
+    mut grace: ResMut<GracePeriod>,
+    settings: Res<GameSettings>,
 ) {
     match **game_state {
         GameState::Uninitialized => {
-            setup(&mut commands, &mut meshes, &mut mats, &asset_server);
-            **brick_tracker = spawn_bricks(&mut commands, **level, &asset_server);
-            game_state_msgs.send(GameStateTransition::ToPlayGame);
+            stats.hardcore = settings.hardcore.enabled;
+            grace.start(&settings);
+            setup(
+                &mut commands,
+                &mut balls,
+                &asset_server,
+                &hud_layout,
+                &theme,
+                &settings,
+                serve_direction(&mut rng, &settings),
+            );
+            **brick_tracker = level_spawner.spawn_bricks(&mut commands, **level);
+            game_state_msgs.send(if settings.gameplay.level_preview {
+                GameStateTransition::ToPreview
+            } else {
+                GameStateTransition::ToPlayGame
+            });
         }
         GameState::Playing => {
-            if **brick_tracker == 0 {
-                game_state_msgs.send(GameStateTransition::NextLevel)
-            }
-            if **health == 0 {
-                game_state_msgs.send(GameStateTransition::ToGameOver)
+            // Infinite runs bring down more bricks instead, see infinite::advance_infinite_rows
+            if **brick_tracker == 0 && !level_spawner.is_endless() {
+                game_state_msgs.send(if settings.gameplay.bonus_round {
+                    GameStateTransition::ToBonusRound
+                } else {
+                    GameStateTransition::NextLevel
+                })
             }
         }
         _ => {}
     }
 }
 
+// Ends the run with the game over screen once the player is out of health, in a level or in the
+// bonus round, which otherwise ends on its own timer
+fn end_run_without_health(
+    game_state: Res<CurrentState>,
+    health: Res<Health>,
+    mut game_state_msgs: EventWriter<GameStateTransition>,
+) {
+    let in_play = matches!(**game_state, GameState::Playing | GameState::BonusRound);
+    if in_play && **health == 0 {
+        game_state_msgs.send(GameStateTransition::ToGameOver);
+    }
+}
+
 fn setup(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    mats: &mut ResMut<Assets<ColorMaterial>>,
+    balls: &mut BallSpawner,
     asset_server: &Res<AssetServer>,
+    hud_layout: &HudLayout,
+    theme: &ThemeColors,
+    settings: &GameSettings,
+    serve: Vec2,
 ) {
     info!("Start breaker setup");
     // Create a default camera + all of its systems
-    commands.spawn(Camera2dBundle::default());
+    commands.spawn(game_camera());
 
-    let ball_collision_sound = asset_server.load(COLLISION_SOUND_PATH);
+    let ball_collision_sound = OptionalSound::load(asset_server, COLLISION_SOUND_PATH);
     commands.insert_resource(CollisionSound(ball_collision_sound));
 
     // Create the paddle
     commands.spawn((
-        SpriteBundle {
-            transform: Transform {
-                translation: Vec3::new(PADDLE_STARTING_POSITION_X, PADDLE_STARTING_POSITION_Y, 0.),
-                scale: PADDLE_SIZE,
-                ..default()
-            },
-            sprite: Sprite {
-                color: PADDLE_COLOR,
-                ..default()
-            },
-            ..default()
-        },
+        paddle_sprite(theme.paddle),
         Paddle,
         Collider,
         Name::new("Paddle"),
     ));
 
+    if settings.gameplay.turret {
+        commands.spawn(turret_bundle(theme.paddle));
+    }
+
     // Create the ball
-    commands.spawn((
-        MaterialMesh2dBundle {
-            mesh: meshes.add(shape::Circle::default().into()).into(),
-            material: mats.add(ColorMaterial::from(BALL_COLOR)),
-            transform: Transform::from_translation(BALL_STARTING_POSITION).with_scale(BALL_SIZE),
-            ..default()
-        },
-        Ball,
-        // Ball doesn't get a collider, collisions are detected manually but with other colliders
-        Velocity(INITIAL_BALL_DIRECTION.normalize()),
-        Name::new("Ball"),
-    ));
+    balls.spawn_serve(commands, theme.ball, serve);
 
     // Create scoreboard
-    commands.spawn(ScoreboardBundle::new(
-        SCOREBOARD_FONT_SIZE,
-        TEXT_COLOR,
-        SCORE_COLOR,
-        "Score: ",
-        Vec2::new(SCOREBOARD_TEXT_PADDING, SCOREBOARD_TEXT_PADDING),
-        Some("0"),
+    commands.spawn((
+        ScoreboardBundle::new(
+            SCOREBOARD_FONT_SIZE,
+            TEXT_COLOR,
+            SCORE_COLOR,
+            "Score: ",
+            hud_layout.score,
+            Some("0"),
+        ),
+        HudElement::Score,
     ));
 
     // Create Health tracker
-    commands.spawn(HealthDisplayBundle::new(
-        SCOREBOARD_FONT_SIZE,
-        TEXT_COLOR,
-        SCORE_COLOR,
-        "Health: ",
-        Vec2::new(SCOREBOARD_TEXT_PADDING, HEALTH_Y_POS),
-        Some("0"),
+    commands.spawn((
+        HealthDisplayBundle::new(
+            SCOREBOARD_FONT_SIZE,
+            TEXT_COLOR,
+            SCORE_COLOR,
+            "Health: ",
+            hud_layout.health,
+            Some("0"),
+        ),
+        HudElement::Health,
     ));
-    walls::setup(commands);
-}
 
-// Updates the paddle's momentum param based on user input. Applies a force to the left with A/<- and to the right with D/<-
-fn update_paddle_momentum(
-    mut paddle_momentum: ResMut<PaddleMomentum>,
-    keyboard_input: Res<Input<KeyCode>>,
-    time_step: Res<FixedTime>,
-) {
-    let left = keyboard_input.pressed(KeyCode::A) || keyboard_input.pressed(KeyCode::Left);
-    let right = keyboard_input.pressed(KeyCode::D) || keyboard_input.pressed(KeyCode::Right);
+    // Create the balls in play tracker
+    commands.spawn((
+        BallCountDisplayBundle::new(
+            SCOREBOARD_FONT_SIZE,
+            TEXT_COLOR,
+            SCORE_COLOR,
+            "Balls: ",
+            hud_layout.ball_count,
+            Some("1"),
+        ),
+        HudElement::BallCount,
+    ));
 
-    let dir = match (left, right) {
-        (true, false) => -1.,
-        (false, true) => 1.,
-        _ => 0.,
-    };
+    // Create the level indicator
+    commands.spawn((
+        LevelDisplayBundle::new(
+            SCOREBOARD_FONT_SIZE,
+            TEXT_COLOR,
+            SCORE_COLOR,
+            "Level: ",
+            hud_layout.level,
+            Some("1"),
+        ),
+        HudElement::Level,
+    ));
+    commands.spawn(speed_display(hud_layout.speed));
+    if settings.combo.meter {
+        commands
+            .spawn(combo_meter(hud_layout.combo))
+            .with_children(|meter| {
+                meter.spawn(combo_meter_fill());
+            });
+    }
+    if settings.hardcore.enabled {
+        commands.spawn(hardcore_flag(hud_layout.hardcore));
+    }
+    if settings.gameplay.wall_bounce_bonus {
+        commands.spawn(wall_bonus_display(hud_layout.wall_bonus));
+    }
+    walls::setup(
+        commands,
+        theme.wall,
+        settings.gameplay.arena_shape,
+        settings.gameplay.drain_layout,
+    );
+}
 
-    // Change the momentum towards the movement direction, scaled by speed and time
-    // PADDLE_LERP basically gives the paddle high mass close to 0 and low mass close to 1
-    **paddle_momentum = paddle_momentum
-        .lerp(
-            dir * PADDLE_SPEED * time_step.period.as_secs_f32(),
-            PADDLE_LERP,
-        )
-        .clamp(-PADDLE_MAX_MOMENTUM, PADDLE_MAX_MOMENTUM);
+// Run condition selecting the schedule the paddle systems run in
+fn paddle_timing_is(timing: PaddleTiming) -> impl Fn(Res<GameSettings>) -> bool {
+    move |settings: Res<GameSettings>| settings.controls.paddle_timing == timing
 }
 
-// Moves the paddle based on the current momentum value
-fn update_paddle(
-    mut paddle_q: Query<&mut Transform, With<Paddle>>,
-    paddle_momentum: Res<PaddleMomentum>,
-) {
-    let mut paddle_tform = paddle_q.single_mut();
-    let start = paddle_tform.translation.x;
-    let x = start + **paddle_momentum;
-    let x = x.clamp(PADDLE_LEFT_BOUND, PADDLE_RIGHT_BOUND);
+// Run condition for systems specific to one paddle input
+fn paddle_input_is(input: PaddleInput) -> impl Fn(Res<GameSettings>) -> bool {
+    move |settings: Res<GameSettings>| settings.controls.paddle_input == input
+}
 
-    let delta = x - start; // Calculate delta off actual movement since paddle is bounded by walls
-    paddle_tform.translation.x = x;
+// Run condition for systems specific to one control style
+fn control_style_is(style: ControlStyle) -> impl Fn(Res<ControlStyle>) -> bool {
+    move |control_style: Res<ControlStyle>| *control_style == style
 }
 
-fn apply_velocity(
-    mut tform_vel_q: Query<(&mut Transform, &Velocity), Without<Ball>>,
-    time_step: Res<FixedTime>,
-) {
-    for (mut tform, velocity) in &mut tform_vel_q {
-        tform.translation.x += velocity.x * time_step.period.as_secs_f32();
-        tform.translation.y += velocity.y * time_step.period.as_secs_f32();
+// How many physics ticks the current paddle update covers. Momentum is always kept in units of
+// distance per physics tick, so ball_influence_momentum sees the same values in either timing
+fn paddle_ticks(settings: &GameSettings, time: &Time, time_step: &FixedTime) -> f32 {
+    match settings.controls.paddle_timing {
+        PaddleTiming::Fixed => 1.,
+        PaddleTiming::Frame => time.delta_seconds() / time_step.period.as_secs_f32(),
     }
 }
 
-// The ball moves differently from other objects with velocity, since we do more manual
-// control of where it goes. It has a given velocity which is treated as a unit vector
-// and is scaled by the speed and duration of this physics tick
-fn move_ball(
-    mut ball_tform_vel: Query<(&mut Transform, &Velocity), With<Ball>>,
-    time_step: Res<FixedTime>,
-) {
-    let (mut ball_t, ball_v) = ball_tform_vel.single_mut();
-    let movement: Vec2 = ball_v.0 * time_step.period.as_secs_f32() * BALL_SPEED;
-    ball_t.translation += movement.extend(0.);
+// The paddle's sprite at its starting position
+pub fn paddle_sprite(color: Color) -> SpriteBundle {
+    SpriteBundle {
+        transform: Transform {
+            translation: Vec3::new(PADDLE_STARTING_POSITION_X, PADDLE_STARTING_POSITION_Y, 0.),
+            scale: PADDLE_SIZE,
+            ..default()
+        },
+        sprite: Sprite {
+            color,
+            ..default()
+        },
+        ..default()
+    }
 }
 
-// Checks for collsions with bricks
-fn check_brick_collisions(
-    mut commands: Commands,
-    mut scoreboard: ResMut<Scoreboard>,
-    mut ball_q: Query<(&mut Velocity, &Transform), With<Ball>>,
-    mut collider_q: Query<(Entity, &Transform, &mut Brick, &mut Sprite), With<Collider>>,
-    mut collision_events: EventWriter<CollisionEvent>,
-    mut brick_tracker: ResMut<BrickTracker>,
-) {
-    let (mut ball_v, ball_t) = ball_q.single_mut();
-    let ball_size = ball_t.scale.truncate();
+// Everything needed to spawn a ball, grouped so the systems that spawn balls stay under Bevy's
+// system parameter limit
+#[derive(SystemParam)]
+pub struct BallSpawner<'w> {
+    meshes: ResMut<'w, Assets<Mesh>>,
+    mats: ResMut<'w, Assets<ColorMaterial>>,
+    config: Res<'w, BallConfig>,
+    tints: ResMut<'w, BallTints>,
+    settings: Res<'w, GameSettings>,
+}
 
-    for (collider_ent, tform, mut brick, mut sprite) in collider_q.iter_mut() {
-        let collision = collide(
-            ball_t.translation,
-            ball_size,
-            tform.translation,
-            tform.scale.truncate(),
-        );
-        if let Some(collision) = collision {
-            collision_events.send(CollisionEvent);
-            brick_collision(
-                &mut scoreboard,
-                &mut commands,
-                &mut brick_tracker,
-                collider_ent,
-                &mut brick,
-                &mut sprite,
-            );
-            ball_ricochet(collision, &mut ball_v);
+impl BallSpawner<'_> {
+    pub fn config(&self) -> &BallConfig {
+        &self.config
+    }
+
+    // Spawns a ball with its own material, so effects can tint one ball without affecting the
+    // others. Every ball is spawned through here, so nothing is spawned once max_balls are already
+    // in play. Each ball also claims a tint, shown instead of the color when ball tints are on
+    pub fn spawn_ball(
+        &mut self,
+        commands: &mut Commands,
+        in_play: usize,
+        color: Color,
+        position: Vec3,
+        velocity: Vec2,
+    ) -> Option<Entity> {
+        if in_play >= self.config.max_balls {
+            return None;
         }
+        let mut ball = commands.spawn((
+            Ball,
+            // Ball doesn't get a collider, collisions are detected manually but with other colliders
+            Velocity(velocity),
+            LastMove::default(),
+            BreakStreak::default(),
+            Name::new("Ball"),
+        ));
+        let tint = self.tints.claim(ball.id());
+        ball.insert((
+            MaterialMesh2dBundle {
+                mesh: self.meshes.add(shape::Circle::default().into()).into(),
+                material: self
+                    .mats
+                    .add(ColorMaterial::from(ball_color(Some(&tint), &self.settings, color))),
+                transform: Transform::from_translation(position).with_scale(BALL_SIZE),
+                ..default()
+            },
+            tint,
+        ));
+        Some(ball.id())
     }
-}
 
-fn check_paddle_collision(
-    mut ball_q: Query<(&mut Velocity, &Transform), With<Ball>>,
+    // Spawns the ball for a serve in the given direction. Docked serves wait on the paddle with no
+    // velocity, so the ball can't fall, until the launch key sends it off
+    pub fn spawn_serve(
+        &mut self,
+        commands: &mut Commands,
+        color: Color,
+        direction: Vec2,
+    ) -> Option<Entity> {
+        if !self.settings.gameplay.docked_serve {
+            return self.spawn_ball(commands, 0, color, BALL_STARTING_POSITION, direction);
+        }
+        let ball = self.spawn_ball(commands, 0, color, BALL_STARTING_POSITION, Vec2::ZERO)?;
+        commands.entity(ball).insert(Held::docked(direction));
+        Some(ball)
+    }
+}
+
+// Serves a fresh ball once every ball has fallen, as long as the player has health left. A ball
+// waiting docked on the paddle is still a ball, so it is never served twice.
+// Must run after health_handler so the losing ball's health is already deducted
+#[allow(clippy::too_many_arguments)]
+fn serve_ball(
+    mut commands: Commands,
+    mut balls: BallSpawner,
+    ball_q: Query<(), With<Ball>>,
+    health: Res<Health>,
+    theme: Res<ThemeColors>,
+    mut rng: ResMut<GameRng>,
+    mut grace: ResMut<GracePeriod>,
+    settings: Res<GameSettings>,
+) {
+    if !ball_q.is_empty() || **health == 0 {
+        return;
+    }
+    let ball = balls.spawn_serve(
+        &mut commands,
+        theme.ball,
+        serve_direction(&mut rng, &settings),
+    );
+    let Some(ball) = ball else {
+        warn!("Failed to serve a ball, the ball limit allows none");
+        return;
+    };
+    // The new ball blinks along with the paddle's invulnerability window
+    grace.start(&settings);
+    commands.entity(ball).insert(Blinking(Timer::new(
+        Duration::from_secs_f64(BLINK_DURATION),
+        TimerMode::Once,
+    )));
+}
+
+// Picks a random upward direction within the configured serve cone, so no two serves are alike
+// NOT A SYSTEM
+pub fn serve_direction(rng: &mut GameRng, settings: &GameSettings) -> Vec2 {
+    let half_cone = (settings.gameplay.serve_cone_degrees / 2.).clamp(0., 90.).to_radians();
+    let angle = if half_cone > 0. {
+        rng.gen_range(-half_cone..=half_cone)
+    } else {
+        0.
+    };
+    // Angles are measured from straight up, so the serve never heads down
+    let mut direction = Velocity(Vec2::new(angle.sin(), angle.cos()));
+    enforce_min_vertical(&mut direction);
+    direction.0
+}
+
+// Steepens a velocity whose vertical part is too small a share of its speed, keeping the speed and
+// the direction of travel on both axes. Stops the ball from getting stuck in near horizontal paths
+pub fn enforce_min_vertical(ball_v: &mut Velocity) {
+    let speed = ball_v.length();
+    if speed == 0. || ball_v.y.abs() >= MIN_BALL_VERTICAL * speed {
+        return;
+    }
+    let y = MIN_BALL_VERTICAL * if ball_v.y.is_sign_negative() { -1. } else { 1. };
+    let x = (1. - MIN_BALL_VERTICAL * MIN_BALL_VERTICAL).sqrt() * ball_v.x.signum();
+    ball_v.0 = Vec2::new(x, y) * speed;
+}
+
+// Steps the positional control target one slot left or right, once per tap of the move keys.
+// Taps are read every frame, since a FixedUpdate tick can see the same press twice or not at all
+fn update_paddle_target(
+    mut target: ResMut<PaddleTarget>,
+    positional: Res<PositionalControl>,
+    keyboard_input: Res<Input<KeyCode>>,
+    frozen_q: Query<(), (With<Paddle>, With<Frozen>)>,
+    reversed: Res<ReversedControls>,
+    settings: Res<GameSettings>,
+) {
+    if !frozen_q.is_empty() {
+        return;
+    }
+    let bindings = &settings.controls.bindings;
+    let (left, right) = reversed.apply(
+        bindings.just_pressed(Action::MoveLeft, &keyboard_input),
+        bindings.just_pressed(Action::MoveRight, &keyboard_input),
+    );
+
+    match (left, right) {
+        (true, false) => **target = target.saturating_sub(1),
+        (false, true) => **target = (**target + 1).min(positional.slots.saturating_sub(1)),
+        _ => {}
+    }
+}
+
+// Follows the cursor for PaddleInput::Mouse. Read every frame, like the positional target, so the
+// physics tick always steers towards where the cursor is now
+fn update_mouse_target(
+    mut target: ResMut<MouseTarget>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+) {
+    let (Ok(window), Ok((camera, camera_tform))) = (window_q.get_single(), camera_q.get_single())
+    else {
+        return;
+    };
+    target.0 = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tform, cursor))
+        .map(|cursor| cursor.x);
+}
+
+// Swaps between steering the paddle with the keyboard and with the mouse, saving the choice
+fn toggle_paddle_input(
+    keys: Res<Input<KeyCode>>,
+    mut settings: ResMut<GameSettings>,
+    mut target: ResMut<MouseTarget>,
+) {
+    if !keys.just_pressed(PADDLE_INPUT_TOGGLE_KEY) {
+        return;
+    }
+    let input = settings.controls.paddle_input.next();
+    settings.controls.paddle_input = input;
+    settings.save();
+    *target = MouseTarget::default(); // Wait for the cursor to be read rather than jump
+    info!("Paddle input changed to {input:?}");
+}
+
+// Updates the paddle's momentum param based on user input. Applies a force in the direction of the held move key
+#[allow(clippy::too_many_arguments)]
+pub fn update_paddle_momentum(
+    mut paddle_momentum: ResMut<PaddleMomentum>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad: GamepadInput,
+    time: Res<Time>,
+    time_step: Res<FixedTime>,
+    settings: Res<GameSettings>,
+    frozen_q: Query<(), (With<Paddle>, With<Frozen>)>,
+    control_style: Res<ControlStyle>,
+    positional: Res<PositionalControl>,
+    target: Res<PaddleTarget>,
+    mouse_target: Res<MouseTarget>,
+    paddle_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    reversed: Res<ReversedControls>,
+) {
+    if settings.controls.paddle_input == PaddleInput::Mouse {
+        // Momentum is the whole distance to the cursor, so the paddle lands on it this update
+        // and the ball influence math sees how fast the mouse moved
+        let distance = match (paddle_q.get_single(), **mouse_target) {
+            (Ok(paddle_t), Some(x)) if frozen_q.is_empty() => {
+                let width = paddle_t.scale.x;
+                x.clamp(paddle_left_bound(width), paddle_right_bound(width))
+                    - paddle_t.translation.x
+            }
+            _ => 0.,
+        };
+        **paddle_momentum = distance / paddle_ticks(&settings, &time, &time_step);
+        return;
+    }
+
+    if *control_style == ControlStyle::Positional {
+        // Momentum is derived from the distance left to the target, so the ball influence
+        // math works the same as with the other control styles
+        let distance = match paddle_q.get_single() {
+            Ok(paddle_t) if frozen_q.is_empty() => {
+                positional.slot_x(**target) - paddle_t.translation.x
+            }
+            _ => 0.,
+        };
+        **paddle_momentum =
+            (distance * positional.lerp).clamp(-PADDLE_MAX_MOMENTUM, PADDLE_MAX_MOMENTUM);
+        return;
+    }
+
+    // Reverse bricks swap the directions for a while
+    let bindings = &settings.controls.bindings;
+    let (dpad_left, dpad_right) = gamepad.dpad();
+    let (left, right) = reversed.apply(
+        bindings.pressed(Action::MoveLeft, &keyboard_input) || dpad_left,
+        bindings.pressed(Action::MoveRight, &keyboard_input) || dpad_right,
+    );
+    let stick = if reversed.active() {
+        -gamepad.stick_x()
+    } else {
+        gamepad.stick_x()
+    };
+
+    let dir = match (left, right) {
+        // A frozen paddle ignores input, letting the momentum decay to 0
+        _ if !frozen_q.is_empty() => 0.,
+        (true, false) => -1.,
+        (false, true) => 1.,
+        // The stick steers continuously, a partial push moves the paddle slower
+        _ => stick,
+    };
+
+    // Change the momentum towards the movement direction, scaled by speed and time
+    // PADDLE_LERP basically gives the paddle high mass close to 0 and low mass close to 1
+    // Compounding the lerp over partial ticks keeps acceleration the same at any frame rate
+    let ticks = paddle_ticks(&settings, &time, &time_step);
+    let lerp = 1. - (1. - PADDLE_LERP).powf(ticks);
+    **paddle_momentum = paddle_momentum
+        .lerp(dir * PADDLE_SPEED * time_step.period.as_secs_f32(), lerp)
+        .clamp(-PADDLE_MAX_MOMENTUM, PADDLE_MAX_MOMENTUM);
+}
+
+// Moves the paddle based on the current momentum value. Every local paddle moves together, and
+// there may be none yet in the frame between setup and the first physics tick
+fn update_paddle(
+    mut paddle_q: Query<&mut Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    paddle_momentum: Res<PaddleMomentum>,
+    time: Res<Time>,
+    time_step: Res<FixedTime>,
+    settings: Res<GameSettings>,
+) {
+    // Per frame movement takes smaller steps, so the paddle never jumps further between ball
+    // collision checks than it would in the fixed timing
+    let movement = **paddle_momentum * paddle_ticks(&settings, &time, &time_step);
+    for mut paddle_tform in paddle_q.iter_mut() {
+        paddle_tform.translation.x = bound_paddle_x(
+            paddle_tform.translation.x + movement,
+            paddle_tform.scale.x,
+            settings.gameplay.paddle_edge,
+        );
+    }
+}
+
+// The furthest left a paddle of the given width can be centered
+const fn paddle_left_bound(width: f32) -> f32 {
+    walls::LEFT_WALL + walls::WALL_THICKNESS / 2.0 + width / 2.0 + PADDLE_PADDING
+}
+
+const fn paddle_right_bound(width: f32) -> f32 {
+    walls::RIGHT_WALL - walls::WALL_THICKNESS / 2.0 - width / 2.0 - PADDLE_PADDING
+}
+
+// Keeps a paddle x position between the walls according to the edge mode. The bounds follow the
+// paddle's width, since it can shrink and grow during a level
+pub fn bound_paddle_x(x: f32, width: f32, edge_mode: PaddleEdgeMode) -> f32 {
+    match edge_mode {
+        PaddleEdgeMode::Clamp => x.clamp(paddle_left_bound(width), paddle_right_bound(width)),
+        PaddleEdgeMode::Wrap => wrap_paddle_x(x, width),
+    }
+}
+
+// Wraps an x position into the paddle's bounds, moving past the right bound re-enters at the left.
+// The paddle is never drawn straddling both edges, so there is no mid-wrap contact to resolve
+pub fn wrap_paddle_x(x: f32, width: f32) -> f32 {
+    let left = paddle_left_bound(width);
+    let span = paddle_right_bound(width) - left;
+    left + (x - left).rem_euclid(span)
+}
+
+// Moves everything with a Velocity except the balls, by that many pixels per second
+fn apply_velocity(
+    mut tform_vel_q: Query<(&mut Transform, &Velocity), Without<Ball>>,
+    time_step: Res<FixedTime>,
+) {
+    for (mut tform, velocity) in &mut tform_vel_q {
+        tform.translation.x += velocity.x * time_step.period.as_secs_f32();
+        tform.translation.y += velocity.y * time_step.period.as_secs_f32();
+    }
+}
+
+// The ball moves differently from other objects with velocity, since we do more manual
+// control of where it goes. It has a given velocity which is treated as a unit vector
+// and is scaled by the speed and duration of this physics tick
+fn move_ball(
+    mut ball_q: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut LastMove,
+            Option<&BallElement>,
+        ),
+        With<Ball>,
+    >,
+    time_step: Res<FixedTime>,
+    slow_ball: Res<SlowBall>,
+    ball_speed: Res<BallSpeed>,
+    settings: Res<GameSettings>,
+) {
+    let gravity = settings.gameplay.ball_gravity;
+    let speed = if slow_ball.active() {
+        **ball_speed * SLOW_BALL_FACTOR
+    } else {
+        **ball_speed
+    };
+    for (mut ball_t, mut ball_v, mut last_move, element) in ball_q.iter_mut() {
+        if gravity != 0. {
+            apply_gravity(&mut ball_v, gravity, time_step.period.as_secs_f32());
+        }
+        // Catches shallow paths from any source, so the ball can't bounce between the walls forever
+        enforce_min_vertical(&mut ball_v);
+        let speed = match element {
+            Some(element) if element.is(Element::Ice) => speed * ICE_SPEED_FACTOR,
+            _ => speed,
+        };
+        let movement: Vec2 = ball_v.0 * time_step.period.as_secs_f32() * speed;
+        ball_t.translation += movement.extend(0.);
+        last_move.0 = movement;
+    }
+}
+
+// Bends the ball's path downward. The speed is kept constant by renormalizing, gravity only
+// changes the direction, so a rally never speeds up or slows down the ball
+// NOT A SYSTEM
+pub fn apply_gravity(ball_v: &mut Velocity, gravity: f32, dt: f32) {
+    let speed = ball_v.length();
+    ball_v.y -= gravity * speed * dt;
+    ball_v.0 = ball_v.normalize_or_zero() * speed;
+    // Gravity alone would flatten a rising ball's arc until it is nearly horizontal. An arc that
+    // gets too flat crosses straight over to falling, rather than being held rising forever
+    if gravity > 0. && ball_v.y.abs() < MIN_BALL_VERTICAL * speed {
+        ball_v.y = -ball_v.y.abs();
+    }
+    enforce_min_vertical(ball_v);
+}
+
+// The normal pointing from the brick towards a ball at ball_pos, if they overlap. It is scaled by
+// how much their AABBs overlap, so summing it over several bricks favors the ones hit squarely
+// NOT A SYSTEM
+fn brick_contact(
+    ball_pos: Vec2,
+    ball_size: Vec2,
+    tform: &Transform,
+    shape: &BrickShape,
+) -> Option<Vec2> {
+    let brick_pos = tform.translation.truncate();
+    let brick_size = tform.scale.truncate();
+    let normal = match shape {
+        BrickShape::Rectangle => collide(
+            ball_pos.extend(0.),
+            ball_size,
+            brick_pos.extend(0.),
+            brick_size,
+        )
+        .map(side_normal),
+        BrickShape::Circle => {
+            let radius = brick_size.x / 2.;
+            circle_contact_normal(ball_pos, ball_size.x / 2., brick_pos, radius)
+        }
+    }?;
+    Some(normal * overlap_area(ball_pos, ball_size, brick_pos, brick_size))
+}
+
+// Checks for collsions with bricks
+#[allow(clippy::too_many_arguments)]
+fn check_brick_collisions(
+    mut commands: Commands,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut ball_q: Query<
+        (
+            Entity,
+            &mut Velocity,
+            &mut Transform,
+            &LastMove,
+            Option<&mut BallElement>,
+            &Handle<ColorMaterial>,
+            Option<&BreakStreak>,
+            Option<&mut GhostBall>,
+            Option<&StrongBall>,
+        ),
+        With<Ball>,
+    >,
     mut collider_q: Query<
-        &Transform,
         (
-            With<Collider>,
-            With<Paddle>,
-            Without<Brick>,
-            Without<walls::BottomWall>,
+            Entity,
+            &Transform,
+            &mut Brick,
+            &BrickKind,
+            &BrickShape,
+            &mut Sprite,
+            Option<&mut StreakProgress>,
         ),
+        With<Collider>,
     >,
     mut collision_events: EventWriter<CollisionEvent>,
-    paddle_momentum: Res<PaddleMomentum>,
-    control_style: Res<ControlStyle>,
+    mut destroyed_events: EventWriter<BrickDestroyed>,
+    mut split_events: EventWriter<SplitBall>,
+    mut brick_tracker: ResMut<BrickTracker>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    paddle_contacts: Res<PaddleContacts>,
+    mut stats: ResMut<RunStats>,
+    mut rng: ResMut<GameRng>,
+    settings: Res<GameSettings>,
 ) {
-    let (mut ball_v, ball_t) = ball_q.single_mut();
-    let ball_size = ball_t.scale.truncate();
+    let jitter = settings.bounce_jitter_degrees();
+    let palette = settings.visual.brick_palette;
+    // Destroyed explosive bricks, with the reach of their blast and the ball that set them off
+    let mut explosions = Vec::new();
+    for (
+        ball,
+        mut ball_v,
+        mut ball_t,
+        last_move,
+        mut element,
+        ball_mat,
+        break_streak,
+        mut ghost,
+        strong,
+    ) in ball_q.iter_mut()
+    {
+        let ball_size = ball_t.scale.truncate();
+        let damage = ball_damage(strong);
+        // A fast ball is put back where it first touched a brick this tick, rather than skipping
+        // over it. Bricks a ghost is already passing through don't stop it
+        let hits_brick = |pos: Vec2| {
+            collider_q
+                .iter()
+                .any(|(ent, tform, brick, _, shape, _, _)| {
+                    let passing = ghost.as_ref().is_some_and(|g| g.is_passing(ent));
+                    **brick > 0 && !passing && brick_contact(pos, ball_size, tform, shape).is_some()
+                })
+        };
+        let max_step = ball_size.min_element() / 2.;
+        let end = ball_t.translation.truncate();
+        if let Some(pos) = first_swept_contact(end, **last_move, max_step, hits_brick) {
+            ball_t.translation = pos.extend(ball_t.translation.z);
+        }
+        // Fire balls and balls breaking through pierce through bricks instead of bouncing off them
+        let piercing = element.as_ref().is_some_and(|e| e.is(Element::Fire))
+            || break_streak.is_some_and(BreakStreak::breaking_through);
+        // Ghost balls tunnel through bricks too, but only weaken them like a normal hit
+        let ghosting = ghost.is_some();
+        let mut touched = Vec::new();
+        let ball_pos = ball_t.translation.truncate();
+
+        // Every brick the ball overlaps is hit, but the ball bounces only once, off the combined
+        // normal of the bricks it bounces off. Two bricks side by side then bounce it like one
+        // flat wall, rather than flipping it once for each brick
+        let mut bounced = false;
+        let mut bounce_normal = Vec2::ZERO;
+        for (_, tform, brick, kind, shape, _, _) in collider_q.iter() {
+            // Indestructible bricks stop even piercing balls, only ghosts pass through them
+            let piercing = piercing && kind.needed_to_clear();
+            if **brick == 0 || piercing || ghosting {
+                continue;
+            }
+            if let Some(normal) = brick_contact(ball_pos, ball_size, tform, shape) {
+                bounced = true;
+                bounce_normal += normal;
+            }
+        }
+        if bounced {
+            ball_reflect(bounce_normal.normalize_or_zero(), &mut ball_v);
+            jitter_bounce(&mut ball_v, jitter, &mut rng);
+        }
 
-    for tform in collider_q.iter_mut() {
-        let collision = collide(
-            ball_t.translation,
-            ball_size,
-            tform.translation,
-            tform.scale.truncate(),
-        );
-        if let Some(collision) = collision {
-            collision_events.send_default();
-            // ball_ricochet mutates ball_v to be the already reflected vector
-            ball_ricochet(collision, &mut ball_v);
-            if let Collision::Bottom | Collision::Top = collision {
-                match control_style {
-                    ControlStyle::Edges => ball_influence_edges(collision, &mut ball_v, &tform),
-                    ControlStyle::Momentum => {
-                        ball_influence_momentum(collision, &mut ball_v, &paddle_momentum, &tform)
+        for (collider_ent, tform, mut brick, kind, shape, mut sprite, streak) in
+            collider_q.iter_mut()
+        {
+            if **brick == 0 {
+                continue; // Already destroyed by another ball this tick, despawn is pending
+            }
+            let piercing = piercing && kind.needed_to_clear();
+            let brick_pos = tform.translation.truncate();
+            // The contact point and collision side, if the ball touched this brick
+            let contact = match shape {
+                BrickShape::Rectangle => collide(
+                    ball_t.translation,
+                    ball_size,
+                    tform.translation,
+                    tform.scale.truncate(),
+                )
+                .map(|collision| {
+                    let point = aabb_contact_point(ball_pos, brick_pos, tform.scale.truncate());
+                    (point, Some(collision))
+                }),
+                BrickShape::Circle => {
+                    let radius = tform.scale.x / 2.;
+                    circle_contact_normal(ball_pos, ball_size.x / 2., brick_pos, radius)
+                        .map(|normal| (brick_pos + normal * radius, None))
+                }
+            };
+            if let Some((point, side)) = contact {
+                if let Some(ghost) = ghost.as_ref() {
+                    touched.push(collider_ent);
+                    if ghost.is_passing(collider_ent) {
+                        continue; // Still inside a brick it already hit on the way in
+                    }
+                }
+                collision_events.send(CollisionEvent {
+                    contact: point,
+                    side,
+                    kind: CollisionKind::Brick {
+                        position: tform.translation,
+                        strength: **brick,
+                    },
+                });
+                if !kind.needed_to_clear() {
+                    continue; // Indestructible, the ball only bounces off
+                }
+                if piercing {
+                    // Break it outright, so the ball doesn't hit it again on the way through
+                    **brick = 1;
+                }
+                if let Some(element) = element.as_mut().filter(|e| e.is(Element::Electric)) {
+                    // Split off a ball heading the mirrored direction, the element is used up
+                    split_events.send(SplitBall {
+                        position: ball_t.translation,
+                        velocity: Vec2::new(-ball_v.x, ball_v.y),
+                    });
+                    element.expire();
+                }
+                if let BrickKind::Elemental(new_element) = kind {
+                    // Replaces any element the ball already has
+                    commands.entity(ball).insert(BallElement::new(*new_element));
+                    if let Some(mat) = mats.get_mut(ball_mat) {
+                        mat.color = new_element.color();
+                    }
+                }
+                if let (BrickKind::Streak(needed), Some(mut streak)) = (kind, streak) {
+                    // Piercing balls still break streak bricks outright
+                    if !streak.register_hit(**paddle_contacts, *needed) && !piercing {
+                        continue; // The streak isn't long enough yet, the brick holds
+                    }
+                }
+                let destroyed = brick_collision(
+                    &mut scoreboard,
+                    &mut stats,
+                    &mut commands,
+                    &mut brick_tracker,
+                    collider_ent,
+                    tform,
+                    &mut brick,
+                    &mut sprite,
+                    palette,
+                    damage,
+                );
+                if destroyed {
+                    destroyed_events.send(BrickDestroyed {
+                        position: tform.translation,
+                        kind: *kind,
+                        ball,
+                        color: sprite.color,
+                    });
+                    match kind {
+                        BrickKind::Explosive => explosions.push((
+                            tform.translation,
+                            tform.scale.truncate() * EXPLOSION_REACH,
+                            ball,
+                            ball_v.0,
+                        )),
+                        // The extra ball heads off mirrored, like an electric split
+                        BrickKind::Multiball => split_events.send(SplitBall {
+                            position: tform.translation,
+                            velocity: Vec2::new(-ball_v.x, ball_v.y),
+                        }),
+                        _ => {}
                     }
-                    ControlStyle::Unaltered => {}
                 }
             }
-            break; // Do not collide with multiple paddles in the same frame
+        }
+        if let Some(ghost) = ghost.as_mut() {
+            ghost.set_passing(touched);
         }
     }
-}
-
-// Changes ball_v based on the location of the ball's collision with the paddle
-// NOT A SYSTEM
-fn ball_influence_edges(collision: Collision, ball_vel: &Velocity, paddle_t: Transform) {
-    let reflected_angle = ball_v.angle_between(Vec2::Y);
 
-    // TODO: Calculate the influence of the paddle on the ball's reflection angle, then factor in to desired_angle below
-    let paddle_influence = PADDLE_MAX_INFLUENCE * (**paddle_momentum / PADDLE_MAX_MOMENTUM);
-
-    // Otherwise, adjust the movement by the offset * influence
-    let desired_angle = (reflected_angle + paddle_influence).clamp(-PI / 2., PI / 2.);
-    let magnitude = ball_v.0.length(); // Preserve momentum by tracking magnitude
-    ball_v.0 = Vec2::new(desired_angle.sin(), reflected_angle.cos()).normalize() * magnitude;
+    // Each blast destroys the bricks around it, setting off any explosive bricks it reaches
+    while let Some((center, reach, ball, ball_v)) = explosions.pop() {
+        for (collider_ent, tform, mut brick, kind, _, mut sprite, _) in collider_q.iter_mut() {
+            let offset = (tform.translation - center).truncate().abs();
+            if **brick == 0 || !kind.needed_to_clear() || offset.cmpgt(reach).any() {
+                continue;
+            }
+            **brick = 1; // Destroyed outright, whatever its strength
+            brick_collision(
+                &mut scoreboard,
+                &mut stats,
+                &mut commands,
+                &mut brick_tracker,
+                collider_ent,
+                tform,
+                &mut brick,
+                &mut sprite,
+                palette,
+                1,
+            );
+            destroyed_events.send(BrickDestroyed {
+                position: tform.translation,
+                kind: *kind,
+                ball,
+                color: sprite.color,
+            });
+            match kind {
+                BrickKind::Explosive => explosions.push((
+                    tform.translation,
+                    tform.scale.truncate() * EXPLOSION_REACH,
+                    ball,
+                    ball_v,
+                )),
+                BrickKind::Multiball => split_events.send(SplitBall {
+                    position: tform.translation,
+                    velocity: Vec2::new(-ball_v.x, ball_v.y),
+                }),
+                _ => {}
+            }
+        }
+    }
 }
 
-// Changes ball_v based on the momentum of the paddle at the time of collision
-// NOT A SYSTEM
-fn ball_influence_momentum(
-    collision: Collision,
-    ball_v: &Velocity,
-    paddle_momentum: &PaddleMomentum,
-    tform: Transform,
+#[allow(clippy::too_many_arguments)]
+fn check_paddle_collision(
+    mut ball_q: Query<(&mut Velocity, &Transform), (With<Ball>, Without<Held>)>,
+    mut collider_q: Query<
+        (&Transform, Option<&RemotePaddle>),
+        (
+            With<Collider>,
+            With<Paddle>,
+            Without<Brick>,
+            Without<walls::DrainZone>,
+        ),
+    >,
+    mut collision_events: EventWriter<CollisionEvent>,
+    paddle_momentum: Res<PaddleMomentum>,
+    remote_momentum: Res<RemoteMomentum>,
+    control_style: Res<ControlStyle>,
+    mut stats: ResMut<RunStats>,
+    mut paddle_contacts: ResMut<PaddleContacts>,
+    settings: Res<GameSettings>,
 ) {
-    let reflected_angle = ball_v.angle_between(Vec2::Y);
-    // Convert the current momentum into a [-1, 1] range by dividing by PADDLE_MAX_SPEED, and scale by max influence to get the desired influence
-    let momentum_influence = PADDLE_MAX_INFLUENCE * (**paddle_momentum / PADDLE_MAX_MOMENTUM);
-
-    // Otherwise, adjust the movement by the offset * influence
-    let desired_angle = (reflected_angle + momentum_influence).clamp(-PI / 2., PI / 2.);
-    let magnitude = ball_v.0.length(); // Preserve momentum by tracking magnitude
-    ball_v.0 = Vec2::new(desired_angle.sin(), reflected_angle.cos()).normalize() * magnitude;
+    // The catch radius assist grows the paddle's collision area without changing its visuals
+    let catch_margin = settings.assist.catch_margin();
+
+    for (mut ball_v, ball_t) in ball_q.iter_mut() {
+        let ball_size = ball_t.scale.truncate();
+
+        for (tform, remote) in collider_q.iter_mut() {
+            let paddle_size = tform.scale.truncate();
+            let collision = collide(
+                ball_t.translation,
+                ball_size,
+                tform.translation,
+                paddle_size + Vec2::splat(2. * catch_margin),
+            );
+            if let Some(collision) = collision {
+                if catch_margin > 0.
+                    && collide(ball_t.translation, ball_size, tform.translation, paddle_size)
+                        .is_none()
+                {
+                    stats.assisted = true; // Only the assist margin caught this ball
+                }
+                let ball_pos = ball_t.translation.truncate();
+                let paddle_pos = tform.translation.truncate();
+                let offset = ((ball_pos.x - paddle_pos.x) / (paddle_size.x / 2.)).clamp(-1., 1.);
+                collision_events.send(CollisionEvent {
+                    contact: aabb_contact_point(ball_pos, paddle_pos, paddle_size),
+                    side: Some(collision),
+                    kind: CollisionKind::Paddle { offset },
+                });
+                stats.current_combo = 0; // Touching the paddle ends the combo
+                **paddle_contacts += 1; // Also resets any brick streaks
+                // ball_ricochet mutates ball_v to be the already reflected vector
+                ball_ricochet(collision, &mut ball_v);
+                if let Collision::Bottom | Collision::Top = collision {
+                    match *control_style {
+                        ControlStyle::Edges => {
+                            ball_influence_edges(collision, &mut ball_v, ball_t, tform)
+                        }
+                        ControlStyle::Momentum | ControlStyle::Positional => {
+                            // The co-op partner's paddle steers with their own momentum
+                            let momentum = match remote {
+                                Some(_) => PaddleMomentum(**remote_momentum),
+                                None => PaddleMomentum(**paddle_momentum),
+                            };
+                            ball_influence_momentum(collision, &mut ball_v, &momentum)
+                        }
+                        ControlStyle::Unaltered => {}
+                    }
+                }
+                settings.hardcore.apply_near_miss(offset, &mut ball_v);
+                break; // Do not collide with multiple paddles in the same frame
+            }
+        }
+    }
 }
 
-// fn ball_influence_edges()
-
 fn check_wall_collision(
     mut ball_q: Query<(&mut Velocity, &Transform), With<Ball>>,
     mut collider_q: Query<
@@ -520,145 +1712,158 @@ fn check_wall_collision(
             With<Wall>,
             Without<Paddle>,
             Without<Brick>,
-            Without<walls::BottomWall>,
+            Without<walls::DrainZone>,
         ),
     >,
     mut collision_events: EventWriter<CollisionEvent>,
+    mut rng: ResMut<GameRng>,
+    settings: Res<GameSettings>,
 ) {
-    let (mut ball_v, ball_t) = ball_q.single_mut();
-    let ball_size = ball_t.scale.truncate();
-
-    for tform in collider_q.iter_mut() {
-        let collision = collide(
-            ball_t.translation,
-            ball_size,
-            tform.translation,
-            tform.scale.truncate(),
-        );
-        if let Some(collision) = collision {
-            collision_events.send_default();
-            ball_ricochet(collision, &mut ball_v);
-        }
-    }
-}
-
-// Adjusts the ball velocity as a result of the collision type
-pub fn ball_ricochet(collision: Collision, ball_v: &mut Velocity) {
-    let reflect_x: bool;
-    let reflect_y: bool;
-    match collision {
-        Collision::Left => {
-            reflect_x = ball_v.x > 0.;
-            reflect_y = false
-        }
-        Collision::Right => {
-            reflect_x = ball_v.x < 0.;
-            reflect_y = false
-        }
-        Collision::Top => {
-            reflect_y = ball_v.y < 0.;
-            reflect_x = false
-        }
-        Collision::Bottom => {
-            reflect_y = ball_v.y > 0.;
-            reflect_x = false
-        }
-        Collision::Inside => {
-            reflect_x = false;
-            reflect_y = false
+    let jitter = settings.bounce_jitter_degrees();
+    for (mut ball_v, ball_t) in ball_q.iter_mut() {
+        let ball_size = ball_t.scale.truncate();
+
+        for tform in collider_q.iter_mut() {
+            let collision = collide(
+                ball_t.translation,
+                ball_size,
+                tform.translation,
+                tform.scale.truncate(),
+            );
+            if let Some(collision) = collision {
+                collision_events.send(CollisionEvent {
+                    contact: aabb_contact_point(
+                        ball_t.translation.truncate(),
+                        tform.translation.truncate(),
+                        tform.scale.truncate(),
+                    ),
+                    side: Some(collision),
+                    kind: CollisionKind::Wall,
+                });
+                ball_ricochet(collision, &mut ball_v);
+                jitter_bounce(&mut ball_v, jitter, &mut rng);
+            }
         }
     }
-    if reflect_x {
-        ball_v.x *= -1.;
-    }
-    if reflect_y {
-        ball_v.y *= -1.;
-    }
 }
 
-// Updates score + brick strength, despawns bricks, changes brick colors
+// Updates score + brick strength, starts the destruction of bricks, changes brick colors
+// Returns true if the brick was destroyed
+#[allow(clippy::too_many_arguments)]
 fn brick_collision(
     scoreboard: &mut ResMut<Scoreboard>,
+    stats: &mut RunStats,
     commands: &mut Commands,
     brick_tracker: &mut ResMut<BrickTracker>,
     brick_ent: Entity,
+    brick_tform: &Transform,
     brick: &mut Brick,
     sprite: &mut Sprite,
-) {
-    scoreboard.score += 10;
-    // Decrease brick strength (0 -> despawn)
-    **brick -= 1;
+    palette: BrickPalette,
+    damage: u8,
+) -> bool {
+    scoreboard.score += BRICK_HIT_SCORE * stats.combo_multiplier();
+    // Decrease brick strength (0 -> destroy), extra damage never takes it below 0
+    **brick = brick.saturating_sub(damage);
     if **brick == 0 {
-        commands.entity(brick_ent).despawn_recursive();
+        // Raises the multiplier until the ball next touches the paddle
+        stats.current_combo += 1;
+        stats.max_combo = stats.max_combo.max(stats.current_combo);
+        // The brick stops colliding and counts as gone right away, the animation is only visual
+        commands
+            .entity(brick_ent)
+            .remove::<Collider>()
+            .insert(Destroying::new(brick_tform.scale));
         ***brick_tracker -= 1;
-        return;
+        return true;
     }
-    // At this point **brick > 0, decrement is safe
-    sprite.color = BRICK_COLORS[(**brick - 1) as usize];
+    sprite.color = palette.brick_color(**brick);
+    false
 }
 
 const COLLISION_SOUND_DELAY: f32 = 0.1;
+
+// Game time since the last collision sound, counted in fixed ticks
+#[derive(Default)]
+struct SoundDebounce(f32);
+
+impl SoundDebounce {
+    // Advances by one fixed tick, returning whether a collision during it gets a sound
+    fn tick(&mut self, period: f32, collided: bool) -> bool {
+        self.0 += period;
+        if collided && self.0 >= COLLISION_SOUND_DELAY {
+            self.0 = 0.;
+            return true;
+        }
+        false
+    }
+}
+
 // Plays a sound any time there is >= 1 CollisionEvent message
-// WARNING: Does not work in FixedUpdate (idk y) + Requires use of CollisionSound so must run only while Playing
+// Runs in FixedUpdate and counts the delay in fixed ticks, so at most one sound plays per
+// COLLISION_SOUND_DELAY of game time whatever the frame rate. Requires use of CollisionSound so
+// must run only while Playing
 fn play_collision_sound(
-    mut delay: Local<f32>,
+    mut debounce: Local<SoundDebounce>,
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    sound: Res<CollisionSound>,
-    time: Res<Time>,
+    mut sound: ResMut<CollisionSound>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    time_step: Res<FixedTime>,
 ) {
-    *delay += time.delta_seconds();
-    if !collision_events.is_empty() {
-        collision_events.clear();
-        if *delay >= COLLISION_SOUND_DELAY {
-            *delay = 0.;
-            commands.spawn(AudioBundle {
-                source: sound.0.clone(),
-                settings: PlaybackSettings::DESPAWN,
-            });
-        }
+    let collided = !collision_events.is_empty();
+    collision_events.clear();
+    if !debounce.tick(time_step.period.as_secs_f32(), collided) {
+        return;
     }
+    let Some(source) = sound.0.playable(&asset_server) else {
+        return;
+    };
+    let volume = Volume::new_relative(settings.audio.with_master(1.));
+    commands.spawn(AudioBundle {
+        source,
+        settings: PlaybackSettings::DESPAWN.with_volume(volume),
+    });
 }
 
 // Decrements Health, causes death and loss of health blinking
-#[allow(clippy::too_many_arguments)]
 fn health_handler(
     mut commands: Commands,
-    mut state_msgs: EventWriter<AppStateTransition>,
     mut player_msgs: EventReader<PlayerMessage>,
     mut health: ResMut<Health>,
-    mut text_q: Query<&mut Text, With<HealthDisplay>>,
-    mut paddle_q: Query<(Entity, Option<&Blinking>), With<Paddle>>,
+    mut paddle_q: Query<(Entity, Option<&Blinking>), (With<Paddle>, Without<RemotePaddle>)>,
     mut ball_q: Query<Entity, With<Ball>>,
+    mut stats: ResMut<RunStats>,
 ) {
     for msg in player_msgs.iter() {
         match msg {
             PlayerMessage::JustLostHealth => {
-                let (paddle, blinking) = paddle_q.single_mut();
+                stats.balls_lost += 1;
+                let Ok((paddle, blinking)) = paddle_q.get_single_mut() else {
+                    warn!("No paddle to blink for the lost ball");
+                    continue;
+                };
                 if blinking.is_some() {
                     continue; // Do not remove health while they are blinking
                 }
 
-                if **health == 0 {
-                    state_msgs.send(AppStateTransition::ToMainMenu); // TODO: Show Game Over screen
-                } else {
+                // At 0 health end_run_without_health has already ended the run
+                if **health > 0 {
                     **health -= 1;
                     // Make the paddle blink
                     commands.entity(paddle).insert(Blinking(Timer::new(
                         Duration::from_secs_f64(BLINK_DURATION),
                         TimerMode::Once,
                     )));
-                    // Make the ball blink
-                    let ball = ball_q.single_mut();
-                    commands.entity(ball).insert(Blinking(Timer::new(
-                        Duration::from_secs_f64(BLINK_DURATION),
-                        TimerMode::Once,
-                    )));
+                    // Make any remaining balls blink, a re-served ball starts out blinking
+                    for ball in ball_q.iter_mut() {
+                        commands.entity(ball).insert(Blinking(Timer::new(
+                            Duration::from_secs_f64(BLINK_DURATION),
+                            TimerMode::Once,
+                        )));
+                    }
                 }
-                // text_q holds the setup values put in the TextBundle
-                let mut text = text_q.single_mut();
-                // Update the empty section given the SCORE_COLOR
-                text.sections[1].value = health.0.to_string();
             }
         }
     }
@@ -671,12 +1876,1024 @@ fn health_handler(
 fn game_aux_keys_handler(
     mut game_msgs: EventWriter<GameStateTransition>,
     keys: Res<Input<KeyCode>>,
+    gamepad: GamepadInput,
+    settings: Res<GameSettings>,
 ) {
-    if keys.just_pressed(KeyCode::Return) {
+    let bindings = &settings.controls.bindings;
+    let gamepad_bindings = &settings.controls.gamepad;
+    if bindings.just_pressed(Action::Start, &keys)
+        || gamepad.just_pressed(Action::Start, gamepad_bindings)
+    {
         game_msgs.send(GameStateTransition::ToPlayGame);
     }
 
-    if keys.just_pressed(KeyCode::Escape) {
+    if bindings.just_pressed(Action::Pause, &keys)
+        || gamepad.just_pressed(Action::Pause, gamepad_bindings)
+    {
         game_msgs.send(GameStateTransition::ToHaltGame);
     }
+
+    if bindings.just_pressed(Action::Restart, &keys) {
+        game_msgs.send(GameStateTransition::RestartGame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        infinite::GameMode,
+        misc::test_utils::{advance_time, sent_events, test_app},
+        theme::Theme,
+        tilt::apply_camera_tilt,
+    };
+
+    #[test]
+    fn wrapping_past_the_right_bound_enters_at_the_left_bound() {
+        let width = PADDLE_SIZE.x;
+        let left = paddle_left_bound(width);
+        let right = paddle_right_bound(width);
+
+        assert_eq!(bound_paddle_x(right, width, PaddleEdgeMode::Wrap), left);
+        let wrapped = bound_paddle_x(right + 5., width, PaddleEdgeMode::Wrap);
+        assert!((wrapped - (left + 5.)).abs() < 1e-3);
+        // Moving left past the left bound comes back in from the right
+        let wrapped = bound_paddle_x(left - 5., width, PaddleEdgeMode::Wrap);
+        assert!((wrapped - (right - 5.)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn clamping_stops_the_paddle_at_the_bounds() {
+        let width = PADDLE_SIZE.x;
+        let right = paddle_right_bound(width);
+        let clamped = bound_paddle_x(right + 5., width, PaddleEdgeMode::Clamp);
+        assert_eq!(clamped, right);
+    }
+
+    // The paddle systems with the right key held and no paddle spawned yet
+    fn held_right_app() -> App {
+        let mut app = test_app();
+        app.insert_resource(PaddleMomentum(0.))
+            .insert_resource(ControlStyle::Edges)
+            .insert_resource(Theme::Classic.colors())
+            .init_resource::<PositionalControl>()
+            .init_resource::<PaddleTarget>()
+            .init_resource::<MouseTarget>()
+            .init_resource::<ReversedControls>()
+            .add_systems(
+                Update,
+                (
+                    update_paddle_momentum,
+                    update_paddle.after(update_paddle_momentum),
+                    thaw_paddle,
+                    tick_reversed_controls.before(update_paddle_momentum),
+                ),
+            );
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Right);
+        app
+    }
+
+    // A paddle held right, frozen for a second
+    fn frozen_paddle_app() -> App {
+        let mut app = held_right_app();
+        app.world.spawn((
+            paddle_sprite(Color::WHITE),
+            Paddle,
+            Frozen(Timer::from_seconds(1., TimerMode::Once)),
+        ));
+        app
+    }
+
+    fn paddle_x(app: &mut App) -> f32 {
+        let mut paddle_q = app.world.query_filtered::<&Transform, With<Paddle>>();
+        paddle_q.single(&app.world).translation.x
+    }
+
+    // The paddle systems registered like the plugin does for the given timing, with the right
+    // key held. FixedUpdate is run by hand once per physics tick
+    fn paddle_timing_app(timing: PaddleTiming) -> App {
+        let mut app = test_app();
+        app.insert_resource(PaddleMomentum(0.))
+            .insert_resource(ControlStyle::Momentum)
+            .init_resource::<PositionalControl>()
+            .init_resource::<PaddleTarget>()
+            .init_resource::<MouseTarget>()
+            .init_resource::<ReversedControls>()
+            .init_resource::<RemoteMomentum>()
+            .init_resource::<RunStats>()
+            .init_resource::<PaddleContacts>()
+            .add_event::<CollisionEvent>()
+            .add_systems(
+                Update,
+                (update_paddle_momentum.before(update_paddle), update_paddle)
+                    .run_if(paddle_timing_is(PaddleTiming::Frame)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_paddle_momentum
+                        .before(update_paddle)
+                        .run_if(paddle_timing_is(PaddleTiming::Fixed)),
+                    update_paddle.run_if(paddle_timing_is(PaddleTiming::Fixed)),
+                    check_paddle_collision.after(update_paddle),
+                ),
+            );
+        app.world
+            .resource_mut::<GameSettings>()
+            .controls
+            .paddle_timing = timing;
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Right);
+        app.world
+            .spawn((paddle_sprite(Color::WHITE), Paddle, Collider));
+        app
+    }
+
+    // One physics tick, drawn over the given number of frames
+    fn paddle_tick(app: &mut App, frames: u32) {
+        let period = app.world.resource::<FixedTime>().period.as_secs_f32();
+        for _ in 0..frames {
+            advance_time(app, period / frames as f32);
+            app.update();
+        }
+        app.world.run_schedule(FixedUpdate);
+    }
+
+    #[test]
+    fn moving_the_paddle_every_frame_hits_the_ball_like_the_physics_tick() {
+        let mut fixed = paddle_timing_app(PaddleTiming::Fixed);
+        let mut frame = paddle_timing_app(PaddleTiming::Frame);
+        // Several frames per physics tick, while the paddle is still speeding up
+        for _ in 0..5 {
+            paddle_tick(&mut fixed, 1);
+            paddle_tick(&mut frame, 4);
+        }
+        let momentum = |app: &App| **app.world.resource::<PaddleMomentum>();
+        assert!((momentum(&fixed) - momentum(&frame)).abs() < 1e-4);
+        assert!(momentum(&fixed) > 0. && momentum(&fixed) < PADDLE_MAX_MOMENTUM);
+
+        // The smaller steps trail the fixed ones slightly, but not by enough to change the contact
+        assert!((paddle_x(&mut fixed) - paddle_x(&mut frame)).abs() < 2.);
+
+        let ball_position = Vec2::new(
+            PADDLE_STARTING_POSITION_X + 20.,
+            PADDLE_STARTING_POSITION_Y + PADDLE_SIZE.y / 2.,
+        );
+        let fixed_ball = spawn_test_ball(&mut fixed, ball_position, Vec2::new(0., -300.));
+        let frame_ball = spawn_test_ball(&mut frame, ball_position, Vec2::new(0., -300.));
+        paddle_tick(&mut fixed, 1);
+        paddle_tick(&mut frame, 4);
+        assert_eq!(**fixed.world.resource::<PaddleContacts>(), 1);
+        assert_eq!(**frame.world.resource::<PaddleContacts>(), 1);
+
+        let fixed_v = fixed.world.get::<Velocity>(fixed_ball).unwrap().0;
+        let frame_v = frame.world.get::<Velocity>(frame_ball).unwrap().0;
+        assert!(fixed_v.y > 0. && fixed_v.x > 0.); // Bounced up, steered right by the momentum
+        assert!(fixed_v.distance(frame_v) < 1e-2);
+    }
+
+    #[test]
+    fn frozen_paddle_ignores_input_until_the_freeze_ends() {
+        let mut app = frozen_paddle_app();
+        for _ in 0..4 {
+            advance_time(&mut app, 0.2);
+            app.update();
+            assert_eq!(paddle_x(&mut app), PADDLE_STARTING_POSITION_X);
+        }
+
+        // Thaws on this update, the paddle answers the held key from the next one
+        advance_time(&mut app, 0.3);
+        app.update();
+        let mut frozen_q = app.world.query_filtered::<Entity, With<Frozen>>();
+        assert!(frozen_q.iter(&app.world).next().is_none());
+        for _ in 0..3 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert!(paddle_x(&mut app) > PADDLE_STARTING_POSITION_X);
+    }
+
+    #[test]
+    fn the_paddle_follows_rebound_keys() {
+        let mut app = held_right_app();
+        app.world.spawn((paddle_sprite(Color::WHITE), Paddle));
+        let mut settings = app.world.resource_mut::<GameSettings>();
+        settings
+            .controls
+            .bindings
+            .bind(Action::MoveRight, 1, KeyCode::L)
+            .unwrap();
+
+        // Right is no longer bound, holding it does nothing
+        for _ in 0..5 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert_eq!(paddle_x(&mut app), PADDLE_STARTING_POSITION_X);
+
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::L);
+        for _ in 0..5 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert!(paddle_x(&mut app) > PADDLE_STARTING_POSITION_X);
+    }
+
+    #[test]
+    fn reversed_controls_swap_directions_until_the_timer_ends() {
+        let mut app = held_right_app();
+        app.add_event::<BrickDestroyed>().add_systems(
+            Update,
+            reverse_controls_on_trap.after(tick_reversed_controls),
+        );
+        app.world.spawn((paddle_sprite(Color::WHITE), Paddle));
+        let destroyed = BrickDestroyed {
+            position: Vec3::ZERO,
+            kind: BrickKind::Reverse,
+            ball: Entity::PLACEHOLDER,
+            color: Color::WHITE,
+        };
+        // A second Reverse brick only refreshes the timer, it doesn't flip the controls back
+        app.world.send_event(destroyed.clone());
+        app.world.send_event(destroyed);
+
+        for _ in 0..10 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert!(app.world.resource::<ReversedControls>().active());
+        assert!(paddle_x(&mut app) < PADDLE_STARTING_POSITION_X);
+
+        // The last of the reversal, then the held key moves the paddle right again
+        advance_time(&mut app, 3.5);
+        app.update();
+        assert!(!app.world.resource::<ReversedControls>().active());
+        let reversed_x = paddle_x(&mut app);
+        for _ in 0..20 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert!(paddle_x(&mut app) > reversed_x);
+    }
+
+    #[test]
+    fn reversed_controls_end_with_the_level_the_run_or_the_game() {
+        for transition in [
+            GameStateTransition::RestartLevel,
+            GameStateTransition::RestartGame,
+            GameStateTransition::ToGameOver,
+        ] {
+            let mut app = test_app();
+            app.init_resource::<ReversedControls>()
+                .add_event::<BrickDestroyed>()
+                .add_event::<GameStateTransition>()
+                .add_systems(
+                    Update,
+                    (
+                        reverse_controls_on_trap,
+                        reset_reversed_controls.after(reverse_controls_on_trap),
+                    ),
+                );
+            app.world.send_event(BrickDestroyed {
+                position: Vec3::ZERO,
+                kind: BrickKind::Reverse,
+                ball: Entity::PLACEHOLDER,
+                color: Color::WHITE,
+            });
+            app.update();
+            assert!(app.world.resource::<ReversedControls>().active());
+
+            app.world.send_event(transition);
+            app.update();
+            assert!(!app.world.resource::<ReversedControls>().active());
+        }
+    }
+
+    // The resources a BallSpawner spawns balls from
+    fn ball_spawner_app() -> App {
+        let mut app = test_app();
+        app.insert_resource(Theme::Classic.colors())
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<BallConfig>()
+            .init_resource::<BallTints>();
+        app
+    }
+
+    // serve_ball with the player on the given health
+    fn serve_app(health: usize) -> App {
+        let mut app = ball_spawner_app();
+        app.insert_resource(Health(health))
+            .init_resource::<GracePeriod>()
+            .add_systems(Update, serve_ball);
+        app
+    }
+
+    fn ball_count(app: &mut App) -> usize {
+        let mut ball_q = app.world.query_filtered::<(), With<Ball>>();
+        ball_q.iter(&app.world).count()
+    }
+
+    #[test]
+    fn losing_the_last_ball_with_health_left_serves_exactly_one_ball() {
+        let mut app = serve_app(2);
+        app.update();
+        assert_eq!(ball_count(&mut app), 1);
+        // The served ball is in play, so no more are served
+        app.update();
+        assert_eq!(ball_count(&mut app), 1);
+    }
+
+    #[test]
+    fn no_ball_is_served_without_health() {
+        let mut app = serve_app(0);
+        app.update();
+        assert_eq!(ball_count(&mut app), 0);
+    }
+
+    // The served ball's velocity, and whether it is held on the paddle
+    fn served_ball(app: &mut App) -> (Vec2, bool) {
+        let mut ball_q = app
+            .world
+            .query_filtered::<(&Velocity, Option<&Held>), With<Ball>>();
+        let (velocity, held) = ball_q.single(&app.world);
+        (velocity.0, held.is_some())
+    }
+
+    #[test]
+    fn served_balls_wait_on_the_paddle_only_when_docked() {
+        let mut app = serve_app(2);
+        app.update();
+        assert_eq!(served_ball(&mut app), (Vec2::ZERO, true));
+
+        let mut app = serve_app(2);
+        app.world
+            .resource_mut::<GameSettings>()
+            .gameplay
+            .docked_serve = false;
+        app.update();
+        let (velocity, held) = served_ball(&mut app);
+        assert_ne!(velocity, Vec2::ZERO);
+        assert!(!held);
+    }
+
+    const TEST_BRICK_SIZE: Vec3 = Vec3::new(100., 50., 1.);
+
+    // check_brick_collisions and tick_ball_elements with the resources they read, but no bricks or
+    // balls yet
+    fn brick_collision_app() -> App {
+        let mut app = test_app();
+        app.insert_resource(Scoreboard { score: 0 })
+            .insert_resource(BrickTracker(0))
+            .insert_resource(Theme::Classic.colors())
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<PaddleContacts>()
+            .init_resource::<RunStats>()
+            .add_event::<CollisionEvent>()
+            .add_event::<BrickDestroyed>()
+            .add_event::<SplitBall>()
+            .add_systems(Update, (check_brick_collisions, tick_ball_elements));
+        app
+    }
+
+    fn spawn_test_ball(app: &mut App, position: Vec2, velocity: Vec2) -> Entity {
+        let ball = (
+            Ball,
+            Velocity(velocity),
+            Transform::from_translation(position.extend(0.)).with_scale(BALL_SIZE),
+            LastMove::default(),
+            Handle::<ColorMaterial>::default(),
+        );
+        app.world.spawn(ball).id()
+    }
+
+    fn spawn_test_brick(app: &mut App, position: Vec2, strength: u8, kind: BrickKind) -> Entity {
+        if kind.needed_to_clear() {
+            **app.world.resource_mut::<BrickTracker>() += 1;
+        }
+        let brick = (
+            Transform::from_translation(position.extend(0.)).with_scale(TEST_BRICK_SIZE),
+            Brick(strength),
+            kind,
+            BrickShape::Rectangle,
+            Sprite::default(),
+            Collider,
+        );
+        app.world.spawn(brick).id()
+    }
+
+    fn ball_velocity(app: &App, ball: Entity) -> Vec2 {
+        app.world.get::<Velocity>(ball).unwrap().0
+    }
+
+    // A ball striking a brick from below, returning the ball's velocity, the brick's strength and
+    // the camera's rotation afterwards
+    fn brick_hit_with_tilt(enabled: bool) -> (Vec2, u8, Quat) {
+        let mut app = brick_collision_app();
+        app.add_systems(Update, apply_camera_tilt);
+        app.world.resource_mut::<GameSettings>().visual.tilt.enabled = enabled;
+        let camera = app.world.spawn(game_camera()).id();
+        let ball = spawn_test_ball(&mut app, Vec2::new(0., -30.), Vec2::new(0.6, 0.8));
+        let brick = spawn_test_brick(&mut app, Vec2::ZERO, 2, BrickKind::Normal);
+        app.update();
+        (
+            ball_velocity(&app, ball),
+            **app.world.get::<Brick>(brick).unwrap(),
+            app.world.get::<Transform>(camera).unwrap().rotation,
+        )
+    }
+
+    #[test]
+    fn tilting_the_camera_leaves_collisions_alone() {
+        let (flat_v, flat_strength, flat_rotation) = brick_hit_with_tilt(false);
+        let (tilted_v, tilted_strength, tilted_rotation) = brick_hit_with_tilt(true);
+        assert_ne!(tilted_rotation, flat_rotation);
+        assert!(flat_v.y < 0.);
+        assert_eq!(flat_strength, 1);
+        assert_eq!(tilted_v, flat_v);
+        assert_eq!(tilted_strength, flat_strength);
+    }
+
+    #[test]
+    fn touching_a_fire_brick_makes_the_ball_piercing() {
+        let mut app = brick_collision_app();
+        let ball = spawn_test_ball(&mut app, Vec2::new(0., -30.), Vec2::new(0., 300.));
+        spawn_test_brick(&mut app, Vec2::ZERO, 1, BrickKind::Elemental(Element::Fire));
+        app.update();
+        let element = app.world.get::<BallElement>(ball).unwrap();
+        assert!(element.is(Element::Fire));
+
+        // A strong brick is broken outright, and the ball carries on through it
+        app.world.get_mut::<Transform>(ball).unwrap().translation = Vec3::new(0., 90., 0.);
+        let strong = spawn_test_brick(&mut app, Vec2::new(0., 120.), 3, BrickKind::Normal);
+        let before = ball_velocity(&app, ball);
+        app.update();
+        assert_eq!(**app.world.get::<Brick>(strong).unwrap(), 0);
+        assert_eq!(ball_velocity(&app, ball), before);
+    }
+
+    #[test]
+    fn fire_ball_bounces_again_once_the_element_expires() {
+        let mut app = brick_collision_app();
+        let ball = spawn_test_ball(&mut app, Vec2::new(0., -30.), Vec2::new(0., 300.));
+        app.world
+            .entity_mut(ball)
+            .insert(BallElement::new(Element::Fire));
+        advance_time(&mut app, 7.);
+        app.update();
+        assert!(app.world.get::<BallElement>(ball).is_none());
+
+        let brick = spawn_test_brick(&mut app, Vec2::ZERO, 3, BrickKind::Normal);
+        app.update();
+        assert_eq!(**app.world.get::<Brick>(brick).unwrap(), 2);
+        assert!(ball_velocity(&app, ball).y < 0.);
+    }
+
+    #[test]
+    fn serves_stay_within_the_cone_and_never_head_down() {
+        let mut rng = GameRng::from_seed(0);
+        let mut settings = GameSettings::default();
+        settings.gameplay.serve_cone_degrees = 60.;
+        for _ in 0..1000 {
+            let direction = serve_direction(&mut rng, &settings);
+            let from_up = direction.x.atan2(direction.y).to_degrees();
+            assert!(
+                from_up.abs() <= 30. + 1e-3,
+                "{direction} is outside the cone"
+            );
+        }
+
+        // The widest cone still never serves flatter than the minimum vertical speed allows
+        settings.gameplay.serve_cone_degrees = 180.;
+        for _ in 0..1000 {
+            let direction = serve_direction(&mut rng, &settings);
+            assert!(
+                direction.y >= MIN_BALL_VERTICAL - 1e-5,
+                "{direction} is too flat"
+            );
+        }
+    }
+
+    #[test]
+    fn destroyed_brick_stops_colliding_at_once_and_is_gone_after_its_animation() {
+        let mut app = brick_collision_app();
+        app.add_systems(Update, animate_destroying_bricks);
+        let ball = spawn_test_ball(&mut app, Vec2::new(0., -30.), Vec2::new(0., 300.));
+        let brick = spawn_test_brick(&mut app, Vec2::ZERO, 1, BrickKind::Normal);
+        app.update();
+        assert!(app.world.get::<Collider>(brick).is_none());
+        assert!(app.world.get::<Destroying>(brick).is_some());
+        assert_eq!(**app.world.resource::<BrickTracker>(), 0);
+
+        // Still shrinking, but the ball overlapping it no longer bounces off it
+        let bounced = ball_velocity(&app, ball);
+        advance_time(&mut app, 0.1);
+        app.update();
+        assert!(app.world.get_entity(brick).is_some());
+        assert_eq!(ball_velocity(&app, ball), bounced);
+
+        advance_time(&mut app, 0.2);
+        app.update();
+        assert!(app.world.get_entity(brick).is_none());
+    }
+
+    #[test]
+    fn gravity_curves_a_sideways_ball_downward_at_the_same_speed() {
+        let mut ball_v = Velocity(Vec2::new(300., 100.));
+        let speed = ball_v.length();
+        for _ in 0..30 {
+            let last_y = ball_v.y;
+            apply_gravity(&mut ball_v, 1., 1. / 60.);
+            assert!(ball_v.y <= last_y);
+            assert!((ball_v.length() - speed).abs() < 1e-2);
+        }
+        assert!(ball_v.y < 0.);
+        assert!(ball_v.x > 0.);
+    }
+
+    #[test]
+    fn no_gravity_leaves_the_path_straight() {
+        let mut ball_v = Velocity(Vec2::new(300., 200.));
+        apply_gravity(&mut ball_v, 0., 1. / 60.);
+        assert!(ball_v.abs_diff_eq(Vec2::new(300., 200.), 1e-3));
+    }
+
+    #[test]
+    fn splitting_past_the_ball_limit_spawns_nothing() {
+        let mut app = ball_spawner_app();
+        app.insert_resource(BallConfig {
+            max_balls: 2,
+            split_speed: SplitSpeed::Shared,
+        })
+        .add_event::<SplitBall>()
+        .add_systems(Update, split_balls);
+        spawn_test_ball(&mut app, Vec2::ZERO, Vec2::Y);
+        for _ in 0..3 {
+            app.world.send_event(SplitBall {
+                position: Vec3::ZERO,
+                velocity: Vec2::new(1., 1.),
+            });
+        }
+        app.update();
+        assert_eq!(ball_count(&mut app), 2);
+    }
+
+    #[test]
+    fn split_balls_follow_the_speed_policy() {
+        let fast = Vec2::new(3., 4.);
+        let shared = BallConfig {
+            max_balls: DEFAULT_MAX_BALLS,
+            split_speed: SplitSpeed::Shared,
+        };
+        assert_eq!(shared.split_velocity(fast), fast);
+        let base = BallConfig {
+            split_speed: SplitSpeed::Base,
+            ..shared
+        };
+        assert_eq!(base.split_velocity(fast), Vec2::new(0.6, 0.8));
+    }
+
+    fn positional_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<PaddleTarget>()
+            .init_resource::<PositionalControl>()
+            .init_resource::<ReversedControls>()
+            .add_systems(Update, update_paddle_target);
+        app
+    }
+
+    // Presses the key for one update, then clears the press so holding it doesn't count again
+    fn tap(app: &mut App, key: KeyCode) {
+        app.world.resource_mut::<Input<KeyCode>>().press(key);
+        app.update();
+        let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+        keys.release(key);
+        keys.clear();
+    }
+
+    #[test]
+    fn tapping_right_advances_the_target_one_slot() {
+        let mut app = positional_app();
+        let start = **app.world.resource::<PaddleTarget>();
+        tap(&mut app, KeyCode::Right);
+        assert_eq!(**app.world.resource::<PaddleTarget>(), start + 1);
+
+        // Holding the key doesn't keep stepping
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Right);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+        assert_eq!(**app.world.resource::<PaddleTarget>(), start + 2);
+    }
+
+    #[test]
+    fn target_stops_at_the_last_slot() {
+        let mut app = positional_app();
+        let slots = app.world.resource::<PositionalControl>().slots;
+        for _ in 0..slots + 2 {
+            tap(&mut app, KeyCode::Right);
+        }
+        assert_eq!(**app.world.resource::<PaddleTarget>(), slots - 1);
+    }
+
+    #[test]
+    fn hardcore_run_starts_on_one_health_and_ends_on_the_first_lost_ball() {
+        let mut app = test_app();
+        app.world.resource_mut::<GameSettings>().hardcore.enabled = true;
+        app.insert_resource(CurrentState(GameState::Uninitialized))
+            .insert_resource(Health(PLAYER_STARTING_HEALTH))
+            .insert_resource(Level(1))
+            .init_resource::<Difficulty>()
+            .init_resource::<BallSpeed>()
+            .init_resource::<PaddleWidth>()
+            .init_resource::<RunStats>()
+            .add_event::<PlayerMessage>()
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (apply_difficulty, health_handler, end_run_without_health).chain(),
+            );
+        app.world.spawn(Paddle);
+        app.update();
+        assert_eq!(**app.world.resource::<Health>(), 1);
+
+        app.insert_resource(CurrentState(GameState::Playing));
+        app.world.send_event(PlayerMessage::JustLostHealth);
+        app.update();
+        assert_eq!(**app.world.resource::<Health>(), 0);
+        let game_over = sent_events::<GameStateTransition>(&app)
+            .into_iter()
+            .any(|msg| matches!(msg, GameStateTransition::ToGameOver));
+        assert!(game_over);
+    }
+
+    // transition_game with the resources it reads, partway through the second level of a run
+    fn transition_app() -> App {
+        let mut app = test_app();
+        // Restarting a level loads its bricks' textures
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .insert_resource(CurrentState(GameState::Playing))
+            .insert_resource(Level(2))
+            .insert_resource(BrickTracker(0))
+            .insert_resource(Scoreboard { score: 100 })
+            .insert_resource(Levels::load())
+            .init_resource::<BallSpeed>()
+            .init_resource::<RunStats>()
+            .init_resource::<BonusRound>()
+            .init_resource::<BrickDimensions>()
+            .init_resource::<GameMode>()
+            .add_event::<GameStateTransition>()
+            .add_event::<AppStateTransition>()
+            .add_systems(Update, transition_game);
+        app
+    }
+
+    #[test]
+    fn music_stems_play_on_through_a_restart() {
+        let mut app = transition_app();
+        let stem = app.world.spawn(MusicStem::Base).id();
+        let brick = app.world.spawn(Brick(1)).id();
+        app.world.send_event(GameStateTransition::RestartGame);
+        app.update();
+        assert!(app.world.get_entity(brick).is_none());
+        assert!(app.world.get_entity(stem).is_some());
+    }
+
+    // A run down to its last health, restarted the given way
+    fn restarted(transition: GameStateTransition) -> App {
+        let mut app = transition_app();
+        app.insert_resource(Health(1))
+            .init_resource::<Difficulty>()
+            .init_resource::<PaddleWidth>()
+            .add_systems(Update, apply_difficulty.after(transition_game));
+        app.world.send_event(transition);
+        app.update();
+        app
+    }
+
+    #[test]
+    fn restarting_the_level_keeps_the_run_but_restarting_the_game_resets_it() {
+        let app = restarted(GameStateTransition::RestartLevel);
+        assert_eq!(**app.world.resource::<Level>(), 2);
+        assert_eq!(app.world.resource::<Scoreboard>().score, 100);
+        assert_eq!(**app.world.resource::<Health>(), 1);
+
+        let app = restarted(GameStateTransition::RestartGame);
+        assert_eq!(**app.world.resource::<Level>(), 1);
+        assert_eq!(app.world.resource::<Scoreboard>().score, 0);
+        let starting_health = Difficulty::default().starting_health(PLAYER_STARTING_HEALTH);
+        assert_eq!(**app.world.resource::<Health>(), starting_health);
+    }
+
+    #[test]
+    fn next_level_without_a_ball_serves_a_fresh_one() {
+        let mut app = serve_app(1);
+        // The level's bricks load their textures
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .insert_resource(CurrentState(GameState::Playing))
+            .insert_resource(Level(1))
+            .insert_resource(BrickTracker(0))
+            .insert_resource(Scoreboard { score: 0 })
+            .insert_resource(Levels::load())
+            .init_resource::<BallSpeed>()
+            .init_resource::<RunStats>()
+            .init_resource::<BonusRound>()
+            .init_resource::<BrickDimensions>()
+            .init_resource::<GameMode>()
+            .add_event::<GameStateTransition>()
+            .add_event::<AppStateTransition>()
+            .add_systems(Update, transition_game.before(serve_ball));
+        app.world.spawn((paddle_sprite(Color::WHITE), Paddle));
+        app.world.send_event(GameStateTransition::NextLevel);
+        app.update();
+
+        assert_eq!(**app.world.resource::<Level>(), 2);
+        assert!(**app.world.resource::<BrickTracker>() > 0);
+        assert_eq!(ball_count(&mut app), 1);
+    }
+
+    #[test]
+    fn ball_stays_put_during_the_preview_until_start_is_pressed() {
+        let mut app = test_app();
+        app.insert_resource(CurrentState(GameState::Preview))
+            .init_resource::<SlowBall>()
+            .init_resource::<BallSpeed>()
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (game_aux_keys_handler, move_ball.run_if(game_running)),
+            );
+        let ball = spawn_test_ball(&mut app, Vec2::ZERO, Vec2::Y);
+        app.update();
+        let ball_y = |app: &App| app.world.get::<Transform>(ball).unwrap().translation.y;
+        assert_eq!(ball_y(&app), 0.);
+
+        tap(&mut app, KeyCode::Return);
+        assert_eq!(ball_y(&app), 0.);
+        let started = sent_events::<GameStateTransition>(&app)
+            .into_iter()
+            .any(|msg| matches!(msg, GameStateTransition::ToPlayGame));
+        assert!(started);
+
+        // transition_game answers ToPlayGame by counting down, then moving on to Playing
+        app.insert_resource(CurrentState(GameState::Playing));
+        app.update();
+        assert!(ball_y(&app) > 0.);
+    }
+
+    #[test]
+    fn a_hit_stop_holds_the_ball_still_until_it_is_over() {
+        use crate::misc::hit_stop::HIT_STOP_DURATION;
+
+        let mut app = test_app();
+        app.init_resource::<HitStop>()
+            .init_resource::<SlowBall>()
+            .init_resource::<BallSpeed>()
+            .add_event::<BrickDestroyed>()
+            .add_systems(
+                Update,
+                (
+                    tick_hit_stop,
+                    trigger_hit_stop,
+                    move_ball.run_if(hit_stop_inactive),
+                )
+                    .chain(),
+            );
+        let ball = spawn_test_ball(&mut app, Vec2::ZERO, Vec2::Y);
+        app.world.send_event(BrickDestroyed {
+            position: Vec3::ZERO,
+            kind: BrickKind::default(),
+            ball,
+            color: Color::WHITE,
+        });
+        let ball_y = |app: &App| app.world.get::<Transform>(ball).unwrap().translation.y;
+        advance_time(&mut app, 0.);
+        app.update();
+        assert_eq!(ball_y(&app), 0.);
+        advance_time(&mut app, HIT_STOP_DURATION * 0.8);
+        app.update();
+        assert_eq!(ball_y(&app), 0.);
+
+        advance_time(&mut app, HIT_STOP_DURATION * 0.4);
+        app.update();
+        assert!(ball_y(&app) > 0.);
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn dev_freeze_holds_the_ball_without_touching_its_velocity() {
+        use crate::dev::freeze::{not_frozen, DevFreeze};
+
+        let mut app = test_app();
+        app.insert_resource(DevFreeze { frozen: true })
+            .init_resource::<SlowBall>()
+            .init_resource::<BallSpeed>()
+            .configure_set(Update, PhysicsSet.run_if(not_frozen))
+            .add_systems(Update, move_ball.in_set(PhysicsSet));
+        let ball = spawn_test_ball(&mut app, Vec2::ZERO, Vec2::Y);
+        for _ in 0..3 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        // Still there for the inspector, just not moving
+        let ball_y = |app: &App| app.world.get::<Transform>(ball).unwrap().translation.y;
+        assert_eq!(ball_y(&app), 0.);
+        assert_eq!(ball_velocity(&app, ball), Vec2::Y);
+
+        app.world.resource_mut::<DevFreeze>().frozen = false;
+        app.update();
+        assert!(ball_y(&app) > 0.);
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn a_sandbox_brick_is_placed_where_clicked_and_collides_like_any_brick() {
+        use bevy::{render::camera::camera_system, window::PrimaryWindow};
+
+        use crate::{
+            bricks::brick_grid,
+            dev::sandbox::{place_sandbox_bricks, BrickSandbox, SandboxBrick},
+        };
+
+        let mut app = brick_collision_app();
+        // The placed brick loads its texture, and the cursor is turned into a world position
+        // through the camera
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .add_asset::<Image>()
+            .add_event::<bevy::window::WindowCreated>()
+            .add_event::<bevy::window::WindowResized>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<BrickDimensions>()
+            .insert_resource(BrickSandbox {
+                enabled: true,
+                strength: 2,
+            })
+            .add_systems(
+                Update,
+                (
+                    camera_system::<OrthographicProjection>,
+                    place_sandbox_bricks.after(camera_system::<OrthographicProjection>),
+                ),
+            );
+        let window = app.world.spawn((Window::default(), PrimaryWindow)).id();
+        // Nothing propagates transforms here
+        let mut camera = game_camera();
+        camera.global_transform = camera.transform.into();
+        app.world.spawn(camera);
+        app.update();
+
+        // Clicks the grid's top left cell
+        let dims = BrickDimensions::default();
+        let grid = brick_grid(&dims).unwrap();
+        let cell = Vec2::new(grid.left_edge + dims.size.x / 2., grid.top_y);
+        let mut camera_q = app.world.query::<(&Camera, &GlobalTransform)>();
+        let (camera, camera_tform) = camera_q.single(&app.world);
+        let cursor = camera
+            .world_to_viewport(camera_tform, cell.extend(0.))
+            .unwrap();
+        app.world
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(cursor));
+        app.world
+            .resource_mut::<Input<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+
+        let mut placed_q = app
+            .world
+            .query_filtered::<(Entity, &Transform, &Brick), With<SandboxBrick>>();
+        let (brick, tform, strength) = placed_q.single(&app.world);
+        assert!(tform.translation.truncate().abs_diff_eq(cell, 1e-3));
+        assert_eq!(**strength, 2);
+        assert_eq!(**app.world.resource::<BrickTracker>(), 1);
+
+        app.world.resource_mut::<Input<MouseButton>>().reset_all();
+        let below = cell - Vec2::new(0., dims.size.y / 2. + 5.);
+        let ball = spawn_test_ball(&mut app, below, Vec2::new(0.6, 0.8));
+        app.update();
+        assert!(ball_velocity(&app, ball).y < 0.);
+        assert_eq!(**app.world.get::<Brick>(brick).unwrap(), 1);
+    }
+
+    #[test]
+    fn at_most_one_collision_sound_plays_per_delay() {
+        let mut debounce = SoundDebounce::default();
+        // A collision on every tick for two seconds
+        let ticks = (2. / FIXED_TIME_TICKS_PER_SECOND).round() as usize;
+        let played: Vec<usize> = (0..ticks)
+            .filter(|_| debounce.tick(FIXED_TIME_TICKS_PER_SECOND, true))
+            .collect();
+        let min_gap = (COLLISION_SOUND_DELAY / FIXED_TIME_TICKS_PER_SECOND).floor() as usize;
+        assert!(played.windows(2).all(|pair| pair[1] - pair[0] >= min_gap));
+        assert!(played.len() <= (2. / COLLISION_SOUND_DELAY) as usize);
+        assert!(!played.is_empty());
+
+        // Without collisions the delay runs out quietly, and the next collision plays at once
+        for _ in 0..min_gap {
+            assert!(!debounce.tick(FIXED_TIME_TICKS_PER_SECOND, false));
+        }
+        assert!(debounce.tick(FIXED_TIME_TICKS_PER_SECOND, true));
+    }
+
+    #[test]
+    fn a_near_horizontal_velocity_is_steepened_keeping_its_speed_and_direction() {
+        for shallow in [
+            Vec2::new(1., 0.01),
+            Vec2::new(-0.5, -0.001),
+            Vec2::new(2., 0.),
+        ] {
+            let mut ball_v = Velocity(shallow);
+            enforce_min_vertical(&mut ball_v);
+            assert!(ball_v.y.abs() >= MIN_BALL_VERTICAL * shallow.length() - 1e-5);
+            assert!((ball_v.length() - shallow.length()).abs() < 1e-5);
+            assert_eq!(ball_v.x.signum(), shallow.x.signum());
+        }
+        // Steep enough already, left alone
+        let mut ball_v = Velocity(Vec2::new(0.6, -0.8));
+        enforce_min_vertical(&mut ball_v);
+        assert_eq!(ball_v.0, Vec2::new(0.6, -0.8));
+    }
+
+    #[test]
+    fn moving_the_ball_corrects_a_near_horizontal_path() {
+        let mut app = test_app();
+        app.init_resource::<SlowBall>()
+            .init_resource::<BallSpeed>()
+            .add_systems(Update, move_ball);
+        let ball = spawn_test_ball(&mut app, Vec2::ZERO, Vec2::new(1., 0.001));
+        app.update();
+        assert!(ball_velocity(&app, ball).y >= MIN_BALL_VERTICAL - 1e-5);
+    }
+
+    #[test]
+    fn a_fast_ball_hits_a_brick_it_would_have_skipped_over() {
+        let mut app = brick_collision_app();
+        let brick = spawn_test_brick(&mut app, Vec2::ZERO, 2, BrickKind::Normal);
+        // Moved from well below the brick to well above it in a single tick
+        let movement = Vec2::new(0., 200.);
+        let ball = spawn_test_ball(&mut app, movement / 2., Vec2::Y);
+        app.world.get_mut::<LastMove>(ball).unwrap().0 = movement;
+        app.update();
+
+        assert_eq!(**app.world.get::<Brick>(brick).unwrap(), 1);
+        assert!(ball_velocity(&app, ball).y < 0.);
+        // Put back below the brick, where it first touched it
+        assert!(app.world.get::<Transform>(ball).unwrap().translation.y < 0.);
+    }
+
+    #[test]
+    fn a_ball_under_two_bricks_bounces_like_off_one_flat_wall() {
+        let mut app = brick_collision_app();
+        let left = spawn_test_brick(&mut app, Vec2::new(-50., 0.), 2, BrickKind::Normal);
+        let right = spawn_test_brick(&mut app, Vec2::new(50., 0.), 2, BrickKind::Normal);
+        let ball = spawn_test_ball(&mut app, Vec2::new(0., -30.), Vec2::new(0.6, 0.8));
+        app.update();
+
+        assert!(ball_velocity(&app, ball).abs_diff_eq(Vec2::new(0.6, -0.8), 1e-5));
+        assert_eq!(**app.world.get::<Brick>(left).unwrap(), 1);
+        assert_eq!(**app.world.get::<Brick>(right).unwrap(), 1);
+    }
+
+    #[test]
+    fn a_ball_in_the_corner_between_two_bricks_comes_back_out_of_it() {
+        let mut app = brick_collision_app();
+        // One brick above the ball, the other to its right and a row lower
+        let above = spawn_test_brick(&mut app, Vec2::ZERO, 2, BrickKind::Normal);
+        let beside = spawn_test_brick(&mut app, Vec2::new(100., -50.), 2, BrickKind::Normal);
+        let ball = spawn_test_ball(&mut app, Vec2::new(37., -37.), Vec2::new(0.6, 0.8));
+        app.update();
+
+        let bounced = ball_velocity(&app, ball);
+        assert!(bounced.x < 0. && bounced.y < 0.);
+        assert!((bounced.length() - 1.).abs() < 1e-5);
+        assert_eq!(**app.world.get::<Brick>(above).unwrap(), 1);
+        assert_eq!(**app.world.get::<Brick>(beside).unwrap(), 1);
+    }
+
+    #[test]
+    fn leaving_the_preview_counts_down_before_playing() {
+        let mut settings = GameSettings::default();
+        settings.gameplay.countdown = true;
+        assert_eq!(
+            play_state(&GameState::Preview, &settings, false),
+            GameState::Countdown
+        );
+        // Asking again during the countdown skips it
+        assert_eq!(
+            play_state(&GameState::Countdown, &settings, false),
+            GameState::Playing
+        );
+
+        settings.gameplay.countdown = false;
+        assert_eq!(
+            play_state(&GameState::Preview, &settings, false),
+            GameState::Playing
+        );
+    }
 }
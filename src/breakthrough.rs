@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+
+use crate::{
+    ball_tints::{ball_color, BallTint},
+    breaker::{BrickDestroyed, Velocity},
+    catch::Held,
+    elements::BallElement,
+    settings::GameSettings,
+    theme::ThemeColors,
+};
+
+// Breaks count towards the same run as long as each comes this soon after the last
+const BREAK_WINDOW: f32 = 1.5;
+// This many breaks in one run sends the ball into a breakthrough
+const BREAKTHROUGH_THRESHOLD: usize = 5;
+const BREAKTHROUGH_SPEEDUP: f32 = 1.4;
+const BREAKTHROUGH_COLOR: Color = Color::rgb(1., 1., 0.85);
+
+// The bricks a ball has broken in quick succession. Once enough are broken the ball breaks
+// through, speeding up and piercing bricks until it goes a whole window without a break
+#[derive(Component)]
+pub struct BreakStreak {
+    breaks: usize,
+    window: Timer,
+    breaking_through: bool,
+    speed_before: f32, // The ball's speed before it broke through, restored once it ends
+}
+
+impl Default for BreakStreak {
+    fn default() -> Self {
+        BreakStreak {
+            breaks: 0,
+            window: Timer::from_seconds(BREAK_WINDOW, TimerMode::Once),
+            breaking_through: false,
+            speed_before: 0.,
+        }
+    }
+}
+
+impl BreakStreak {
+    pub fn breaking_through(&self) -> bool {
+        self.breaking_through
+    }
+}
+
+pub fn track_break_streaks(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut ball_q: Query<(&mut BreakStreak, &mut Velocity, &Handle<ColorMaterial>)>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+) {
+    for destroyed in destroyed_events.iter() {
+        let Ok((mut streak, mut ball_v, handle)) = ball_q.get_mut(destroyed.ball) else {
+            continue; // The ball was lost in the same tick
+        };
+        streak.breaks += 1;
+        streak.window.reset();
+        if streak.breaks >= BREAKTHROUGH_THRESHOLD && !streak.breaking_through {
+            streak.breaking_through = true;
+            streak.speed_before = ball_v.length();
+            **ball_v *= BREAKTHROUGH_SPEEDUP;
+            if let Some(mat) = mats.get_mut(handle) {
+                mat.color = BREAKTHROUGH_COLOR;
+            }
+        }
+    }
+}
+
+// Ends runs whose window lapsed, bringing a breaking through ball back to its own speed and color.
+// A ball caught in the meantime is held still, so it is the speed it launches with that goes back
+pub fn tick_break_streaks(
+    mut ball_q: Query<(
+        &mut BreakStreak,
+        &mut Velocity,
+        Option<&mut Held>,
+        &Handle<ColorMaterial>,
+        Option<&BallElement>,
+        Option<&BallTint>,
+    )>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    theme: Res<ThemeColors>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    for (mut streak, mut ball_v, held, handle, element, tint) in ball_q.iter_mut() {
+        if streak.breaks == 0 || !streak.window.tick(time.delta()).finished() {
+            continue;
+        }
+        streak.breaks = 0;
+        if streak.breaking_through {
+            streak.breaking_through = false;
+            let velocity = match held {
+                Some(held) => held.into_inner().launch_mut(),
+                None => &mut ball_v.0,
+            };
+            *velocity = velocity.normalize_or_zero() * streak.speed_before;
+            if let Some(mat) = mats.get_mut(handle) {
+                mat.color = match element {
+                    Some(element) => element.element.color(),
+                    None => ball_color(tint, &settings, theme.ball),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bricks::BrickKind,
+        misc::test_utils::{advance_time, test_app},
+        theme::Theme,
+    };
+
+    const BALL_SPEED: f32 = 100.;
+
+    fn streak_app() -> (App, Entity) {
+        let mut app = test_app();
+        app.insert_resource(Theme::Classic.colors())
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_event::<BrickDestroyed>()
+            .add_systems(
+                Update,
+                (
+                    track_break_streaks,
+                    tick_break_streaks.before(track_break_streaks),
+                ),
+            );
+        let ball = app
+            .world
+            .spawn((
+                BreakStreak::default(),
+                Velocity(Vec2::new(0., BALL_SPEED)),
+                Handle::<ColorMaterial>::default(),
+            ))
+            .id();
+        advance_time(&mut app, 0.);
+        app.update();
+        (app, ball)
+    }
+
+    fn break_bricks(app: &mut App, ball: Entity, count: usize) {
+        for _ in 0..count {
+            app.world.send_event(BrickDestroyed {
+                position: Vec3::ZERO,
+                kind: BrickKind::default(),
+                ball,
+                color: Color::WHITE,
+            });
+            advance_time(app, BREAK_WINDOW / 10.);
+            app.update();
+        }
+    }
+
+    fn breaking_through(app: &App, ball: Entity) -> bool {
+        app.world
+            .get::<BreakStreak>(ball)
+            .unwrap()
+            .breaking_through()
+    }
+
+    fn speed(app: &App, ball: Entity) -> f32 {
+        app.world.get::<Velocity>(ball).unwrap().length()
+    }
+
+    #[test]
+    fn enough_quick_breaks_break_through_until_the_window_lapses() {
+        let (mut app, ball) = streak_app();
+        break_bricks(&mut app, ball, BREAKTHROUGH_THRESHOLD - 1);
+        assert!(!breaking_through(&app, ball));
+        assert_eq!(speed(&app, ball), BALL_SPEED);
+
+        // Brick collisions pierce for as long as the ball is breaking through
+        break_bricks(&mut app, ball, 1);
+        assert!(breaking_through(&app, ball));
+        assert_eq!(speed(&app, ball), BALL_SPEED * BREAKTHROUGH_SPEEDUP);
+
+        advance_time(&mut app, BREAK_WINDOW);
+        app.update();
+        assert!(!breaking_through(&app, ball));
+        assert_eq!(speed(&app, ball), BALL_SPEED);
+    }
+
+    #[test]
+    fn a_ball_caught_while_breaking_through_launches_at_its_own_speed() {
+        let (mut app, ball) = streak_app();
+        break_bricks(&mut app, ball, BREAKTHROUGH_THRESHOLD);
+        // Caught the way catch_balls does, holding the ball still with the velocity it will launch
+        // with
+        let launch = Vec2::new(0., BALL_SPEED * BREAKTHROUGH_SPEEDUP);
+        app.world
+            .entity_mut(ball)
+            .insert((Velocity(Vec2::ZERO), Held::docked(launch)));
+
+        advance_time(&mut app, BREAK_WINDOW);
+        app.update();
+        assert!(!breaking_through(&app, ball));
+        assert_eq!(speed(&app, ball), 0.);
+        let mut held = app.world.get_mut::<Held>(ball).unwrap();
+        assert_eq!(*held.launch_mut(), Vec2::new(0., BALL_SPEED));
+    }
+}
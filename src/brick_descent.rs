@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{GameStateTransition, PlayerMessage, PADDLE_DIST_FROM_BOTTOM_WALL, PADDLE_SIZE},
+    bricks::{Brick, BrickDimensions, Destroying},
+    settings::GameSettings,
+    walls::BOTTOM_WALL,
+};
+
+pub const DEFAULT_BRICK_DESCENT_INTERVAL: f32 = 20.;
+// Intervals any shorter would leave no time to clear a row before the next one comes down
+pub const MIN_BRICK_DESCENT_INTERVAL: f32 = 2.;
+// How fast bricks slide into their new row, in pixels per second
+const BRICK_DESCENT_SLIDE_SPEED: f32 = 150.;
+// Bricks breach once they would come down past the top of the paddle
+pub const BREACH_LINE: f32 = BOTTOM_WALL + PADDLE_DIST_FROM_BOTTOM_WALL + PADDLE_SIZE.y / 2.;
+
+// Challenge mode timer, every time it finishes the surviving bricks all move down a row
+#[derive(Resource, Default)]
+pub struct BrickDescent(Timer);
+
+impl BrickDescent {
+    fn new(settings: &GameSettings) -> Self {
+        let interval = settings
+            .gameplay
+            .brick_descent_interval
+            .max(MIN_BRICK_DESCENT_INTERVAL);
+        BrickDescent(Timer::from_seconds(interval, TimerMode::Repeating))
+    }
+}
+
+// A brick sliding down to the y of its new row
+#[derive(Component)]
+pub struct Descending {
+    pub target_y: f32,
+}
+
+// Each level and each run gets the full interval before the first row comes down
+pub fn reset_brick_descent(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut descent: ResMut<BrickDescent>,
+    settings: Res<GameSettings>,
+) {
+    for msg in game_msgs.iter() {
+        if matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::RestartGame
+        ) {
+            *descent = BrickDescent::new(&settings);
+        }
+    }
+}
+
+pub fn start_brick_descent(mut descent: ResMut<BrickDescent>, settings: Res<GameSettings>) {
+    *descent = BrickDescent::new(&settings);
+}
+
+// Moves every brick down a row when the timer finishes. If that would bring any brick down to the
+// paddle, the bricks have breached instead: the player loses health and the level starts over
+#[allow(clippy::too_many_arguments)]
+pub fn descend_bricks(
+    mut commands: Commands,
+    mut descent: ResMut<BrickDescent>,
+    mut player_msgs: EventWriter<PlayerMessage>,
+    mut game_msgs: EventWriter<GameStateTransition>,
+    brick_q: Query<(Entity, &Transform, Option<&Descending>), (With<Brick>, Without<Destroying>)>,
+    brick_dims: Res<BrickDimensions>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if !settings.gameplay.brick_descent || !descent.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let row_height = brick_dims.size.y + brick_dims.margin;
+    // Bricks still sliding count from where they are headed
+    let target_y = |tform: &Transform, descending: Option<&Descending>| {
+        descending.map_or(tform.translation.y, |d| d.target_y) - row_height
+    };
+
+    let breached = brick_q.iter().any(|(_, tform, descending)| {
+        target_y(tform, descending) - tform.scale.y / 2. <= BREACH_LINE
+    });
+    if breached {
+        player_msgs.send(PlayerMessage::JustLostHealth);
+        game_msgs.send(GameStateTransition::RestartLevel);
+        return;
+    }
+    for (brick, tform, descending) in brick_q.iter() {
+        commands.entity(brick).insert(Descending {
+            target_y: target_y(tform, descending),
+        });
+    }
+}
+
+pub fn slide_descending_bricks(
+    mut commands: Commands,
+    mut brick_q: Query<(Entity, &mut Transform, &Descending)>,
+    time: Res<Time>,
+) {
+    let step = BRICK_DESCENT_SLIDE_SPEED * time.delta_seconds();
+    for (brick, mut tform, descending) in brick_q.iter_mut() {
+        let remaining = tform.translation.y - descending.target_y;
+        if remaining <= step {
+            tform.translation.y = descending.target_y;
+            commands.entity(brick).remove::<Descending>();
+        } else {
+            tform.translation.y -= step;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{advance_time, sent_events, test_app};
+
+    fn descent_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<BrickDimensions>()
+            .add_event::<PlayerMessage>()
+            .add_event::<GameStateTransition>()
+            .add_systems(Update, (descend_bricks, slide_descending_bricks).chain());
+        let mut settings = app.world.resource_mut::<GameSettings>();
+        settings.gameplay.brick_descent = true;
+        settings.gameplay.brick_descent_interval = MIN_BRICK_DESCENT_INTERVAL;
+        let descent = BrickDescent::new(&settings);
+        app.insert_resource(descent);
+        app
+    }
+
+    fn spawn_brick(app: &mut App, y: f32) -> Entity {
+        let dims = *app.world.resource::<BrickDimensions>();
+        let tform = Transform::from_xyz(0., y, 0.).with_scale(dims.size.extend(1.));
+        app.world.spawn((tform, Brick(1))).id()
+    }
+
+    #[test]
+    fn bricks_move_down_a_row_after_the_interval() {
+        let mut app = descent_app();
+        let brick = spawn_brick(&mut app, 200.);
+        advance_time(&mut app, MIN_BRICK_DESCENT_INTERVAL - 0.5);
+        app.update();
+        assert_eq!(
+            app.world.get::<Transform>(brick).unwrap().translation.y,
+            200.
+        );
+
+        advance_time(&mut app, 0.5);
+        app.update();
+        // Slides the rest of the way down over the next moments
+        for _ in 0..10 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        let dims = app.world.resource::<BrickDimensions>();
+        let row_height = dims.size.y + dims.margin;
+        let y = app.world.get::<Transform>(brick).unwrap().translation.y;
+        assert_eq!(y, 200. - row_height);
+        assert!(app.world.get::<Descending>(brick).is_none());
+    }
+
+    #[test]
+    fn bricks_reaching_the_paddle_restart_the_level() {
+        let mut app = descent_app();
+        let dims = *app.world.resource::<BrickDimensions>();
+        let brick = spawn_brick(&mut app, BREACH_LINE + dims.size.y);
+        advance_time(&mut app, MIN_BRICK_DESCENT_INTERVAL);
+        app.update();
+        assert!(app.world.get::<Descending>(brick).is_none());
+        assert_eq!(sent_events::<PlayerMessage>(&app).len(), 1);
+        let restarts = sent_events::<GameStateTransition>(&app);
+        assert!(matches!(restarts[..], [GameStateTransition::RestartLevel]));
+    }
+}
@@ -1,114 +1,716 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::breaker::{Collider, PADDLE_DIST_FROM_BOTTOM_WALL};
-use crate::walls::{BOTTOM_WALL, LEFT_WALL, RIGHT_WALL, TOP_WALL};
+use crate::breaker::{Collider, GameStateTransition, PADDLE_DIST_FROM_BOTTOM_WALL};
+use crate::elements::Element;
+use crate::settings::GameSettings;
+use crate::walls::{ArenaShape, BOTTOM_WALL, LEFT_WALL, RIGHT_WALL, TOP_WALL};
 
-const BRICK_SIZE: Vec2 = Vec2::new(100., 50.);
-const BRICK_MARGIN: f32 = 5.;
+const DEFAULT_BRICK_SIZE: Vec2 = Vec2::new(100., 50.);
+const DEFAULT_BRICK_MARGIN: f32 = 5.;
 const BRICK_DIST_FROM_SIDE_WALL: f32 = 60.0;
 const BRICK_DIST_FROM_CEILING: f32 = 60.0;
 const BRICK_DIST_FROM_PADDLE: f32 = 270.0;
-pub const BRICK_COLORS: [Color; 3] = [
+const BRICK_DESTROY_DURATION: f32 = 0.2;
+// Brick colors by strength, from 1 up. Anything stronger shares the last color
+const BRICK_COLORS: [Color; 6] = [
     Color::rgb(0.5, 0.5, 1.),
     Color::rgb(1., 0.5, 1.),
     Color::rgb(0.5, 1., 0.5),
+    Color::rgb(1., 0.75, 0.35),
+    Color::rgb(1., 0.4, 0.35),
+    Color::rgb(0.6, 0.25, 0.55),
 ];
+// Told apart by brightness as well as hue, going from lightest to darkest as strength rises
+const COLORBLIND_BRICK_COLORS: [Color; 6] = [
+    Color::rgb(0.94, 0.89, 0.26),
+    Color::rgb(0.34, 0.71, 0.91),
+    Color::rgb(0.9, 0.62, 0.),
+    Color::rgb(0., 0.62, 0.45),
+    Color::rgb(0.84, 0.37, 0.),
+    Color::rgb(0., 0.35, 0.6),
+];
+const GOLDEN_BRICK_COLOR: Color = Color::rgb(1., 0.84, 0.);
+const INDESTRUCTIBLE_BRICK_COLOR: Color = Color::rgb(0.45, 0.45, 0.5);
+// An explosive brick destroys the bricks whose centers are within this many of its own sizes,
+// which reaches the bricks touching it, diagonals included
+pub const EXPLOSION_REACH: f32 = 1.5;
+// Degrees of hue the brick color cycle moves through per second
+const COLOR_CYCLE_SPEED: f32 = 40.;
+// Degrees of hue between bricks one unit apart horizontally, making the cycle roll across as a wave
+const COLOR_CYCLE_WAVE: f32 = 0.3;
+const COLOR_CYCLE_SATURATION: f32 = 0.8;
+// Cycling brick lightness by strength, so a brick's strength still shows
+const COLOR_CYCLE_LIGHTNESS: [f32; 6] = [0.75, 0.65, 0.55, 0.45, 0.38, 0.3];
+// The bonus round's golden bricks fill this row of the brick area
+const GOLDEN_BRICK_ROW: usize = 2;
+// HP bar height and its gap above the brick, as fractions of the brick's height
+const HP_BAR_HEIGHT: f32 = 0.12;
+const HP_BAR_GAP: f32 = 0.05;
+const HP_BAR_COLOR: Color = Color::rgb(0.3, 1., 0.4);
 
-#[derive(Deref, DerefMut)]
-// Describes the organization of bricks in rows, with the given strengths
-pub struct BrickLayout([u8; 5]);
+// Special behaviors a brick can have on top of its strength
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum BrickKind {
+    #[default]
+    Normal,
+    Freeze,             // Trap brick, freezes the paddle when destroyed
+    Mystery,            // Grants a random power-up when destroyed
+    Elemental(Element), // Tags the ball with an element on contact
+    Streak(u8),         // Only breaks after this many hits without the ball touching the paddle
+    Golden,             // Only spawned in the bonus round, worth extra points
+    Reverse,            // Trap brick, swaps the paddle's left and right controls when destroyed
+    Indestructible,     // Never breaks, so it isn't needed to clear the level
+    Explosive,          // Destroys the bricks around it when destroyed
+    Multiball,          // Splits off an extra ball when destroyed
+}
 
-const LEVELS: [BrickLayout; 5] = [
-    BrickLayout([1, 1, 1, 1, 1]),
-    BrickLayout([2, 1, 1, 1, 2]),
-    BrickLayout([1, 1, 2, 2, 3]),
-    BrickLayout([1, 3, 1, 3, 1]),
-    BrickLayout([3, 3, 1, 3, 3]),
-];
+impl BrickKind {
+    // Text drawn on the brick so special kinds stand out from normal bricks
+    fn label(&self) -> Option<String> {
+        match self {
+            BrickKind::Mystery => Some("?".to_string()),
+            BrickKind::Elemental(element) => Some(element.label().to_string()),
+            BrickKind::Streak(needed) => Some(streak_label(0, *needed)),
+            BrickKind::Reverse => Some("<>".to_string()),
+            BrickKind::Explosive => Some("*".to_string()),
+            BrickKind::Multiball => Some("+".to_string()),
+            BrickKind::Normal
+            | BrickKind::Freeze
+            | BrickKind::Golden
+            | BrickKind::Indestructible => None,
+        }
+    }
+
+    // Whether the level can only be cleared once this brick is destroyed. Only these bricks are
+    // counted by the BrickTracker
+    pub fn needed_to_clear(&self) -> bool {
+        *self != BrickKind::Indestructible
+    }
+
+    // Overrides the strength based color for kinds that should always look the same
+    pub fn tint(&self) -> Option<Color> {
+        match self {
+            BrickKind::Golden => Some(GOLDEN_BRICK_COLOR),
+            BrickKind::Indestructible => Some(INDESTRUCTIBLE_BRICK_COLOR),
+            _ => None,
+        }
+    }
+}
+
+// Counts every time a ball touches a paddle, so bricks can tell if the paddle was touched since
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PaddleContacts(u32);
+
+// Consecutive hits on a Streak brick, which only count if no paddle contact happened in between
+#[derive(Component, Default)]
+pub struct StreakProgress {
+    hits: u8,
+    paddle_contacts: u32, // The PaddleContacts value at the last hit
+}
+
+impl StreakProgress {
+    // Registers a hit, returning true once the streak is long enough to break the brick
+    pub fn register_hit(&mut self, paddle_contacts: u32, needed: u8) -> bool {
+        if self.paddle_contacts != paddle_contacts {
+            self.hits = 0; // The paddle was touched since the last hit, start over
+        }
+        self.paddle_contacts = paddle_contacts;
+        self.hits += 1;
+        self.hits >= needed
+    }
+}
+
+fn streak_label(hits: u8, needed: u8) -> String {
+    format!("{hits}/{needed}")
+}
+
+// Shows each Streak brick's progress on its label
+pub fn update_streak_labels(
+    brick_q: Query<(&StreakProgress, &BrickKind, &Children), Changed<StreakProgress>>,
+    mut text_q: Query<&mut Text>,
+) {
+    for (progress, kind, children) in brick_q.iter() {
+        let BrickKind::Streak(needed) = kind else {
+            continue;
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = text_q.get_mut(*child) {
+                text.sections[0].value = streak_label(progress.hits.min(*needed), *needed);
+            }
+        }
+    }
+}
+
+// A bar above a strong brick showing how much of its strength is left. It is a child of the
+// brick, so it is in the brick's scaled space where the brick is one unit across
+#[derive(Component)]
+pub struct HpBar {
+    max: u8, // The brick's strength when it spawned
+}
+
+impl HpBar {
+    // The fill shrinks towards the bar's left end
+    fn transform(&self, strength: u8) -> Transform {
+        let fill = strength as f32 / self.max as f32;
+        Transform {
+            translation: Vec3::new((fill - 1.) / 2., 0.5 + HP_BAR_GAP + HP_BAR_HEIGHT / 2., 1.),
+            scale: Vec3::new(fill, HP_BAR_HEIGHT, 1.),
+            ..default()
+        }
+    }
+}
+
+// Gives newly spawned bricks an HP bar when they are strong enough for color alone to be unclear
+pub fn add_hp_bars(
+    mut commands: Commands,
+    brick_q: Query<(Entity, &Brick), Added<Brick>>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.visual.hp_bars {
+        return;
+    }
+    for (brick_ent, brick) in brick_q.iter() {
+        if **brick < settings.visual.hp_bar_min_strength {
+            continue;
+        }
+        let bar = HpBar { max: **brick };
+        let transform = bar.transform(**brick);
+        commands.entity(brick_ent).with_children(|parent| {
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: HP_BAR_COLOR,
+                        ..default()
+                    },
+                    transform,
+                    ..default()
+                },
+                bar,
+                Name::new("HpBar"),
+            ));
+        });
+    }
+}
+
+// Shrinks each HP bar as its brick is hit
+pub fn update_hp_bars(
+    brick_q: Query<(&Brick, &Children), Changed<Brick>>,
+    mut bar_q: Query<(&HpBar, &mut Transform)>,
+) {
+    for (brick, children) in brick_q.iter() {
+        for child in children.iter() {
+            if let Ok((bar, mut tform)) = bar_q.get_mut(*child) {
+                *tform = bar.transform(**brick);
+            }
+        }
+    }
+}
+
+// Marker for the number on a brick showing how much of its strength is left
+#[derive(Component)]
+pub struct StrengthLabel;
+
+// Numbers newly spawned bricks with their strength. Bricks that already carry a kind label keep
+// just that one, and indestructible bricks have no strength worth showing
+pub fn add_strength_labels(
+    mut commands: Commands,
+    brick_q: Query<(Entity, &Brick, &BrickKind, &Transform), Added<Brick>>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.visual.strength_numbers {
+        return;
+    }
+    for (brick_ent, brick, kind, tform) in brick_q.iter() {
+        if kind.label().is_some() || !kind.needed_to_clear() {
+            continue;
+        }
+        let label = brick_label(brick.0.to_string(), tform.scale.truncate());
+        commands.entity(brick_ent).with_children(|parent| {
+            parent.spawn((label, StrengthLabel, Name::new("StrengthLabel")));
+        });
+    }
+}
+
+// Counts each strength number down as its brick is hit
+pub fn update_strength_labels(
+    brick_q: Query<(&Brick, &Children), Changed<Brick>>,
+    mut label_q: Query<&mut Text, With<StrengthLabel>>,
+) {
+    for (brick, children) in brick_q.iter() {
+        if **brick == 0 {
+            continue; // Keeps showing its last strength while the destroy animation plays
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = label_q.get_mut(*child) {
+                text.sections[0].value = brick.0.to_string();
+            }
+        }
+    }
+}
+
+// The collision shape of a brick, rectangles use the ball's AABB collision
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum BrickShape {
+    #[default]
+    Rectangle,
+    Circle, // Drawn with a diameter equal to the brick height
+}
+
+impl BrickShape {
+    fn size(&self, brick_size: Vec2) -> Vec2 {
+        match self {
+            BrickShape::Rectangle => brick_size,
+            BrickShape::Circle => Vec2::splat(brick_size.y),
+        }
+    }
+
+    fn texture_path(&self) -> &'static str {
+        match self {
+            BrickShape::Rectangle => "images/holo-brick.png",
+            BrickShape::Circle => "images/round-brick.png",
+        }
+    }
+}
+
+// A row of bricks sharing the same kind and shape, as written in a level file
+#[derive(Clone, Debug, Deserialize)]
+pub struct BrickRow {
+    pub strength: RowStrength,
+    #[serde(default)]
+    pub kind: BrickKind,
+    #[serde(default)]
+    pub shape: BrickShape,
+}
+
+// The strength of each brick in a row
+#[derive(Clone, Debug, Deserialize)]
+pub enum RowStrength {
+    Uniform(u8), // Every column gets a brick of this strength
+    // One strength per column from the left, 0 leaves the cell empty. Columns past the end of the
+    // list are left empty, strengths past the last column are ignored
+    Cells(Vec<u8>),
+}
+
+impl RowStrength {
+    // The strength of the brick in a column, None for an empty cell
+    fn at(&self, col: u32) -> Option<u8> {
+        let strength = match self {
+            RowStrength::Uniform(strength) => *strength,
+            RowStrength::Cells(cells) => cells.get(col as usize).copied().unwrap_or(0),
+        };
+        (strength > 0).then_some(strength)
+    }
+}
+
+// Describes the organization of bricks in rows, from the top down. A level can have any number
+// of rows, those that don't fit in the brick area are left out
+#[derive(Clone, Debug, Default, Deref, DerefMut, Deserialize)]
+#[serde(transparent)]
+pub struct BrickLayout(pub Vec<BrickRow>);
 
 // There will be many bricks, deployed at level start and
 #[derive(Component, Clone, Copy, Deref, DerefMut)]
-pub struct Brick(u8);
+pub struct Brick(pub u8);
+
+// A destroyed brick shrinking and fading out. It no longer has a Collider, so the ball passes through
+#[derive(Component)]
+pub struct Destroying {
+    timer: Timer,
+    start_scale: Vec3,
+}
+
+impl Destroying {
+    pub fn new(start_scale: Vec3) -> Self {
+        Destroying {
+            timer: Timer::from_seconds(BRICK_DESTROY_DURATION, TimerMode::Once),
+            start_scale,
+        }
+    }
+}
+
+pub fn animate_destroying_bricks(
+    mut commands: Commands,
+    mut brick_q: Query<(Entity, &mut Destroying, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (ent, mut destroying, mut tform, mut sprite) in brick_q.iter_mut() {
+        destroying.timer.tick(time.delta());
+        if destroying.timer.finished() {
+            commands.entity(ent).despawn_recursive();
+            continue;
+        }
+        let left = destroying.timer.percent_left();
+        tform.scale = destroying.start_scale * left;
+        sprite.color.set_a(left);
+    }
+}
+
+// Indestructible bricks are still standing when a level is cleared, so they are cleared away before
+// the bonus round or the next level's bricks take their place
+pub fn clear_indestructible_bricks(
+    mut commands: Commands,
+    mut game_msgs: EventReader<GameStateTransition>,
+    brick_q: Query<(Entity, &BrickKind), With<Brick>>,
+) {
+    let level_over = game_msgs.iter().any(|msg| {
+        matches!(
+            msg,
+            GameStateTransition::NextLevel | GameStateTransition::ToBonusRound
+        )
+    });
+    if !level_over {
+        return;
+    }
+    for (ent, kind) in brick_q.iter() {
+        if !kind.needed_to_clear() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
+
+// Rotates the hue of every brick over time, offset by its x position for a wave effect. Runs only
+// while playing so pausing freezes the colors. Lightness comes from the current strength, so this
+// wins over the recolor brick_collision does on hits without losing what it shows
+pub fn cycle_brick_colors(
+    mut elapsed: Local<f32>,
+    mut brick_q: Query<(&Brick, &BrickKind, &Transform, &mut Sprite), Without<Destroying>>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if !settings.visual.brick_color_cycle || settings.accessibility.reduce_motion {
+        return;
+    }
+    *elapsed += time.delta_seconds();
+    for (brick, kind, tform, mut sprite) in brick_q.iter_mut() {
+        if kind.tint().is_some() || brick.0 == 0 {
+            continue;
+        }
+        let hue = (*elapsed * COLOR_CYCLE_SPEED + tform.translation.x * COLOR_CYCLE_WAVE)
+            .rem_euclid(360.);
+        let lightness = COLOR_CYCLE_LIGHTNESS[strength_index(brick.0, COLOR_CYCLE_LIGHTNESS.len())];
+        sprite.color = Color::hsla(hue, COLOR_CYCLE_SATURATION, lightness, sprite.color.a());
+    }
+}
+
+// The size of each brick and the gap between them, smaller bricks make for denser levels
+#[derive(Resource, Clone, Copy)]
+pub struct BrickDimensions {
+    pub size: Vec2,
+    pub margin: f32,
+}
+
+impl Default for BrickDimensions {
+    fn default() -> Self {
+        BrickDimensions {
+            size: DEFAULT_BRICK_SIZE,
+            margin: DEFAULT_BRICK_MARGIN,
+        }
+    }
+}
 
 pub fn spawn_bricks(
     commands: &mut Commands,
-    level: usize,
+    brick_layout: &BrickLayout,
+    dims: &BrickDimensions,
+    arena: ArenaShape,
+    palette: BrickPalette,
+    asset_server: &Res<AssetServer>,
+) -> usize {
+    let Some(grid) = brick_grid(dims) else {
+        return 0;
+    };
+
+    if brick_layout.len() > grid.rows {
+        warn!(
+            "Only {} of the layout's {} rows fit in the brick area",
+            grid.rows,
+            brick_layout.len()
+        );
+    }
+    let mut num_bricks = 0;
+    for (row, brick_row) in brick_layout.iter().enumerate().take(grid.rows) {
+        num_bricks += spawn_brick_row(
+            commands,
+            brick_row,
+            grid.row_y(row, dims),
+            grid.left_edge,
+            grid.cols,
+            dims,
+            arena,
+            palette,
+            asset_server,
+        );
+    }
+    num_bricks
+}
+
+// Spawns the bonus round's single row of golden bricks, returning how many were spawned
+pub fn spawn_golden_bricks(
+    commands: &mut Commands,
+    dims: &BrickDimensions,
+    arena: ArenaShape,
     asset_server: &Res<AssetServer>,
 ) -> usize {
-    #[allow(clippy::assertions_on_constants)]
-    {
-        assert!(BRICK_SIZE.x > 0.);
-        assert!(BRICK_SIZE.y > 0.);
+    let Some(grid) = brick_grid(dims) else {
+        return 0;
+    };
+    // Moves up for bricks so large there aren't that many rows
+    let row_y = grid.row_y(GOLDEN_BRICK_ROW.min(grid.rows - 1), dims);
+    let golden_row = BrickRow {
+        strength: RowStrength::Uniform(1),
+        kind: BrickKind::Golden,
+        shape: BrickShape::Rectangle,
+    };
+    spawn_brick_row(
+        commands,
+        &golden_row,
+        row_y,
+        grid.left_edge,
+        grid.cols,
+        dims,
+        arena,
+        BrickPalette::default(), // Unused, golden bricks are always tinted gold
+        asset_server,
+    )
+}
+
+// No brick reaches below this, leaving room for the ball to move above the paddle
+fn brick_area_bottom() -> f32 {
+    BOTTOM_WALL + PADDLE_DIST_FROM_BOTTOM_WALL + BRICK_DIST_FROM_PADDLE
+}
+
+// Where a level's bricks go: how many columns and rows fit in the brick area, the left edge of the
+// first column, and the y of the first row
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BrickGrid {
+    pub cols: u32,
+    pub rows: usize,
+    pub left_edge: f32,
+    pub top_y: f32,
+}
+
+impl BrickGrid {
+    pub fn row_y(&self, row: usize, dims: &BrickDimensions) -> f32 {
+        self.top_y - row as f32 * (dims.size.y + dims.margin)
+    }
+}
+
+// The grid bricks of the given dimensions are laid out in. None if the dimensions are unusable or
+// not even one brick fits in the brick area, in which case no bricks are spawned
+pub fn brick_grid(dims: &BrickDimensions) -> Option<BrickGrid> {
+    // Also rejects NaN
+    let valid = dims.size.x > 0. && dims.size.y > 0. && dims.margin >= 0.;
+    if !valid {
+        warn!(
+            "Invalid {}x{} bricks with a {} margin, spawning none",
+            dims.size.x, dims.size.y, dims.margin
+        );
+        return None;
     }
     let bricks_width = (RIGHT_WALL - LEFT_WALL) - 2. * BRICK_DIST_FROM_SIDE_WALL;
-    let bottom_edge = BOTTOM_WALL + PADDLE_DIST_FROM_BOTTOM_WALL + BRICK_DIST_FROM_PADDLE;
-    let bricks_height = TOP_WALL - bottom_edge - BRICK_DIST_FROM_CEILING;
-    assert!(bricks_width > BRICK_SIZE.x);
-    assert!(bricks_height > BRICK_SIZE.y);
+    let bricks_height = TOP_WALL - brick_area_bottom() - BRICK_DIST_FROM_CEILING;
 
-    let brick_cols = (bricks_width / (BRICK_SIZE.x + BRICK_MARGIN)).floor() as u32;
-    let brick_rows = LEVELS.len();
+    let brick_cols = (bricks_width / (dims.size.x + dims.margin)).floor() as u32;
+    if brick_cols == 0 || bricks_height < dims.size.y {
+        warn!(
+            "{}x{} bricks don't fit in the {bricks_width}x{bricks_height} brick area, spawning none",
+            dims.size.x, dims.size.y
+        );
+        return None;
+    }
+    // Each row's bottom edge must stay above the brick area's bottom
+    let brick_rows = (bricks_height / (dims.size.y + dims.margin)).floor() as usize + 1;
 
     // Determine the starting position from top left to bottom right, centering the bricks
     let center = LEFT_WALL + (RIGHT_WALL - LEFT_WALL) / 2.0;
     let left_edge = center
-        - ((brick_cols as f32) / 2.0 * BRICK_SIZE.x)
-        - ((brick_cols - 1) as f32 / 2.0 * BRICK_MARGIN);
-    let offset_y = TOP_WALL - BRICK_DIST_FROM_CEILING + BRICK_SIZE.y / 2.0;
-
-    let brick_layout = &LEVELS[level];
+        - ((brick_cols as f32) / 2.0 * dims.size.x)
+        - ((brick_cols - 1) as f32 / 2.0 * dims.margin);
+    let offset_y = TOP_WALL - BRICK_DIST_FROM_CEILING + dims.size.y / 2.0;
+    Some(BrickGrid {
+        cols: brick_cols,
+        rows: brick_rows,
+        left_edge,
+        top_y: offset_y,
+    })
+}
 
-    let mut num_bricks = 0;
-    for row in 0..brick_rows {
-        let row_strength = brick_layout[row];
-        let row_y = offset_y - row as f32 * (BRICK_SIZE.y + BRICK_MARGIN);
-        num_bricks += spawn_brick_row(
-            commands,
-            row_strength,
-            row_y,
-            left_edge,
-            brick_cols,
-            asset_server,
-        );
+// The center of the brick grid cell nearest a point, the same cells levels are laid out in. None
+// if the point is outside the brick area
+pub fn snap_to_brick_grid(position: Vec2, dims: &BrickDimensions) -> Option<Vec2> {
+    let grid = brick_grid(dims)?;
+    let step = dims.size + dims.margin;
+    let first = Vec2::new(grid.left_edge + dims.size.x / 2., grid.top_y);
+    let col = ((position.x - first.x) / step.x).round();
+    let row = ((first.y - position.y) / step.y).round();
+    if col < 0. || col >= grid.cols as f32 || row < 0. || row >= grid.rows as f32 {
+        return None;
     }
-    num_bricks
+    Some(Vec2::new(first.x + col * step.x, first.y - row * step.y))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_brick_row(
     commands: &mut Commands,
-    brick_strength: u8,
+    brick_row: &BrickRow,
     y_position: f32,
     left_edge: f32,
     cols: u32,
+    dims: &BrickDimensions,
+    arena: ArenaShape,
+    palette: BrickPalette,
     asset_server: &Res<AssetServer>,
 ) -> usize {
-    let offset_x = left_edge + BRICK_SIZE.x / 2.0;
+    let offset_x = left_edge + dims.size.x / 2.0;
 
     let mut spawned = 0;
     for col in 0..cols {
         let brick_pos = Vec2::new(
-            offset_x + col as f32 * (BRICK_SIZE.x + BRICK_MARGIN),
+            offset_x + col as f32 * (dims.size.x + dims.margin),
             y_position,
         );
-        let brick = Brick(brick_strength);
-        commands.spawn((
-            brick_sprite(brick_pos, brick.clone(), asset_server),
-            brick,
-            Collider,
+        let Some(brick_strength) = brick_row.strength.at(col) else {
+            continue; // Left empty by the layout
+        };
+        if !arena.fits(brick_pos, dims.size) {
+            continue; // Cut off by the arena's corners
+        }
+        spawn_brick(
+            commands,
+            brick_strength,
+            brick_row.kind,
+            brick_row.shape,
+            brick_pos,
+            dims,
+            palette,
+            asset_server,
             Name::new(format!("Brick{spawned}")),
-        ));
+        );
         spawned += 1;
     }
-    spawned
+    // Returns how many of the spawned bricks must be destroyed to clear the level
+    if brick_row.kind.needed_to_clear() {
+        spawned
+    } else {
+        0
+    }
 }
 
-fn brick_sprite(position: Vec2, brick: Brick, asset_server: &Res<AssetServer>) -> SpriteBundle {
-    let color = BRICK_COLORS[(brick.0 - 1) as usize];
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_brick(
+    commands: &mut Commands,
+    brick_strength: u8,
+    brick_kind: BrickKind,
+    brick_shape: BrickShape,
+    position: Vec2,
+    dims: &BrickDimensions,
+    palette: BrickPalette,
+    asset_server: &Res<AssetServer>,
+    name: Name,
+) -> Entity {
+    let brick = Brick(brick_strength);
+    let mut brick_ent = commands.spawn((
+        brick_sprite(
+            position,
+            dims.size,
+            brick_shape,
+            brick.clone(),
+            brick_kind,
+            palette,
+            asset_server,
+        ),
+        brick,
+        brick_kind,
+        brick_shape,
+        Collider,
+        name,
+    ));
+    if let Some(label) = brick_kind.label() {
+        brick_ent.with_children(|parent| {
+            parent.spawn(brick_label(label, dims.size));
+        });
+    }
+    if let BrickKind::Streak(_) = brick_kind {
+        brick_ent.insert(StreakProgress::default());
+    }
+    brick_ent.id()
+}
+
+// Text drawn on top of a brick, the scale undoes the brick's scale so the text isn't stretched.
+// Longer labels get a smaller font so they still fit across the brick
+fn brick_label(label: String, brick_size: Vec2) -> Text2dBundle {
+    let fit_width = brick_size.x / label.chars().count().max(1) as f32;
+    Text2dBundle {
+        text: Text::from_section(
+            label,
+            TextStyle {
+                font_size: (brick_size.y * 0.8).min(fit_width),
+                color: Color::WHITE,
+                ..default()
+            },
+        ),
+        transform: Transform {
+            translation: Vec3::new(0., 0., 1.),
+            scale: Vec3::new(1. / brick_size.x, 1. / brick_size.y, 1.),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+// The colors bricks are drawn in by strength, chosen in the settings
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrickPalette {
+    #[default]
+    Standard,
+    Colorblind,
+}
+
+impl BrickPalette {
+    pub fn brick_color(&self, strength: u8) -> Color {
+        let colors = match self {
+            BrickPalette::Standard => &BRICK_COLORS,
+            BrickPalette::Colorblind => &COLORBLIND_BRICK_COLORS,
+        };
+        colors[strength_index(strength, colors.len())]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BrickPalette::Standard => "Standard",
+            BrickPalette::Colorblind => "Colorblind",
+        }
+    }
+
+    pub fn next(&self) -> BrickPalette {
+        match self {
+            BrickPalette::Standard => BrickPalette::Colorblind,
+            BrickPalette::Colorblind => BrickPalette::Standard,
+        }
+    }
+}
+
+// Indexes a per strength table starting at strength 1, clamping strengths the table doesn't reach
+fn strength_index(strength: u8, len: usize) -> usize {
+    (strength.max(1) as usize - 1).min(len - 1)
+}
+
+fn brick_sprite(
+    position: Vec2,
+    size: Vec2,
+    shape: BrickShape,
+    brick: Brick,
+    kind: BrickKind,
+    palette: BrickPalette,
+    asset_server: &Res<AssetServer>,
+) -> SpriteBundle {
+    let color = kind.tint().unwrap_or_else(|| palette.brick_color(brick.0));
     SpriteBundle {
-        texture: asset_server.load("images/holo-brick.png"),
+        texture: asset_server.load(shape.texture_path()),
         transform: Transform {
             translation: position.extend(0.),
-            scale: BRICK_SIZE.extend(1.),
+            scale: shape.size(size).extend(1.),
             ..default()
         },
         sprite: Sprite {
@@ -119,3 +721,151 @@ fn brick_sprite(position: Vec2, brick: Brick, asset_server: &Res<AssetServer>) -
         ..default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::test_app;
+
+    #[test]
+    fn halving_the_brick_size_about_doubles_the_columns() {
+        let dims = BrickDimensions::default();
+        let half = BrickDimensions {
+            size: dims.size / 2.,
+            margin: dims.margin / 2.,
+        };
+        let cols = brick_grid(&dims).unwrap().cols;
+        let half_cols = brick_grid(&half).unwrap().cols;
+        let ratio = half_cols as f32 / cols as f32;
+        assert!(
+            (1.75..=2.25).contains(&ratio),
+            "{cols} -> {half_cols} columns"
+        );
+    }
+
+    #[test]
+    fn rows_stay_above_the_brick_area_bottom() {
+        let dims = BrickDimensions::default();
+        let grid = brick_grid(&dims).unwrap();
+        assert!(grid.rows >= 1);
+        let lowest = grid.row_y(grid.rows - 1, &dims) - dims.size.y / 2.;
+        assert!(lowest >= brick_area_bottom());
+        let below = grid.row_y(grid.rows, &dims) - dims.size.y / 2.;
+        assert!(below < brick_area_bottom());
+    }
+
+    #[test]
+    fn unusable_brick_dimensions_have_no_grid() {
+        for (size, margin) in [
+            (Vec2::new(0., 50.), 5.),
+            (Vec2::new(100., -50.), 5.),
+            (Vec2::new(100., 50.), -5.),
+            (Vec2::new(f32::NAN, 50.), 5.),
+        ] {
+            assert_eq!(brick_grid(&BrickDimensions { size, margin }), None);
+        }
+    }
+
+    #[test]
+    fn bricks_wider_than_the_arena_have_no_grid_rather_than_underflowing() {
+        let too_wide = BrickDimensions {
+            size: Vec2::new(RIGHT_WALL - LEFT_WALL, 50.),
+            margin: 5.,
+        };
+        assert_eq!(brick_grid(&too_wide), None);
+    }
+
+    #[test]
+    fn every_strength_maps_to_a_palette_color() {
+        for palette in [BrickPalette::Standard, BrickPalette::Colorblind] {
+            let colors: Vec<Color> = (1..=6)
+                .map(|strength| palette.brick_color(strength))
+                .collect();
+            for (i, color) in colors.iter().enumerate() {
+                assert!(
+                    !colors[i + 1..].contains(color),
+                    "{palette:?} repeats a color"
+                );
+            }
+            // Strengths past the table take its strongest color rather than panicking
+            assert_eq!(palette.brick_color(7), colors[5]);
+            assert_eq!(palette.brick_color(u8::MAX), colors[5]);
+            assert_eq!(palette.brick_color(0), colors[0]);
+        }
+    }
+
+    #[test]
+    fn hp_bar_shrinks_with_each_hit() {
+        let mut app = test_app();
+        app.world.resource_mut::<GameSettings>().visual.hp_bars = true;
+        app.add_systems(Update, (add_hp_bars, update_hp_bars));
+        let brick = app.world.spawn((Transform::default(), Brick(5))).id();
+        app.update();
+
+        for _ in 0..3 {
+            **app.world.get_mut::<Brick>(brick).unwrap() -= 1;
+            app.update();
+        }
+        let mut bar_q = app.world.query::<(&HpBar, &Transform, &Parent)>();
+        let (bar, tform, parent) = bar_q.single(&app.world);
+        assert_eq!(parent.get(), brick);
+        assert_eq!(bar.max, 5);
+        assert!((tform.scale.x - 2. / 5.).abs() < 1e-6);
+    }
+
+    fn strength_shown(app: &mut App) -> String {
+        let mut label_q = app.world.query_filtered::<&Text, With<StrengthLabel>>();
+        label_q.single(&app.world).sections[0].value.clone()
+    }
+
+    #[test]
+    fn strength_numbers_count_down_with_each_hit() {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .visual
+            .strength_numbers = true;
+        app.add_systems(Update, (add_strength_labels, update_strength_labels));
+        let brick = app
+            .world
+            .spawn((
+                Transform::from_scale(DEFAULT_BRICK_SIZE.extend(1.)),
+                Brick(3),
+                BrickKind::Normal,
+            ))
+            .id();
+        app.update();
+
+        assert_eq!(strength_shown(&mut app), "3");
+        **app.world.get_mut::<Brick>(brick).unwrap() -= 1;
+        app.update();
+        assert_eq!(strength_shown(&mut app), "2");
+        // Destroyed bricks keep their last number while they fade out
+        **app.world.get_mut::<Brick>(brick).unwrap() = 0;
+        app.update();
+        assert_eq!(strength_shown(&mut app), "2");
+    }
+
+    #[test]
+    fn weak_bricks_get_no_hp_bar() {
+        let mut app = test_app();
+        app.world.resource_mut::<GameSettings>().visual.hp_bars = true;
+        app.add_systems(Update, add_hp_bars);
+        app.world.spawn((Transform::default(), Brick(1)));
+        app.update();
+        let mut bar_q = app.world.query::<&HpBar>();
+        assert_eq!(bar_q.iter(&app.world).count(), 0);
+    }
+
+    #[test]
+    fn streak_brick_needs_its_hits_without_a_paddle_touch_between() {
+        let mut interrupted = StreakProgress::default();
+        assert!(!interrupted.register_hit(0, 2));
+        // The paddle touched the ball between the hits, so the streak starts over
+        assert!(!interrupted.register_hit(1, 2));
+
+        let mut unbroken = StreakProgress::default();
+        assert!(!unbroken.register_hit(1, 2));
+        assert!(unbroken.register_hit(1, 2));
+    }
+}
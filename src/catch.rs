@@ -0,0 +1,312 @@
+use bevy::{
+    prelude::*,
+    sprite::collide_aabb::{collide, Collision},
+};
+
+use crate::{
+    breaker::{
+        enforce_min_vertical, Ball, CollisionEvent, CollisionKind, CurrentState, GameState,
+        GameStateTransition, Paddle, Velocity,
+    },
+    bricks::PaddleContacts,
+    gamepad::GamepadInput,
+    key_bindings::Action,
+    net::RemotePaddle,
+    physics::aabb_contact_point,
+    returning::ReturningToStart,
+    settings::GameSettings,
+    stats::RunStats,
+};
+
+const LAUNCH_PROMPT_FONT_SIZE: f32 = 30.;
+const LAUNCH_PROMPT_COLOR: Color = Color::rgb(1., 1., 1.);
+
+// A ball caught on top of the paddle, riding along with it until it is launched
+#[derive(Component)]
+pub struct Held {
+    offset: f32,  // From the paddle's center along its width
+    launch: Vec2, // The velocity the ball leaves with, as if it had bounced when caught
+}
+
+impl Held {
+    // A freshly served ball, waiting on the paddle's center to be launched in the serve direction
+    pub fn docked(launch: Vec2) -> Self {
+        Held { offset: 0., launch }
+    }
+
+    // The velocity the ball leaves with once launched
+    pub fn launch_mut(&mut self) -> &mut Vec2 {
+        &mut self.launch
+    }
+}
+
+// Catches balls landing on top of the paddle while fewer than the catch capacity are held. The
+// balls still count towards the ball limit, so holding them never allows extra balls
+#[allow(clippy::too_many_arguments)]
+pub fn catch_balls(
+    mut commands: Commands,
+    mut ball_q: Query<(Entity, &mut Velocity, &mut Transform), (With<Ball>, Without<Held>)>,
+    held_q: Query<(), With<Held>>,
+    paddle_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>, Without<Ball>)>,
+    mut collision_events: EventWriter<CollisionEvent>,
+    mut stats: ResMut<RunStats>,
+    mut paddle_contacts: ResMut<PaddleContacts>,
+    settings: Res<GameSettings>,
+) {
+    let capacity = settings.gameplay.catch_capacity;
+    let Ok(paddle) = paddle_q.get_single() else {
+        return;
+    };
+    let mut held = held_q.iter().count();
+    for (ball, mut ball_v, mut ball_t) in ball_q.iter_mut() {
+        if held >= capacity {
+            return;
+        }
+        let paddle_size = paddle.scale.truncate();
+        let collision = collide(
+            ball_t.translation,
+            ball_t.scale.truncate(),
+            paddle.translation,
+            paddle_size,
+        );
+        if ball_v.y >= 0. || collision != Some(Collision::Top) {
+            continue;
+        }
+        let ball_pos = ball_t.translation.truncate();
+        let paddle_pos = paddle.translation.truncate();
+        collision_events.send(CollisionEvent {
+            contact: aabb_contact_point(ball_pos, paddle_pos, paddle_size),
+            side: collision,
+            kind: CollisionKind::Paddle {
+                offset: ((ball_pos.x - paddle_pos.x) / (paddle_size.x / 2.)).clamp(-1., 1.),
+            },
+        });
+        stats.current_combo = 0; // A catch is a paddle touch like any other
+        **paddle_contacts += 1;
+
+        ball_v.y = -ball_v.y;
+        enforce_min_vertical(&mut ball_v);
+        commands.entity(ball).insert(Held {
+            offset: ball_pos.x - paddle_pos.x,
+            launch: **ball_v,
+        });
+        **ball_v = Vec2::ZERO;
+        ball_t.translation.y = paddle_pos.y + (paddle_size.y + ball_t.scale.y) / 2.;
+        held += 1;
+    }
+}
+
+// Keeps the held balls on top of the paddle as it moves, and on it as it changes width
+pub fn carry_held_balls(
+    mut ball_q: Query<(&mut Transform, &Held), Without<ReturningToStart>>,
+    paddle_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>, Without<Held>)>,
+) {
+    let Ok(paddle) = paddle_q.get_single() else {
+        return;
+    };
+    let half_width = paddle.scale.x / 2.;
+    for (mut ball_t, held) in ball_q.iter_mut() {
+        ball_t.translation.x = paddle.translation.x + held.offset.clamp(-half_width, half_width);
+        ball_t.translation.y = paddle.translation.y + (paddle.scale.y + ball_t.scale.y) / 2.;
+    }
+}
+
+// Launches one held ball per press, the one nearest the paddle's center first. Pressing launch
+// with no ball at all, before the next serve, does nothing
+pub fn launch_held_ball(
+    mut commands: Commands,
+    mut ball_q: Query<(Entity, &mut Velocity, &Held)>,
+    all_balls_q: Query<(), With<Ball>>,
+    keys: Res<Input<KeyCode>>,
+    gamepad: GamepadInput,
+    settings: Res<GameSettings>,
+) {
+    let controls = &settings.controls;
+    if !controls.bindings.just_pressed(Action::Launch, &keys)
+        && !gamepad.just_pressed(Action::Launch, &controls.gamepad)
+    {
+        return;
+    }
+    if all_balls_q.is_empty() {
+        warn!("Launch pressed with no ball to launch, waiting for the serve");
+        return;
+    }
+    let nearest = ball_q
+        .iter_mut()
+        .min_by(|(_, _, a), (_, _, b)| a.offset.abs().total_cmp(&b.offset.abs()));
+    if let Some((ball, mut ball_v, held)) = nearest {
+        **ball_v = held.launch;
+        commands.entity(ball).remove::<Held>();
+    }
+}
+
+// A new level starts with every held ball let go, so they aren't left stuck once back at the start
+pub fn release_held_balls(
+    mut commands: Commands,
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut ball_q: Query<(Entity, &mut Velocity, &Held)>,
+) {
+    for msg in game_msgs.iter() {
+        if let GameStateTransition::NextLevel = msg {
+            for (ball, mut ball_v, held) in ball_q.iter_mut() {
+                **ball_v = held.launch;
+                commands.entity(ball).remove::<Held>();
+            }
+        }
+    }
+}
+
+// Marker for the text naming the launch key while a ball waits on the paddle
+#[derive(Component)]
+pub struct LaunchPrompt;
+
+// Names the launch key for as long as a ball is held during play, so a docked serve never leaves
+// the player wondering how to get the ball going
+pub fn show_launch_prompt(
+    mut commands: Commands,
+    held_q: Query<(), With<Held>>,
+    prompt_q: Query<Entity, With<LaunchPrompt>>,
+    game_state: Res<CurrentState>,
+    settings: Res<GameSettings>,
+) {
+    let playing = matches!(**game_state, GameState::Playing | GameState::BonusRound);
+    let waiting = playing && !held_q.is_empty();
+    if waiting && prompt_q.is_empty() {
+        let launch_key = settings.controls.bindings.prompt_label(Action::Launch);
+        commands.spawn((
+            TextBundle::from_section(
+                format!("Press {launch_key} to launch"),
+                TextStyle {
+                    font_size: LAUNCH_PROMPT_FONT_SIZE,
+                    color: LAUNCH_PROMPT_COLOR,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Percent(25.),
+                left: Val::Percent(40.),
+                ..default()
+            }),
+            LaunchPrompt,
+            Name::new("LaunchPrompt"),
+        ));
+    } else if !waiting {
+        for ent in prompt_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        breaker::{paddle_sprite, PADDLE_SIZE},
+        misc::test_utils::test_app,
+    };
+
+    const TEST_BALL_SIZE: f32 = 30.;
+
+    fn catch_app(capacity: usize) -> App {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .gameplay
+            .catch_capacity = capacity;
+        app.init_resource::<RunStats>()
+            .init_resource::<PaddleContacts>()
+            .insert_resource(CurrentState(GameState::Playing))
+            .add_event::<CollisionEvent>()
+            .add_systems(
+                Update,
+                (
+                    catch_balls,
+                    carry_held_balls,
+                    launch_held_ball,
+                    show_launch_prompt,
+                )
+                    .chain(),
+            );
+        app.world.spawn((paddle_sprite(Color::WHITE), Paddle));
+        app
+    }
+
+    // A ball sinking into the top of the paddle, offset along it from its center
+    fn drop_ball(app: &mut App, offset: f32) -> Entity {
+        let mut paddle_q = app.world.query_filtered::<&Transform, With<Paddle>>();
+        let paddle = paddle_q.single(&app.world).translation;
+        let y = paddle.y + (PADDLE_SIZE.y + TEST_BALL_SIZE) / 2. - 5.;
+        app.world
+            .spawn((
+                Ball,
+                Velocity(Vec2::new(0.3, -0.9)),
+                Transform::from_xyz(paddle.x + offset, y, 0.)
+                    .with_scale(Vec3::splat(TEST_BALL_SIZE)),
+            ))
+            .id()
+    }
+
+    fn press_launch(app: &mut App) {
+        let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+        keys.reset_all();
+        keys.press(KeyCode::W);
+        app.update();
+    }
+
+    fn is_held(app: &App, ball: Entity) -> bool {
+        app.world.get::<Held>(ball).is_some()
+    }
+
+    #[test]
+    fn held_balls_are_launched_one_per_press_nearest_the_center_first() {
+        let mut app = catch_app(2);
+        let outer = drop_ball(&mut app, 40.);
+        let inner = drop_ball(&mut app, -10.);
+        app.update();
+        assert!(is_held(&app, outer) && is_held(&app, inner));
+
+        press_launch(&mut app);
+        assert!(!is_held(&app, inner));
+        assert!(app.world.get::<Velocity>(inner).unwrap().y > 0.);
+        assert!(is_held(&app, outer));
+
+        press_launch(&mut app);
+        assert!(!is_held(&app, outer));
+        assert!(app.world.get::<Velocity>(outer).unwrap().y > 0.);
+    }
+
+    #[test]
+    fn balls_past_the_capacity_are_not_caught() {
+        let mut app = catch_app(1);
+        let first = drop_ball(&mut app, 0.);
+        app.update();
+        let second = drop_ball(&mut app, 30.);
+        app.update();
+        assert!(is_held(&app, first));
+        assert!(!is_held(&app, second));
+    }
+
+    fn launch_prompt(app: &mut App) -> Option<String> {
+        let mut prompt_q = app.world.query_filtered::<&Text, With<LaunchPrompt>>();
+        let prompt = prompt_q.get_single(&app.world).ok()?;
+        Some(prompt.sections[0].value.clone())
+    }
+
+    #[test]
+    fn the_launch_key_is_named_while_a_ball_is_held() {
+        let mut app = catch_app(1);
+        app.update();
+        assert_eq!(launch_prompt(&mut app), None);
+
+        // The prompt shows the frame after the catch, once the ball is held
+        drop_ball(&mut app, 0.);
+        app.update();
+        app.update();
+        assert_eq!(launch_prompt(&mut app).unwrap(), "Press W to launch");
+
+        press_launch(&mut app);
+        app.update();
+        assert_eq!(launch_prompt(&mut app), None);
+    }
+}
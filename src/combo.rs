@@ -0,0 +1,296 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, BrickDestroyed, CollisionEvent, CollisionKind, GameStateTransition},
+    hud::HudElement,
+    scoreboard::ScoreDisplay,
+    settings::GameSettings,
+    stats::RunStats,
+};
+
+const DEFAULT_COMBO_WINDOW: f32 = 2.;
+// The meter turns the expiring color once this share of the window is left
+const DEFAULT_COMBO_WARNING_FRACTION: f32 = 0.25;
+const DEFAULT_COMBO_FULL_COLOR: Color = Color::rgb(1., 0.85, 0.35);
+const DEFAULT_COMBO_EXPIRING_COLOR: Color = Color::rgb(1., 0.25, 0.25);
+const COMBO_METER_SIZE: Vec2 = Vec2::new(200., 12.);
+const COMBO_METER_BACKGROUND: Color = Color::rgba(0., 0., 0., 0.4);
+
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>().add_systems(
+            Update,
+            (
+                end_combo,
+                update_multiplier_display.after(end_combo),
+                (
+                    update_combo_window,
+                    update_combo_meter.after(update_combo_window),
+                )
+                    .run_if(game_running),
+            )
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComboSettings {
+    // A combo lapses when its window passes without a break, shown by a draining bar in the HUD
+    pub meter: bool,
+    pub window_secs: f32,
+    pub warning_fraction: f32,
+    pub full_color: Color,
+    pub expiring_color: Color,
+}
+
+impl Default for ComboSettings {
+    fn default() -> Self {
+        ComboSettings {
+            meter: false,
+            window_secs: DEFAULT_COMBO_WINDOW,
+            warning_fraction: DEFAULT_COMBO_WARNING_FRACTION,
+            full_color: DEFAULT_COMBO_FULL_COLOR,
+            expiring_color: DEFAULT_COMBO_EXPIRING_COLOR,
+        }
+    }
+}
+
+impl ComboSettings {
+    // The meter's color with this share of the window left
+    pub fn meter_color(&self, remaining: f32) -> Color {
+        if remaining <= self.warning_fraction {
+            self.expiring_color
+        } else {
+            self.full_color
+        }
+    }
+}
+
+// How long is left to keep the current combo going, refilled by every break
+#[derive(Resource)]
+pub struct Combo {
+    window: Timer,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        // Starts empty, there is no combo to keep until the first break
+        let mut window = Timer::from_seconds(DEFAULT_COMBO_WINDOW, TimerMode::Once);
+        window.tick(window.duration());
+        Combo { window }
+    }
+}
+
+impl Combo {
+    pub fn refill(&mut self, settings: &ComboSettings) {
+        self.window = Timer::from_seconds(settings.window_secs.max(0.), TimerMode::Once);
+    }
+
+    // The share of the window left, from 1 just after a break down to 0 once it lapses
+    pub fn remaining(&self) -> f32 {
+        if self.window.finished() {
+            0.
+        } else {
+            self.window.percent_left()
+        }
+    }
+}
+
+// Marker for the part of the meter that drains
+#[derive(Component)]
+pub struct ComboMeterFill;
+
+pub fn combo_meter(top_left_placement: Vec2) -> impl Bundle {
+    (
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(top_left_placement.y),
+                left: Val::Px(top_left_placement.x),
+                width: Val::Px(COMBO_METER_SIZE.x),
+                height: Val::Px(COMBO_METER_SIZE.y),
+                ..default()
+            },
+            background_color: COMBO_METER_BACKGROUND.into(),
+            ..default()
+        },
+        HudElement::Combo,
+        Name::new("ComboMeter"),
+    )
+}
+
+pub fn combo_meter_fill() -> impl Bundle {
+    (
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(0.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            ..default()
+        },
+        ComboMeterFill,
+    )
+}
+
+// Refills the window on every break, and ends the combo the moment it runs out
+pub fn update_combo_window(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut combo: ResMut<Combo>,
+    mut stats: ResMut<RunStats>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if !settings.combo.meter {
+        destroyed_events.clear();
+        return;
+    }
+    if destroyed_events.iter().count() > 0 {
+        combo.refill(&settings.combo);
+    } else if combo.window.tick(time.delta()).just_finished() {
+        stats.current_combo = 0;
+    }
+}
+
+// Drains the meter over the window, it is empty whenever there is no combo to keep, like after
+// the ball touches the paddle
+pub fn update_combo_meter(
+    combo: Res<Combo>,
+    mut fill_q: Query<(&mut Style, &mut BackgroundColor), With<ComboMeterFill>>,
+    stats: Res<RunStats>,
+    settings: Res<GameSettings>,
+) {
+    let remaining = if stats.current_combo == 0 {
+        0.
+    } else {
+        combo.remaining()
+    };
+    for (mut style, mut color) in fill_q.iter_mut() {
+        style.width = Val::Percent(remaining * 100.);
+        *color = settings.combo.meter_color(remaining).into();
+    }
+}
+
+// Losing a ball or leaving the level ends the combo, just like the ball touching the paddle
+pub fn end_combo(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut stats: ResMut<RunStats>,
+) {
+    let drained = collision_events
+        .iter()
+        .any(|collision| matches!(collision.kind, CollisionKind::Drain));
+    let left_level = game_msgs.iter().any(|msg| {
+        matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::ToBonusRound
+        )
+    });
+    if (drained || left_level) && stats.current_combo > 0 {
+        stats.current_combo = 0;
+    }
+}
+
+// Shows the multiplier after the score while a combo is going
+pub fn update_multiplier_display(
+    stats: Res<RunStats>,
+    mut text_q: Query<&mut Text, With<ScoreDisplay>>,
+) {
+    if !stats.is_changed() {
+        return;
+    }
+    for mut text in text_q.iter_mut() {
+        text.sections[2].value = match stats.combo_multiplier() {
+            1 => String::new(),
+            multiplier => format!(" x{multiplier}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bricks::BrickKind,
+        misc::test_utils::{advance_time, test_app},
+    };
+
+    fn combo_app() -> App {
+        let mut app = test_app();
+        app.world.resource_mut::<GameSettings>().combo.meter = true;
+        app.init_resource::<Combo>()
+            .init_resource::<RunStats>()
+            .add_event::<BrickDestroyed>()
+            .add_systems(Update, (update_combo_window, update_combo_meter).chain());
+        app.world
+            .spawn((Style::default(), BackgroundColor::default(), ComboMeterFill));
+        advance_time(&mut app, 0.);
+        app.update();
+        app
+    }
+
+    // Breaks a brick, building the combo like the breaker does
+    fn break_brick(app: &mut App) {
+        app.world.resource_mut::<RunStats>().current_combo += 1;
+        app.world.send_event(BrickDestroyed {
+            position: Vec3::ZERO,
+            kind: BrickKind::default(),
+            ball: Entity::PLACEHOLDER,
+            color: Color::WHITE,
+        });
+        advance_time(app, 0.);
+        app.update();
+    }
+
+    fn meter_width(app: &mut App) -> Val {
+        let mut fill_q = app.world.query_filtered::<&Style, With<ComboMeterFill>>();
+        fill_q.single(&app.world).width
+    }
+
+    #[test]
+    fn a_break_refills_the_meter() {
+        let mut app = combo_app();
+        break_brick(&mut app);
+        advance_time(&mut app, DEFAULT_COMBO_WINDOW / 2.);
+        app.update();
+        assert_eq!(meter_width(&mut app), Val::Percent(50.));
+
+        break_brick(&mut app);
+        assert_eq!(meter_width(&mut app), Val::Percent(100.));
+    }
+
+    #[test]
+    fn the_combo_ends_exactly_when_the_meter_empties() {
+        let mut app = combo_app();
+        break_brick(&mut app);
+        let frame = DEFAULT_COMBO_WINDOW / 16.;
+        for _ in 0..32 {
+            advance_time(&mut app, frame);
+            app.update();
+            let combo = app.world.resource::<RunStats>().current_combo;
+            assert_eq!(combo == 0, meter_width(&mut app) == Val::Percent(0.));
+        }
+        assert_eq!(app.world.resource::<RunStats>().current_combo, 0);
+    }
+
+    #[test]
+    fn the_meter_turns_the_expiring_color_past_the_warning() {
+        let mut combo = ComboSettings {
+            warning_fraction: 0.5,
+            ..default()
+        };
+        assert_eq!(combo.meter_color(0.6), combo.full_color);
+        assert_eq!(combo.meter_color(0.5), combo.expiring_color);
+
+        combo.warning_fraction = 0.;
+        assert_eq!(combo.meter_color(0.1), combo.full_color);
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    breaker::{CurrentState, GameState, GameStateTransition},
+    misc::live_text::set_or_spawn_text,
+};
+
+const COUNTDOWN_SECS: f32 = 3.;
+const COUNTDOWN_FONT_SIZE: f32 = 120.;
+const COUNTDOWN_COLOR: Color = Color::rgb(1., 1., 1.);
+// The number's top edge, as a percentage of the window height. Below the level banner
+const COUNTDOWN_TOP_PERCENT: f32 = 55.;
+
+// Counts "3, 2, 1" with the physics frozen before a run starts, a level starts, or play resumes
+// from pause. Asking to play again during the countdown skips the rest of it
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Countdown>().add_systems(
+            Update,
+            run_countdown.run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct Countdown(Timer);
+
+impl Default for Countdown {
+    fn default() -> Self {
+        Countdown(Timer::from_seconds(COUNTDOWN_SECS, TimerMode::Once))
+    }
+}
+
+// Marker for the number shown during the countdown
+#[derive(Component)]
+pub struct CountdownText;
+
+// Restarts the countdown every time GameState::Countdown is entered, and starts play once it runs
+// out. The number is only shown while counting down
+pub fn run_countdown(
+    mut commands: Commands,
+    game_state: Res<CurrentState>,
+    mut countdown: ResMut<Countdown>,
+    mut game_msgs: EventWriter<GameStateTransition>,
+    mut text_q: Query<(Entity, &mut Text), With<CountdownText>>,
+    time: Res<Time>,
+) {
+    if **game_state != GameState::Countdown {
+        for (ent, _) in text_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+        return;
+    }
+    if game_state.is_changed() {
+        countdown.reset();
+    }
+    if countdown.tick(time.delta()).just_finished() {
+        game_msgs.send(GameStateTransition::ToPlayGame);
+        return;
+    }
+
+    let shown = countdown.remaining_secs().ceil().max(1.).to_string();
+    let text = text_q.get_single_mut().ok().map(|(_, text)| text);
+    set_or_spawn_text(&mut commands, text, shown, |shown| {
+        (
+            TextBundle::from_section(
+                shown,
+                TextStyle {
+                    font_size: COUNTDOWN_FONT_SIZE,
+                    color: COUNTDOWN_COLOR,
+                    ..default()
+                },
+            )
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(COUNTDOWN_TOP_PERCENT),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..default()
+            }),
+            CountdownText,
+            Name::new("Countdown"),
+        )
+    });
+}
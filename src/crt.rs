@@ -0,0 +1,318 @@
+use bevy::{
+    core_pipeline::{core_2d, fullscreen_vertex_shader::fullscreen_shader_vertex_state},
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_graph::{
+            NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner,
+        },
+        render_resource::*,
+        renderer::{RenderContext, RenderDevice},
+        texture::BevyDefault,
+        view::ViewTarget,
+        RenderApp,
+    },
+    ui::draw_ui_graph,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::GameSettings;
+
+const CRT_SHADER_PATH: &str = "shaders/crt.wgsl";
+const DEFAULT_SCANLINE_INTENSITY: f32 = 0.3;
+const DEFAULT_CURVATURE: f32 = 0.15;
+const DEFAULT_VIGNETTE: f32 = 0.35;
+// The settings slider stops here, past it the corners bend out of the window
+pub const MAX_CRT_CURVATURE: f32 = 0.5;
+
+// An optional fullscreen CRT look, scanlines over a slightly bulging screen with darkened corners
+pub struct CrtPlugin;
+
+impl Plugin for CrtPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<CrtEffect>::default(),
+            UniformComponentPlugin::<CrtUniform>::default(),
+        ))
+        .add_systems(Update, sync_crt_effect);
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        // The pass runs in one of two places, before the UI so the HUD stays crisp or after it so
+        // the HUD is processed with the rest of the screen. Only the one picked in settings draws
+        render_app
+            .add_render_graph_node::<ViewNodeRunner<CrtNode<false>>>(
+                core_2d::graph::NAME,
+                CrtNode::<false>::NAME,
+            )
+            .add_render_graph_edges(
+                core_2d::graph::NAME,
+                &[
+                    core_2d::graph::node::TONEMAPPING,
+                    CrtNode::<false>::NAME,
+                    core_2d::graph::node::END_MAIN_PASS_POST_PROCESSING,
+                ],
+            )
+            .add_render_graph_node::<ViewNodeRunner<CrtNode<true>>>(
+                core_2d::graph::NAME,
+                CrtNode::<true>::NAME,
+            )
+            .add_render_graph_edges(
+                core_2d::graph::NAME,
+                &[
+                    draw_ui_graph::node::UI_PASS,
+                    CrtNode::<true>::NAME,
+                    core_2d::graph::node::UPSCALING,
+                ],
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<CrtPipeline>();
+    }
+}
+
+// Tuning for the CRT pass, found under visual settings. It is off by default
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrtSettings {
+    pub enabled: bool,
+    // How dark the gaps between scanlines get, from 0 to 1
+    pub scanline_intensity: f32,
+    // How far the screen bulges out towards the corners, 0 keeps it flat
+    pub curvature: f32,
+    // How much the corners darken, from 0 to 1
+    pub vignette: f32,
+    // Also runs the pass over the HUD instead of leaving it crisp
+    pub include_ui: bool,
+}
+
+impl Default for CrtSettings {
+    fn default() -> Self {
+        CrtSettings {
+            enabled: false,
+            scanline_intensity: DEFAULT_SCANLINE_INTENSITY,
+            curvature: DEFAULT_CURVATURE,
+            vignette: DEFAULT_VIGNETTE,
+            include_ui: false,
+        }
+    }
+}
+
+// Carries the CRT settings on each camera into the render world
+#[derive(Component, Clone)]
+pub struct CrtEffect(CrtSettings);
+
+// The shader's view of the settings
+#[derive(Component, Clone, Copy, ShaderType)]
+pub struct CrtUniform {
+    scanline_intensity: f32,
+    curvature: f32,
+    vignette: f32,
+}
+
+// Which of the two passes draws for a camera
+#[derive(Component, Clone, Copy)]
+pub struct CrtPlacement {
+    over_ui: bool,
+}
+
+impl ExtractComponent for CrtEffect {
+    type Query = &'static CrtEffect;
+    type Filter = ();
+    type Out = (CrtUniform, CrtPlacement);
+
+    // Nothing is extracted while the effect is off, so neither pass costs anything
+    fn extract_component(effect: QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        let settings = &effect.0;
+        if !settings.enabled {
+            return None;
+        }
+        Some((
+            CrtUniform {
+                scanline_intensity: settings.scanline_intensity.clamp(0., 1.),
+                curvature: settings.curvature.max(0.),
+                vignette: settings.vignette.clamp(0., 1.),
+            },
+            CrtPlacement {
+                over_ui: settings.include_ui,
+            },
+        ))
+    }
+}
+
+// Keeps every 2D camera's effect in line with the settings, cameras are respawned with each run
+pub fn sync_crt_effect(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    camera_q: Query<(Entity, Option<&CrtEffect>), With<Camera2d>>,
+) {
+    for (camera, effect) in camera_q.iter() {
+        if effect.is_none() || settings.is_changed() {
+            commands
+                .entity(camera)
+                .insert(CrtEffect(settings.visual.crt.clone()));
+        }
+    }
+}
+
+#[derive(Default)]
+struct CrtNode<const OVER_UI: bool>;
+
+impl<const OVER_UI: bool> CrtNode<OVER_UI> {
+    const NAME: &'static str = if OVER_UI { "crt_over_ui" } else { "crt" };
+}
+
+impl<const OVER_UI: bool> ViewNode for CrtNode<OVER_UI> {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static CrtPlacement,
+        &'static DynamicUniformIndex<CrtUniform>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, placement, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if placement.over_ui != OVER_UI {
+            return Ok(());
+        }
+        let crt_pipeline = world.resource::<CrtPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        // The shader may still be loading
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(crt_pipeline.pipeline_id) else {
+            return Ok(());
+        };
+        let uniforms = world.resource::<ComponentUniforms<CrtUniform>>();
+        let Some(uniforms_binding) = uniforms.uniforms().binding() else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+        let bind_group = render_context
+            .render_device()
+            .create_bind_group(&BindGroupDescriptor {
+                label: Some("crt_bind_group"),
+                layout: &crt_pipeline.layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(post_process.source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&crt_pipeline.sampler),
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: uniforms_binding,
+                    },
+                ],
+            });
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("crt_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1); // One triangle covering the screen
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct CrtPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for CrtPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("crt_bind_group_layout"),
+            entries: &[
+                // The rendered screen
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: Some(CrtUniform::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        let shader = world.resource::<AssetServer>().load(CRT_SHADER_PATH);
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("crt_pipeline".into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader,
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: TextureFormat::bevy_default(),
+                            blend: None,
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: vec![],
+                });
+
+        CrtPipeline {
+            layout,
+            sampler,
+            pipeline_id,
+        }
+    }
+}
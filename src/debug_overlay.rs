@@ -0,0 +1,103 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    ecs::entity::Entities,
+    prelude::*,
+};
+
+use crate::{
+    breaker::{BrickTracker, CurrentState, Level},
+    misc::live_text::set_or_spawn_text,
+};
+
+pub const DEBUG_OVERLAY_TOGGLE_KEY: KeyCode = KeyCode::F3;
+const DEBUG_OVERLAY_FONT_SIZE: f32 = 16.;
+const DEBUG_OVERLAY_COLOR: Color = Color::rgb(0.1, 0.8, 0.1);
+// Kept in the bottom left corner, out of the way of the HUD
+const DEBUG_OVERLAY_MARGIN: f32 = 5.;
+
+// A few lines of numbers for chasing down performance and collision problems, toggled with F3
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(FrameTimeDiagnosticsPlugin)
+            .init_resource::<DebugOverlay>()
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    update_debug_overlay.after(toggle_debug_overlay),
+                ),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+// Marker for the overlay's text
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+pub fn toggle_debug_overlay(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keys.just_pressed(DEBUG_OVERLAY_TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+        info!("Debug overlay visible: {}", overlay.visible);
+    }
+}
+
+// Shows the FPS, the number of live entities and the game's progress
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_overlay(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    mut text_q: Query<(Entity, &mut Text), With<DebugOverlayText>>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    game_state: Res<CurrentState>,
+    level: Res<Level>,
+    brick_tracker: Res<BrickTracker>,
+) {
+    if !overlay.visible {
+        for (ent, _) in text_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+        return;
+    }
+
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let shown = format!(
+        "FPS: {:.0}\nEntities: {}\nState: {:?}\nLevel: {}\nBricks: {}",
+        fps,
+        entities.len(),
+        **game_state,
+        **level,
+        **brick_tracker,
+    );
+    let text = text_q.get_single_mut().ok().map(|(_, text)| text);
+    set_or_spawn_text(&mut commands, text, shown, |shown| {
+        (
+            TextBundle::from_section(
+                shown,
+                TextStyle {
+                    font_size: DEBUG_OVERLAY_FONT_SIZE,
+                    color: DEBUG_OVERLAY_COLOR,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(DEBUG_OVERLAY_MARGIN),
+                left: Val::Px(DEBUG_OVERLAY_MARGIN),
+                ..default()
+            }),
+            DebugOverlayText,
+            Name::new("DebugOverlay"),
+        )
+    });
+}
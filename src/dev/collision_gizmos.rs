@@ -0,0 +1,132 @@
+use bevy::{prelude::*, sprite::collide_aabb::Collision};
+
+use crate::breaker::{Ball, Collider, CollisionEvent};
+
+const COLLISION_GIZMOS_TOGGLE_KEY: KeyCode = KeyCode::F4;
+const COLLIDER_COLOR: Color = Color::LIME_GREEN;
+const BALL_BOX_COLOR: Color = Color::YELLOW;
+const CONTACT_COLOR: Color = Color::RED;
+const CONTACT_MARKER_RADIUS: f32 = 4.;
+const CONTACT_NORMAL_LENGTH: f32 = 20.;
+// Contacts only last a tick, so markers linger long enough to actually be seen
+const CONTACT_MARKER_LIFETIME: f32 = 0.5;
+const CONTACT_LABEL_SIZE: f32 = 14.;
+
+// Whether collider outlines and contact markers are drawn, toggled with F4
+#[derive(Resource, Default)]
+pub struct CollisionGizmos {
+    pub visible: bool,
+}
+
+// A recent contact, drawn until its timer finishes
+#[derive(Component)]
+pub struct ContactMarker {
+    point: Vec2,
+    side: Option<Collision>,
+    timer: Timer,
+}
+
+pub fn toggle_collision_gizmos(keys: Res<Input<KeyCode>>, mut gizmos: ResMut<CollisionGizmos>) {
+    if keys.just_pressed(COLLISION_GIZMOS_TOGGLE_KEY) {
+        gizmos.visible = !gizmos.visible;
+        info!("Collision gizmos visible: {}", gizmos.visible);
+    }
+}
+
+// The direction the collision side faces, used to draw a short normal at the contact
+fn side_direction(side: Collision) -> Vec2 {
+    match side {
+        Collision::Left => Vec2::NEG_X,
+        Collision::Right => Vec2::X,
+        Collision::Top => Vec2::Y,
+        Collision::Bottom => Vec2::NEG_Y,
+        Collision::Inside => Vec2::ZERO,
+    }
+}
+
+// Spawns a labeled marker for every contact this frame. Only reads events, so gameplay is unaffected
+pub fn record_contacts(
+    mut commands: Commands,
+    gizmos: Res<CollisionGizmos>,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    if !gizmos.visible {
+        collision_events.clear();
+        return;
+    }
+    for collision in collision_events.iter() {
+        let label = match collision.side {
+            Some(side) => format!("{side:?}"),
+            None => "Round".to_string(),
+        };
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    label,
+                    TextStyle {
+                        font_size: CONTACT_LABEL_SIZE,
+                        color: CONTACT_COLOR,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(
+                    (collision.contact + Vec2::new(0., CONTACT_LABEL_SIZE)).extend(10.),
+                ),
+                ..default()
+            },
+            ContactMarker {
+                point: collision.contact,
+                side: collision.side,
+                timer: Timer::from_seconds(CONTACT_MARKER_LIFETIME, TimerMode::Once),
+            },
+            Name::new("ContactMarker"),
+        ));
+    }
+}
+
+pub fn draw_collision_gizmos(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    settings: Res<CollisionGizmos>,
+    collider_q: Query<&Transform, With<Collider>>,
+    ball_q: Query<&Transform, With<Ball>>,
+    mut marker_q: Query<(Entity, &mut ContactMarker)>,
+    time: Res<Time>,
+) {
+    for (ent, mut marker) in marker_q.iter_mut() {
+        marker.timer.tick(time.delta());
+        if marker.timer.finished() || !settings.visible {
+            commands.entity(ent).despawn();
+            continue;
+        }
+        gizmos.circle_2d(marker.point, CONTACT_MARKER_RADIUS, CONTACT_COLOR);
+        if let Some(side) = marker.side {
+            gizmos.ray_2d(
+                marker.point,
+                side_direction(side) * CONTACT_NORMAL_LENGTH,
+                CONTACT_COLOR,
+            );
+        }
+    }
+    if !settings.visible {
+        return;
+    }
+
+    // These are exactly the positions and sizes passed to collide()
+    for tform in collider_q.iter() {
+        gizmos.rect_2d(
+            tform.translation.truncate(),
+            0.,
+            tform.scale.truncate(),
+            COLLIDER_COLOR,
+        );
+    }
+    for tform in ball_q.iter() {
+        gizmos.rect_2d(
+            tform.translation.truncate(),
+            0.,
+            tform.scale.truncate(),
+            BALL_BOX_COLOR,
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+const FREEZE_TOGGLE_KEY: KeyCode = KeyCode::F2;
+
+// Whether the ball and paddle are held in place for the inspector, toggled with F2. Unlike the
+// player's pause nothing is shown, and velocities are left alone so unfreezing picks up exactly
+// where it stopped
+#[derive(Resource, Default)]
+pub struct DevFreeze {
+    pub frozen: bool,
+}
+
+pub fn toggle_freeze(keys: Res<Input<KeyCode>>, mut freeze: ResMut<DevFreeze>) {
+    if keys.just_pressed(FREEZE_TOGGLE_KEY) {
+        freeze.frozen = !freeze.frozen;
+        info!("Physics frozen: {}", freeze.frozen);
+    }
+}
+
+// Run condition for the physics set, the entities stay spawned and queryable while frozen
+pub fn not_frozen(freeze: Res<DevFreeze>) -> bool {
+    !freeze.frozen
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::{
+    bricks::Brick,
+    walls::{BOTTOM_WALL, LEFT_WALL, RIGHT_WALL, TOP_WALL},
+};
+
+const HEATMAP_TOGGLE_KEY: KeyCode = KeyCode::F1;
+const HEATMAP_COLS: usize = 12;
+const HEATMAP_ROWS: usize = 8;
+// A cell holding this much total brick strength gets the hottest tint
+const HEATMAP_MAX_STRENGTH: f32 = 6.;
+const HEATMAP_MAX_ALPHA: f32 = 0.45;
+// Just behind everything else, so bricks, paddle, and ball all draw over the overlay
+const HEATMAP_Z: f32 = -0.05;
+
+// Whether the brick density overlay is shown, toggled with F1
+#[derive(Resource, Default)]
+pub struct HeatmapOverlay {
+    pub visible: bool,
+}
+
+// One region of the arena in the overlay grid
+#[derive(Component)]
+pub struct HeatmapCell {
+    col: usize,
+    row: usize,
+}
+
+pub fn toggle_heatmap(keys: Res<Input<KeyCode>>, mut overlay: ResMut<HeatmapOverlay>) {
+    if keys.just_pressed(HEATMAP_TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+        info!("Brick heatmap visible: {}", overlay.visible);
+    }
+}
+
+fn cell_size() -> Vec2 {
+    Vec2::new(
+        (RIGHT_WALL - LEFT_WALL) / HEATMAP_COLS as f32,
+        (TOP_WALL - BOTTOM_WALL) / HEATMAP_ROWS as f32,
+    )
+}
+
+// The grid cell containing a point, or None if it is outside the arena
+fn cell_at(position: Vec2) -> Option<(usize, usize)> {
+    let size = cell_size();
+    let col = ((position.x - LEFT_WALL) / size.x).floor();
+    let row = ((TOP_WALL - position.y) / size.y).floor();
+    if col < 0. || row < 0. || col >= HEATMAP_COLS as f32 || row >= HEATMAP_ROWS as f32 {
+        return None;
+    }
+    Some((col as usize, row as usize))
+}
+
+// Keeps the overlay quads in sync with the live bricks, summing the remaining strength per cell.
+// Runs every frame while visible so both authored and generated layouts are reflected as they change
+pub fn update_heatmap(
+    mut commands: Commands,
+    overlay: Res<HeatmapOverlay>,
+    brick_q: Query<(&Brick, &Transform)>,
+    mut cell_q: Query<(Entity, &HeatmapCell, &mut Sprite)>,
+) {
+    if !overlay.visible {
+        for (ent, _, _) in cell_q.iter() {
+            commands.entity(ent).despawn();
+        }
+        return;
+    }
+
+    // Cells are lost whenever the game despawns everything, so respawn them on demand
+    if cell_q.is_empty() {
+        spawn_heatmap_cells(&mut commands);
+        return;
+    }
+
+    let mut strength = [[0.; HEATMAP_COLS]; HEATMAP_ROWS];
+    for (brick, tform) in brick_q.iter() {
+        if let Some((col, row)) = cell_at(tform.translation.truncate()) {
+            strength[row][col] += **brick as f32;
+        }
+    }
+
+    for (_, cell, mut sprite) in cell_q.iter_mut() {
+        let heat = (strength[cell.row][cell.col] / HEATMAP_MAX_STRENGTH).min(1.);
+        // Cool blue for sparse regions, shifting to red as strength piles up
+        sprite.color = Color::rgba(heat, 0.2, 1. - heat, heat * HEATMAP_MAX_ALPHA);
+    }
+}
+
+fn spawn_heatmap_cells(commands: &mut Commands) {
+    let size = cell_size();
+    for row in 0..HEATMAP_ROWS {
+        for col in 0..HEATMAP_COLS {
+            let center = Vec2::new(
+                LEFT_WALL + (col as f32 + 0.5) * size.x,
+                TOP_WALL - (row as f32 + 0.5) * size.y,
+            );
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform {
+                        translation: center.extend(HEATMAP_Z),
+                        scale: size.extend(1.),
+                        ..default()
+                    },
+                    sprite: Sprite {
+                        color: Color::NONE,
+                        ..default()
+                    },
+                    ..default()
+                },
+                HeatmapCell { col, row },
+                Name::new(format!("HeatmapCell{col}x{row}")),
+            ));
+        }
+    }
+}
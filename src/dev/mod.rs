@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::{app_state::AppState, breaker::PhysicsSet};
+
+pub mod collision_gizmos;
+pub mod freeze;
+pub mod heatmap;
+pub mod sandbox;
+
+// Tools for level design and debugging, only compiled with the "dev" feature
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<heatmap::HeatmapOverlay>()
+            .init_resource::<collision_gizmos::CollisionGizmos>()
+            .init_resource::<freeze::DevFreeze>()
+            .init_resource::<sandbox::BrickSandbox>()
+            .configure_set(Update, PhysicsSet.run_if(freeze::not_frozen))
+            .configure_set(FixedUpdate, PhysicsSet.run_if(freeze::not_frozen))
+            .add_systems(
+                Update,
+                (
+                    heatmap::toggle_heatmap,
+                    heatmap::update_heatmap.after(heatmap::toggle_heatmap),
+                    collision_gizmos::toggle_collision_gizmos,
+                    collision_gizmos::record_contacts
+                        .after(collision_gizmos::toggle_collision_gizmos),
+                    collision_gizmos::draw_collision_gizmos
+                        .after(collision_gizmos::record_contacts),
+                    freeze::toggle_freeze,
+                    sandbox::toggle_sandbox,
+                    sandbox::place_sandbox_bricks
+                        .after(sandbox::toggle_sandbox)
+                        .run_if(state_exists_and_equals(AppState::InGame)),
+                ),
+            );
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    breaker::BrickTracker,
+    bricks::{
+        snap_to_brick_grid, spawn_brick, Brick, BrickDimensions, BrickKind, BrickShape, Destroying,
+    },
+    settings::GameSettings,
+};
+
+const SANDBOX_TOGGLE_KEY: KeyCode = KeyCode::F5;
+const SANDBOX_RESET_KEY: KeyCode = KeyCode::Back;
+const STRENGTH_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+// Live brick placement for feeling out ricochets, toggled with F5. While on, left click places a
+// brick of the selected strength (1-9) on the level's grid, right click removes the brick under
+// the cursor, and backspace removes every placed brick. Nothing is saved
+#[derive(Resource)]
+pub struct BrickSandbox {
+    pub enabled: bool,
+    pub strength: u8,
+}
+
+impl Default for BrickSandbox {
+    fn default() -> Self {
+        BrickSandbox {
+            enabled: false,
+            strength: 1,
+        }
+    }
+}
+
+// Marker for bricks placed in the sandbox, so they can be cleared without touching the level's
+#[derive(Component)]
+pub struct SandboxBrick;
+
+pub fn toggle_sandbox(keys: Res<Input<KeyCode>>, mut sandbox: ResMut<BrickSandbox>) {
+    if keys.just_pressed(SANDBOX_TOGGLE_KEY) {
+        sandbox.enabled = !sandbox.enabled;
+        info!("Brick sandbox enabled: {}", sandbox.enabled);
+    }
+    if !sandbox.enabled {
+        return;
+    }
+    if let Some(key) = STRENGTH_KEYS.iter().position(|key| keys.just_pressed(*key)) {
+        sandbox.strength = key as u8 + 1;
+        info!("Sandbox brick strength: {}", sandbox.strength);
+    }
+}
+
+// Placed bricks are ordinary bricks, so the ball and paddle treat them like any other and they
+// count towards clearing the level
+#[allow(clippy::too_many_arguments)]
+pub fn place_sandbox_bricks(
+    mut commands: Commands,
+    sandbox: Res<BrickSandbox>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+    brick_q: Query<
+        (Entity, &Transform, Option<&SandboxBrick>, &BrickKind),
+        (With<Brick>, Without<Destroying>),
+    >,
+    mut brick_tracker: ResMut<BrickTracker>,
+    brick_dims: Res<BrickDimensions>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    if !sandbox.enabled {
+        return;
+    }
+    if keys.just_pressed(SANDBOX_RESET_KEY) {
+        for (ent, _, placed, _) in brick_q.iter() {
+            if placed.is_some() {
+                commands.entity(ent).despawn_recursive();
+                **brick_tracker = brick_tracker.saturating_sub(1);
+            }
+        }
+        return;
+    }
+
+    let place = buttons.just_pressed(MouseButton::Left);
+    let remove = buttons.just_pressed(MouseButton::Right);
+    if !place && !remove {
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_tform))) = (window_q.get_single(), camera_q.get_single())
+    else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_tform, cursor))
+    else {
+        return;
+    };
+    let Some(cell) = snap_to_brick_grid(cursor, &brick_dims) else {
+        return;
+    };
+    // Any brick overlapping the cell's center occupies it
+    let occupant = brick_q.iter().find(|(_, tform, _, _)| {
+        (tform.translation.truncate() - cell)
+            .abs()
+            .cmplt(brick_dims.size / 2.)
+            .all()
+    });
+
+    match occupant {
+        Some((ent, _, _, kind)) if remove => {
+            commands.entity(ent).despawn_recursive();
+            if kind.needed_to_clear() {
+                **brick_tracker = brick_tracker.saturating_sub(1);
+            }
+        }
+        None if place => {
+            let brick = spawn_brick(
+                &mut commands,
+                sandbox.strength,
+                BrickKind::Normal,
+                BrickShape::Rectangle,
+                cell,
+                &brick_dims,
+                settings.visual.brick_palette,
+                &asset_server,
+                Name::new("SandboxBrick"),
+            );
+            commands.entity(brick).insert(SandboxBrick);
+            **brick_tracker += 1;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+const EASY_STARTING_HEALTH: usize = 5;
+const HARD_STARTING_HEALTH: usize = 1;
+const EASY_BALL_SPEED_FACTOR: f32 = 0.8;
+const HARD_BALL_SPEED_FACTOR: f32 = 1.2;
+const HARD_PADDLE_WIDTH_FACTOR: f32 = 0.75;
+
+// Chosen in the main menu, applied as each run starts. Normal leaves the game as designed
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn starting_health(&self, normal: usize) -> usize {
+        match self {
+            Difficulty::Easy => EASY_STARTING_HEALTH,
+            Difficulty::Normal => normal,
+            Difficulty::Hard => HARD_STARTING_HEALTH,
+        }
+    }
+
+    // Scales the ball's speed on every level
+    pub fn ball_speed_factor(&self) -> f32 {
+        match self {
+            Difficulty::Easy => EASY_BALL_SPEED_FACTOR,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => HARD_BALL_SPEED_FACTOR,
+        }
+    }
+
+    // Scales the paddle's full width
+    pub fn paddle_width_factor(&self) -> f32 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 1.,
+            Difficulty::Hard => HARD_PADDLE_WIDTH_FACTOR,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    ball_tints::{ball_color, BallTint},
+    breaker::{Ball, BallSpawner},
+    powerups::SLOW_BALL_FACTOR,
+    settings::GameSettings,
+    theme::ThemeColors,
+};
+
+const ELEMENT_DURATION: f32 = 6.0;
+// Ice slows the ball by the same amount as the SlowBall power-up
+pub const ICE_SPEED_FACTOR: f32 = SLOW_BALL_FACTOR;
+
+// Temporary ball modifiers granted by touching an elemental brick
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Element {
+    Fire,     // Piercing, the ball breaks bricks outright without bouncing
+    Ice,      // The ball moves slower
+    Electric, // The ball splits in two on the next brick it hits
+}
+
+impl Element {
+    pub fn color(&self) -> Color {
+        match self {
+            Element::Fire => Color::rgb(1., 0.35, 0.),
+            Element::Ice => Color::rgb(0.6, 0.9, 1.),
+            Element::Electric => Color::rgb(1., 1., 0.2),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Element::Fire => "F",
+            Element::Ice => "I",
+            Element::Electric => "E",
+        }
+    }
+}
+
+// The element a ball currently carries, a ball only ever has one and a new one replaces it.
+// Expires once the timer finishes, or early once a one-shot element triggers
+#[derive(Component)]
+pub struct BallElement {
+    pub element: Element,
+    timer: Timer,
+}
+
+impl BallElement {
+    pub fn new(element: Element) -> Self {
+        BallElement {
+            element,
+            timer: Timer::from_seconds(ELEMENT_DURATION, TimerMode::Once),
+        }
+    }
+
+    pub fn is(&self, element: Element) -> bool {
+        self.element == element && !self.timer.finished()
+    }
+
+    // Ends the element, it is removed from the ball on the next tick_ball_elements
+    pub fn expire(&mut self) {
+        let duration = self.timer.duration();
+        self.timer.tick(duration);
+    }
+}
+
+// Write a SplitBall to spawn an extra ball at the given position
+#[derive(Event, Clone, Debug)]
+pub struct SplitBall {
+    pub position: Vec3,
+    pub velocity: Vec2,
+}
+
+// Advances element timers, removing finished elements and restoring the ball's own color
+pub fn tick_ball_elements(
+    mut commands: Commands,
+    mut ball_q: Query<(
+        Entity,
+        &mut BallElement,
+        &Handle<ColorMaterial>,
+        Option<&BallTint>,
+    )>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    theme: Res<ThemeColors>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    for (ball, mut element, handle, tint) in ball_q.iter_mut() {
+        element.timer.tick(time.delta());
+        if element.timer.finished() {
+            commands.entity(ball).remove::<BallElement>();
+            if let Some(mat) = mats.get_mut(handle) {
+                mat.color = ball_color(tint, &settings, theme.ball);
+            }
+        }
+    }
+}
+
+pub fn split_balls(
+    mut commands: Commands,
+    mut balls: BallSpawner,
+    mut split_events: EventReader<SplitBall>,
+    ball_q: Query<(), With<Ball>>,
+    theme: Res<ThemeColors>,
+) {
+    let mut in_play = ball_q.iter().count();
+    for split in split_events.iter() {
+        let velocity = balls.config().split_velocity(split.velocity);
+        let spawned =
+            balls.spawn_ball(&mut commands, in_play, theme.ball, split.position, velocity);
+        if spawned.is_some() {
+            in_play += 1;
+        }
+    }
+}
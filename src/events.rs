@@ -0,0 +1,64 @@
+use bevy::{prelude::*, sprite::collide_aabb::Collision};
+
+use crate::bricks::BrickKind;
+
+// Registers the events the game's systems use to talk to each other
+pub struct GameEventsPlugin;
+
+impl Plugin for GameEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CollisionEvent>()
+            .add_event::<GameStateTransition>()
+            .add_event::<PlayerMessage>()
+            .add_event::<BrickDestroyed>();
+    }
+}
+
+// Events related to starting the game, creating levels, playing, pausing
+#[derive(Event, Clone, Debug)]
+pub enum GameStateTransition {
+    ToUninitialized,
+    ToPlayGame,
+    ToPreview,
+    ToHaltGame,
+    NextLevel,
+    ToBonusRound,
+    ToGameOver,
+    RestartLevel, // Respawns the current level's bricks and ball, keeping score and health
+    RestartGame,  // Starts over from the first level with a fresh score and health
+}
+
+// Events related to player health, death
+#[derive(Event, Clone)]
+pub enum PlayerMessage {
+    JustLostHealth,
+}
+
+// Events are added to an EventWriter, read multiple places by EventReaders
+// each system that reads events tracks its processed events independently
+#[derive(Event, Clone, Debug)]
+pub struct CollisionEvent {
+    // Approximate point where the ball touched the collider
+    pub contact: Vec2,
+    // The side reported by collide(), None for round colliders
+    pub side: Option<Collision>,
+    pub kind: CollisionKind,
+}
+
+// What the ball collided with
+#[derive(Clone, Debug)]
+pub enum CollisionKind {
+    Brick { position: Vec3, strength: u8 }, // Strength before the hit
+    Paddle { offset: f32 }, // Where the ball hit, from -1 at the left edge to 1 at the right
+    Wall,
+    Drain,
+}
+
+// Sent whenever a brick's strength reaches 0, lets brick kinds trigger their effects
+#[derive(Event, Clone, Debug)]
+pub struct BrickDestroyed {
+    pub position: Vec3,
+    pub kind: BrickKind,
+    pub ball: Entity, // The ball that broke it
+    pub color: Color, // The brick's color as it broke
+}
@@ -0,0 +1,159 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::key_bindings::Action;
+
+// The actions a gamepad button can be bound to on the controls screen
+const GAMEPAD_ACTIONS: [Action; 3] = [Action::Start, Action::Pause, Action::Launch];
+// The buttons the controls screen cycles through, the d-pad is left to movement
+const BINDABLE_BUTTONS: [GamepadButtonType; 6] = [
+    GamepadButtonType::South,
+    GamepadButtonType::East,
+    GamepadButtonType::West,
+    GamepadButtonType::North,
+    GamepadButtonType::Start,
+    GamepadButtonType::Select,
+];
+
+// The gamepad buttons bound to the actions a controller can trigger. Movement is always the left
+// stick and the d-pad
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadBindings {
+    pub start: Vec<GamepadButtonType>,
+    pub pause: Vec<GamepadButtonType>,
+    pub launch: Vec<GamepadButtonType>,
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        GamepadBindings {
+            start: vec![GamepadButtonType::Start],
+            pause: vec![GamepadButtonType::Select],
+            launch: vec![GamepadButtonType::South],
+        }
+    }
+}
+
+impl GamepadBindings {
+    // Actions without a gamepad binding return no buttons
+    pub fn buttons(&self, action: Action) -> &[GamepadButtonType] {
+        match action {
+            Action::Start => &self.start,
+            Action::Pause => &self.pause,
+            Action::Launch => &self.launch,
+            _ => &[],
+        }
+    }
+
+    // Binds the action to just this button, actions without a gamepad binding are left alone
+    pub fn set(&mut self, action: Action, button: GamepadButtonType) {
+        match action {
+            Action::Start => self.start = vec![button],
+            Action::Pause => self.pause = vec![button],
+            Action::Launch => self.launch = vec![button],
+            _ => {}
+        }
+    }
+
+    // The bindable button after the action's current one, skipping buttons bound to other actions.
+    // None for actions that can't be bound to a gamepad button
+    pub fn next_button(&self, action: Action) -> Option<GamepadButtonType> {
+        if !GAMEPAD_ACTIONS.contains(&action) {
+            return None;
+        }
+        let current = self
+            .buttons(action)
+            .first()
+            .and_then(|bound| BINDABLE_BUTTONS.iter().position(|button| button == bound));
+        let start = current.map_or(0, |i| i + 1);
+        (0..BINDABLE_BUTTONS.len())
+            .map(|i| BINDABLE_BUTTONS[(start + i) % BINDABLE_BUTTONS.len()])
+            .find(|button| {
+                GAMEPAD_ACTIONS
+                    .iter()
+                    .all(|other| *other == action || !self.buttons(*other).contains(button))
+            })
+    }
+}
+
+// Input from every connected gamepad, so any of them can drive the paddle. Gamepads are looked
+// up on each read, so one plugged in or removed mid run is picked up right away, and with none
+// connected every read is neutral
+#[derive(SystemParam)]
+pub struct GamepadInput<'w> {
+    gamepads: Res<'w, Gamepads>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+    buttons: Res<'w, Input<GamepadButton>>,
+}
+
+impl GamepadInput<'_> {
+    // The left stick's horizontal position in [-1, 1], taken from whichever gamepad is pushed the
+    // furthest. Bevy's default axis settings already apply a dead zone
+    pub fn stick_x(&self) -> f32 {
+        self.gamepads
+            .iter()
+            .filter_map(|gamepad| {
+                self.axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            })
+            .fold(0., |strongest: f32, x| {
+                if x.abs() > strongest.abs() {
+                    x
+                } else {
+                    strongest
+                }
+            })
+            .clamp(-1., 1.)
+    }
+
+    // Whether the d-pad is held left and right, on any gamepad
+    pub fn dpad(&self) -> (bool, bool) {
+        (
+            self.any_pressed(GamepadButtonType::DPadLeft),
+            self.any_pressed(GamepadButtonType::DPadRight),
+        )
+    }
+
+    pub fn just_pressed(&self, action: Action, bindings: &GamepadBindings) -> bool {
+        bindings.buttons(action).iter().any(|button_type| {
+            self.gamepads.iter().any(|gamepad| {
+                self.buttons
+                    .just_pressed(GamepadButton::new(gamepad, *button_type))
+            })
+        })
+    }
+
+    fn any_pressed(&self, button_type: GamepadButtonType) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            self.buttons
+                .pressed(GamepadButton::new(gamepad, button_type))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling_a_binding_skips_buttons_bound_to_other_actions() {
+        let mut bindings = GamepadBindings::default();
+        assert_eq!(
+            bindings.next_button(Action::Launch),
+            Some(GamepadButtonType::East)
+        );
+        // Start and Select come next, but Start and Pause already have them
+        bindings.set(Action::Launch, GamepadButtonType::North);
+        assert_eq!(
+            bindings.next_button(Action::Launch),
+            Some(GamepadButtonType::South)
+        );
+        // Select wraps around to South
+        assert_eq!(
+            bindings.next_button(Action::Pause),
+            Some(GamepadButtonType::South)
+        );
+        assert_eq!(bindings.next_button(Action::MoveLeft), None);
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, Ball},
+    powerups::{ApplyPowerUp, PowerUpKind},
+};
+
+const GHOST_BALL_DURATION: f32 = 5.;
+// Ghost balls are drawn see-through, whatever color their tint or element gives them
+const GHOST_BALL_ALPHA: f32 = 0.35;
+
+pub struct GhostBallPlugin;
+
+impl Plugin for GhostBallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                grant_ghost_balls,
+                tick_ghost_balls
+                    .after(grant_ghost_balls)
+                    .run_if(game_running),
+            )
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// While the timer runs the ball tunnels through bricks instead of bouncing off them. Walls and the
+// paddle still bounce it as usual
+#[derive(Component)]
+pub struct GhostBall {
+    timer: Timer,
+    // The bricks the ball is inside of, each one is only hit as the ball enters it
+    passing: Vec<Entity>,
+}
+
+impl Default for GhostBall {
+    fn default() -> Self {
+        GhostBall {
+            timer: Timer::from_seconds(GHOST_BALL_DURATION, TimerMode::Once),
+            passing: Vec::new(),
+        }
+    }
+}
+
+impl GhostBall {
+    pub fn is_passing(&self, brick: Entity) -> bool {
+        self.passing.contains(&brick)
+    }
+
+    // Replaces the bricks the ball is inside of with the ones it touched this tick
+    pub fn set_passing(&mut self, bricks: Vec<Entity>) {
+        self.passing = bricks;
+    }
+}
+
+// The ghost ball power-up turns every ball in play into a ghost, restarting the timer of any that
+// already were
+pub fn grant_ghost_balls(
+    mut commands: Commands,
+    mut power_ups: EventReader<ApplyPowerUp>,
+    ball_q: Query<Entity, With<Ball>>,
+) {
+    if !power_ups
+        .iter()
+        .any(|ApplyPowerUp(kind)| *kind == PowerUpKind::GhostBall)
+    {
+        return;
+    }
+    for ball in ball_q.iter() {
+        commands.entity(ball).insert(GhostBall::default());
+    }
+}
+
+// Keeps ghost balls see-through, and makes them solid again once their timer runs out
+pub fn tick_ghost_balls(
+    mut commands: Commands,
+    mut ball_q: Query<(Entity, &mut GhostBall, &Handle<ColorMaterial>)>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (ball, mut ghost, handle) in ball_q.iter_mut() {
+        let alpha = if ghost.timer.tick(time.delta()).finished() {
+            commands.entity(ball).remove::<GhostBall>();
+            1.
+        } else {
+            GHOST_BALL_ALPHA
+        };
+        // Only touched when it differs, picking up an element recolors the ball fully opaque
+        if mats.get(handle).is_some_and(|mat| mat.color.a() != alpha) {
+            if let Some(mat) = mats.get_mut(handle) {
+                mat.color.set_a(alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::GameStateTransition,
+    hud::{HudElement, HudLayout},
+    settings::GameSettings,
+};
+
+const GRACE_PERIOD_DURATION: f32 = 3.0;
+const GRACE_CUE_FONT_SIZE: f32 = 24.;
+const GRACE_CUE_COLOR: Color = Color::rgba(0.5, 1., 0.6, 0.6);
+
+// A short window at the start of each level and after each serve where losing the last ball
+// costs no health, it is still re-served. Only runs while the game is playing
+#[derive(Resource)]
+pub struct GracePeriod(Timer);
+
+impl Default for GracePeriod {
+    fn default() -> Self {
+        // Starts finished, so there is no grace until a level or serve starts one
+        let mut timer = Timer::from_seconds(GRACE_PERIOD_DURATION, TimerMode::Once);
+        timer.tick(timer.duration());
+        GracePeriod(timer)
+    }
+}
+
+impl GracePeriod {
+    pub fn active(&self) -> bool {
+        !self.0.finished()
+    }
+
+    // Starts a fresh grace period, if they are turned on
+    pub fn start(&mut self, settings: &GameSettings) {
+        if settings.gameplay.grace_period {
+            self.0.reset();
+        }
+    }
+}
+
+// Marker for the HUD text shown while the grace period runs
+#[derive(Component)]
+pub struct GraceCue;
+
+// The next level and a restarted level both start with a grace period, a new run starts one
+// when it is set up
+pub fn start_level_grace_period(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut grace: ResMut<GracePeriod>,
+    settings: Res<GameSettings>,
+) {
+    for msg in game_msgs.iter() {
+        if let GameStateTransition::NextLevel | GameStateTransition::RestartLevel = msg {
+            grace.start(&settings);
+        }
+    }
+}
+
+pub fn tick_grace_period(mut grace: ResMut<GracePeriod>, time: Res<Time>) {
+    grace.0.tick(time.delta());
+}
+
+// Shows the cue for as long as the grace period runs
+pub fn show_grace_cue(
+    mut commands: Commands,
+    grace: Res<GracePeriod>,
+    cue_q: Query<Entity, With<GraceCue>>,
+    hud_layout: Res<HudLayout>,
+) {
+    if grace.active() && cue_q.is_empty() {
+        commands.spawn((
+            TextBundle::from_section(
+                "SAFE",
+                TextStyle {
+                    font_size: GRACE_CUE_FONT_SIZE,
+                    color: GRACE_CUE_COLOR,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(hud_layout.grace.y),
+                left: Val::Px(hud_layout.grace.x),
+                ..default()
+            }),
+            HudElement::Grace,
+            GraceCue,
+            Name::new("GraceCue"),
+        ));
+    } else if !grace.active() {
+        for ent in cue_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::hud::HudElement;
+
+const HARDCORE_STARTING_HEALTH: usize = 1;
+// Catches this far out from the paddle's center, as a share of its half width, count as near misses
+const NEAR_MISS_OFFSET: f32 = 0.8;
+// Near miss speedups stop once a ball is this many times its base speed, so it can't tunnel
+const MAX_NEAR_MISS_SPEED: f32 = 2.;
+const HARDCORE_FLAG_FONT_SIZE: f32 = 30.;
+const HARDCORE_FLAG_COLOR: Color = Color::rgb(1., 0.2, 0.2);
+
+// One life runs, ranked apart from normal runs. Optionally every near miss catch also speeds the
+// ball up for good
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HardcoreConfig {
+    pub enabled: bool,
+    // How much a near miss catch multiplies the ball's speed by on top of 1, 0 turns it off
+    pub near_miss_speedup: f32,
+}
+
+impl HardcoreConfig {
+    pub fn starting_health(&self, normal: usize) -> usize {
+        if self.enabled {
+            HARDCORE_STARTING_HEALTH
+        } else {
+            normal
+        }
+    }
+
+    // Speeds up a ball that was just caught at the given paddle offset, if it was a near miss
+    pub fn apply_near_miss(&self, offset: f32, velocity: &mut Vec2) {
+        if !self.enabled || self.near_miss_speedup <= 0. || offset.abs() < NEAR_MISS_OFFSET {
+            return;
+        }
+        let speed = velocity.length();
+        let faster = (speed * (1. + self.near_miss_speedup)).min(MAX_NEAR_MISS_SPEED.max(speed));
+        *velocity = velocity.normalize_or_zero() * faster;
+    }
+}
+
+// Shown on the HUD for the whole of a hardcore run
+pub fn hardcore_flag(top_left_placement: Vec2) -> impl Bundle {
+    (
+        TextBundle::from_section(
+            "HARDCORE",
+            TextStyle {
+                font_size: HARDCORE_FLAG_FONT_SIZE,
+                color: HARDCORE_FLAG_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(top_left_placement.y),
+            left: Val::Px(top_left_placement.x),
+            ..default()
+        }),
+        HudElement::Hardcore,
+        Name::new("HardcoreFlag"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(near_miss_speedup: f32) -> HardcoreConfig {
+        HardcoreConfig {
+            enabled: true,
+            near_miss_speedup,
+        }
+    }
+
+    #[test]
+    fn near_miss_catches_speed_the_ball_up_to_the_cap() {
+        let hardcore = config(0.5);
+        let mut velocity = Vec2::new(0., 1.);
+        // Central catches aren't near misses
+        hardcore.apply_near_miss(0.5, &mut velocity);
+        assert_eq!(velocity, Vec2::new(0., 1.));
+
+        hardcore.apply_near_miss(-0.9, &mut velocity);
+        assert_eq!(velocity, Vec2::new(0., 1.5));
+        hardcore.apply_near_miss(0.9, &mut velocity);
+        assert_eq!(velocity, Vec2::new(0., MAX_NEAR_MISS_SPEED));
+    }
+
+    #[test]
+    fn near_misses_only_count_in_hardcore_runs() {
+        let mut velocity = Vec2::new(0., 1.);
+        config(0.).apply_near_miss(0.9, &mut velocity);
+        let normal = HardcoreConfig {
+            enabled: false,
+            ..config(0.5)
+        };
+        normal.apply_near_miss(0.9, &mut velocity);
+        assert_eq!(velocity, Vec2::new(0., 1.));
+    }
+}
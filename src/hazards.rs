@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{BrickDestroyed, GameStateTransition, Paddle, PADDLE_SIZE},
+    bricks::BrickKind,
+    net::RemotePaddle,
+    theme::ThemeColors,
+};
+
+const FREEZE_DURATION: f64 = 1.0;
+const FROZEN_PADDLE_COLOR: Color = Color::rgb(0.7, 0.9, 1.0);
+const REVERSE_DURATION: f32 = 4.0;
+const REVERSED_CUE_FONT_SIZE: f32 = 30.;
+const REVERSED_CUE_COLOR: Color = Color::rgb(1., 0.4, 1.);
+
+// While present on the paddle, player input is ignored until the timer finishes
+#[derive(Component)]
+pub struct Frozen(pub Timer);
+
+// Freezes the paddle(s) whenever a Freeze brick is destroyed, refreshing any existing freeze
+pub fn freeze_paddle_on_trap(
+    mut commands: Commands,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut paddle_q: Query<(Entity, &mut Sprite), With<Paddle>>,
+) {
+    let triggered = destroyed_events
+        .iter()
+        .any(|destroyed| destroyed.kind == BrickKind::Freeze);
+    if !triggered {
+        return;
+    }
+
+    for (paddle, mut sprite) in paddle_q.iter_mut() {
+        commands.entity(paddle).insert(Frozen(Timer::new(
+            Duration::from_secs_f64(FREEZE_DURATION),
+            TimerMode::Once,
+        )));
+        sprite.color = FROZEN_PADDLE_COLOR; // Ice tint while frozen
+    }
+}
+
+// Advances the freeze timers, restoring control and color once they finish
+pub fn thaw_paddle(
+    mut commands: Commands,
+    mut frozen_q: Query<(Entity, &mut Frozen, &mut Sprite)>,
+    theme: Res<ThemeColors>,
+    time: Res<Time>,
+) {
+    for (ent, mut frozen, mut sprite) in frozen_q.iter_mut() {
+        frozen.0.tick(time.delta());
+        if frozen.0.finished() {
+            commands.entity(ent).remove::<Frozen>();
+            sprite.color = theme.paddle;
+        }
+    }
+}
+
+// While the timer runs, left and right paddle input are swapped
+#[derive(Resource)]
+pub struct ReversedControls(Timer);
+
+impl ReversedControls {
+    pub fn active(&self) -> bool {
+        !self.0.finished()
+    }
+
+    // Swaps the left and right inputs while active
+    pub fn apply(&self, left: bool, right: bool) -> (bool, bool) {
+        if self.active() {
+            (right, left)
+        } else {
+            (left, right)
+        }
+    }
+}
+
+impl Default for ReversedControls {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(REVERSE_DURATION, TimerMode::Once);
+        let duration = timer.duration();
+        timer.tick(duration); // Start inactive
+        ReversedControls(timer)
+    }
+}
+
+// Marker for the confused icon floating over the paddle while its controls are reversed
+#[derive(Component)]
+pub struct ReversedCue;
+
+// Reverses the controls whenever a Reverse brick is destroyed. Another one only refreshes the
+// timer, so the controls never flip back while reversed
+pub fn reverse_controls_on_trap(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut reversed: ResMut<ReversedControls>,
+) {
+    let triggered = destroyed_events
+        .iter()
+        .any(|destroyed| destroyed.kind == BrickKind::Reverse);
+    if triggered {
+        reversed.0.reset();
+    }
+}
+
+// Reversed controls never carry over into a new level, a restart or the game over screen
+pub fn reset_reversed_controls(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut reversed: ResMut<ReversedControls>,
+) {
+    for msg in game_msgs.iter() {
+        if matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::RestartGame
+                | GameStateTransition::ToGameOver
+        ) {
+            *reversed = ReversedControls::default();
+        }
+    }
+}
+
+pub fn tick_reversed_controls(mut reversed: ResMut<ReversedControls>, time: Res<Time>) {
+    reversed.0.tick(time.delta());
+}
+
+// Shows the confused icon over the local paddle for as long as the controls are reversed
+pub fn show_reversed_cue(
+    mut commands: Commands,
+    reversed: Res<ReversedControls>,
+    paddle_q: Query<Entity, (With<Paddle>, Without<RemotePaddle>)>,
+    cue_q: Query<Entity, With<ReversedCue>>,
+) {
+    if reversed.active() && cue_q.is_empty() {
+        for paddle in paddle_q.iter() {
+            commands.entity(paddle).with_children(|parent| {
+                parent.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            "?!",
+                            TextStyle {
+                                font_size: REVERSED_CUE_FONT_SIZE,
+                                color: REVERSED_CUE_COLOR,
+                                ..default()
+                            },
+                        ),
+                        // Sits above the paddle, the scale undoes the paddle's scale
+                        transform: Transform {
+                            translation: Vec3::new(0., 1.5, 1.),
+                            scale: Vec3::new(1. / PADDLE_SIZE.x, 1. / PADDLE_SIZE.y, 1.),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ReversedCue,
+                ));
+            });
+        }
+    } else if !reversed.active() {
+        for cue in cue_q.iter() {
+            commands.entity(cue).despawn_recursive();
+        }
+    }
+}
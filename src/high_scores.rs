@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState, breaker::Level, misc::persist, scoreboard::Scoreboard, stats::RunStats,
+};
+
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+const MAX_HIGH_SCORES: usize = 10;
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScores::load())
+            .add_systems(OnEnter(AppState::GameOver), record_high_score);
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: usize,
+    pub level: usize, // The level the run ended on
+    pub date: String, // The local date the run ended, as YYYY-MM-DD
+}
+
+// The best scores across runs, highest first. Starts empty until the first run ends
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        persist::load_json(HIGH_SCORES_FILE)
+    }
+
+    pub fn save(&self) {
+        persist::save_json(HIGH_SCORES_FILE, self)
+    }
+
+    // Adds the score if it makes the list, returning whether it did. Ties keep the older entry
+    // ahead of the new one
+    pub fn insert(&mut self, entry: HighScore) -> bool {
+        let rank = self
+            .entries
+            .partition_point(|held| held.score >= entry.score);
+        if rank >= MAX_HIGH_SCORES {
+            return false;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        true
+    }
+}
+
+// Records the final score of each run once it ends, whether it was lost or won. Runs an assist
+// helped with and one life hardcore runs aren't ranked against normal runs, so they are left off
+pub fn record_high_score(
+    mut high_scores: ResMut<HighScores>,
+    scoreboard: Res<Scoreboard>,
+    level: Res<Level>,
+    stats: Res<RunStats>,
+) {
+    if stats.assisted || stats.hardcore {
+        info!("Assisted or hardcore run not recorded as a high score");
+        return;
+    }
+    let entry = HighScore {
+        score: scoreboard.score,
+        level: **level,
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+    };
+    if high_scores.insert(entry) {
+        high_scores.save();
+    }
+}
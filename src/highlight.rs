@@ -0,0 +1,387 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, Ball, GameStateTransition, Paddle, PADDLE_SIZE},
+    bricks::{Brick, Destroying},
+    misc::persist,
+    net::RemotePaddle,
+    settings::GameSettings,
+    theme::ThemeColors,
+    walls::{BOTTOM_WALL, LEFT_WALL, RIGHT_WALL, TOP_WALL},
+};
+
+const HIGHLIGHT_FILE: &str = "highlight.json";
+// Frames are recorded and played back at this rate rather than every frame
+const HIGHLIGHT_FRAME_INTERVAL: f32 = 1. / 30.;
+const DEFAULT_HIGHLIGHT_WINDOW: f32 = 10.;
+// Keeps the buffer, and the saved file, a reasonable size however long the window is set
+pub const MAX_HIGHLIGHT_WINDOW: f32 = 30.;
+// Playback is drawn over the frozen game, covering it with the arena's background
+const HIGHLIGHT_Z: f32 = 10.;
+
+pub struct HighlightPlugin;
+
+impl Plugin for HighlightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HighlightBuffer>()
+            .init_resource::<HighlightPlayer>()
+            .add_systems(OnEnter(AppState::InGame), clear_highlight)
+            .add_systems(OnExit(AppState::GameOver), stop_highlight)
+            .add_systems(
+                Update,
+                (
+                    save_highlight,
+                    record_highlight_frame
+                        .before(save_highlight)
+                        .run_if(game_running),
+                )
+                    .run_if(state_exists_and_equals(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                play_highlight.run_if(state_exists_and_equals(AppState::GameOver)),
+            );
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightSettings {
+    // Saves the last moments of each run at game over, so they can be watched or shared
+    pub enabled: bool,
+    pub window_secs: f32,
+}
+
+impl Default for HighlightSettings {
+    fn default() -> Self {
+        HighlightSettings {
+            enabled: false,
+            window_secs: DEFAULT_HIGHLIGHT_WINDOW,
+        }
+    }
+}
+
+impl HighlightSettings {
+    // How many frames cover the window
+    fn frame_capacity(&self) -> usize {
+        let window = self
+            .window_secs
+            .clamp(HIGHLIGHT_FRAME_INTERVAL, MAX_HIGHLIGHT_WINDOW);
+        (window / HIGHLIGHT_FRAME_INTERVAL).ceil() as usize
+    }
+}
+
+// Where everything was at one moment of play
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighlightFrame {
+    paddle: [f32; 3],     // x, y, and width
+    balls: Vec<[f32; 3]>, // x, y, and size
+    bricks: Vec<HighlightBrick>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct HighlightBrick {
+    position: [f32; 2],
+    size: [f32; 2],
+    strength: u8,
+}
+
+// The most recent frames of the run, only ever as many as fit in the highlight window
+#[derive(Resource)]
+pub struct HighlightBuffer {
+    frames: VecDeque<HighlightFrame>,
+    timer: Timer,
+}
+
+impl Default for HighlightBuffer {
+    fn default() -> Self {
+        HighlightBuffer {
+            frames: VecDeque::new(),
+            timer: Timer::from_seconds(HIGHLIGHT_FRAME_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl HighlightBuffer {
+    // Adds the newest frame, dropping the oldest ones that no longer fit
+    pub fn push(&mut self, frame: HighlightFrame, capacity: usize) {
+        self.frames.push_back(frame);
+        while self.frames.len() > capacity {
+            self.frames.pop_front();
+        }
+    }
+}
+
+// The frames saved at the end of a run
+#[derive(Default, Serialize, Deserialize)]
+pub struct Highlight {
+    frames: Vec<HighlightFrame>,
+}
+
+// The last run's highlight, and how far through watching it the player is
+#[derive(Resource, Default)]
+pub struct HighlightPlayer {
+    highlight: Option<Highlight>,
+    frame: Option<usize>, // None unless it is being watched
+    timer: Timer,
+}
+
+impl HighlightPlayer {
+    pub fn available(&self) -> bool {
+        self.highlight.is_some()
+    }
+
+    pub fn playing(&self) -> bool {
+        self.frame.is_some()
+    }
+
+    // Watches the highlight from the start
+    pub fn play(&mut self) {
+        if self.available() {
+            self.frame = Some(0);
+            self.timer = Timer::from_seconds(HIGHLIGHT_FRAME_INTERVAL, TimerMode::Repeating);
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.frame = None;
+    }
+}
+
+// A sprite drawn for the frame being played back
+#[derive(Component)]
+pub struct HighlightSprite;
+
+// Every run records its own highlight
+pub fn clear_highlight(mut buffer: ResMut<HighlightBuffer>, mut player: ResMut<HighlightPlayer>) {
+    *buffer = HighlightBuffer::default();
+    *player = HighlightPlayer::default();
+}
+
+pub fn stop_highlight(mut player: ResMut<HighlightPlayer>) {
+    player.stop();
+}
+
+pub fn record_highlight_frame(
+    mut buffer: ResMut<HighlightBuffer>,
+    paddle_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    ball_q: Query<&Transform, With<Ball>>,
+    brick_q: Query<(&Transform, &Brick), Without<Destroying>>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if !settings.highlight.enabled || !buffer.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok(paddle) = paddle_q.get_single() else {
+        return;
+    };
+    let frame = HighlightFrame {
+        paddle: [paddle.translation.x, paddle.translation.y, paddle.scale.x],
+        balls: ball_q
+            .iter()
+            .map(|tform| [tform.translation.x, tform.translation.y, tform.scale.x])
+            .collect(),
+        bricks: brick_q
+            .iter()
+            .map(|(tform, brick)| HighlightBrick {
+                position: tform.translation.truncate().into(),
+                size: tform.scale.truncate().into(),
+                strength: **brick,
+            })
+            .collect(),
+    };
+    buffer.push(frame, settings.highlight.frame_capacity());
+}
+
+// Keeps the buffered frames as the run's highlight once the game is over, and saves them to a
+// file so they can be shared. A restarted run starts recording afresh
+pub fn save_highlight(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut buffer: ResMut<HighlightBuffer>,
+    mut player: ResMut<HighlightPlayer>,
+    settings: Res<GameSettings>,
+) {
+    for msg in game_msgs.iter() {
+        match msg {
+            GameStateTransition::ToGameOver => {
+                if !settings.highlight.enabled || buffer.frames.is_empty() {
+                    continue;
+                }
+                let highlight = Highlight {
+                    frames: buffer.frames.drain(..).collect(),
+                };
+                persist::save_json(HIGHLIGHT_FILE, &highlight);
+                player.highlight = Some(highlight);
+            }
+            GameStateTransition::RestartGame => buffer.frames.clear(),
+            _ => {}
+        }
+    }
+}
+
+// Steps through the highlight while it is being watched, redrawing the arena for each frame
+pub fn play_highlight(
+    mut commands: Commands,
+    mut player: ResMut<HighlightPlayer>,
+    sprite_q: Query<Entity, With<HighlightSprite>>,
+    theme: Res<ThemeColors>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Some(frame) = player.frame else {
+        for sprite in sprite_q.iter() {
+            commands.entity(sprite).despawn_recursive();
+        }
+        return;
+    };
+    let advanced = player.timer.tick(time.delta()).just_finished();
+    if !advanced && !sprite_q.is_empty() {
+        return;
+    }
+    let frame = if advanced { frame + 1 } else { frame };
+    let Some(highlight_frame) = player.highlight.as_ref().and_then(|h| h.frames.get(frame)) else {
+        player.stop(); // The sprites go on the next update
+        return;
+    };
+
+    for sprite in sprite_q.iter() {
+        commands.entity(sprite).despawn_recursive();
+    }
+    let mut spawn_rect = |position: Vec2, size: Vec2, color: Color, z: f32| {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite { color, ..default() },
+                transform: Transform {
+                    translation: position.extend(HIGHLIGHT_Z + z),
+                    scale: size.extend(1.),
+                    ..default()
+                },
+                ..default()
+            },
+            HighlightSprite,
+        ));
+    };
+    spawn_rect(
+        Vec2::new((LEFT_WALL + RIGHT_WALL) / 2., (BOTTOM_WALL + TOP_WALL) / 2.),
+        Vec2::new(RIGHT_WALL - LEFT_WALL, TOP_WALL - BOTTOM_WALL),
+        theme.background,
+        0.,
+    );
+    for brick in highlight_frame.bricks.iter() {
+        spawn_rect(
+            brick.position.into(),
+            brick.size.into(),
+            settings.visual.brick_palette.brick_color(brick.strength),
+            1.,
+        );
+    }
+    let [x, y, width] = highlight_frame.paddle;
+    spawn_rect(
+        Vec2::new(x, y),
+        Vec2::new(width, PADDLE_SIZE.y),
+        theme.paddle,
+        1.,
+    );
+    for &[x, y, size] in highlight_frame.balls.iter() {
+        spawn_rect(Vec2::new(x, y), Vec2::splat(size), theme.ball, 2.);
+    }
+    player.frame = Some(frame);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        misc::test_utils::{advance_time, test_app},
+        theme::Theme,
+    };
+
+    fn paddle_frame(x: f32) -> HighlightFrame {
+        HighlightFrame {
+            paddle: [x, 0., PADDLE_SIZE.x],
+            balls: vec![],
+            bricks: vec![],
+        }
+    }
+
+    #[test]
+    fn the_buffer_only_keeps_the_window() {
+        let settings = HighlightSettings {
+            enabled: true,
+            window_secs: 1.,
+        };
+        let capacity = settings.frame_capacity();
+        let mut buffer = HighlightBuffer::default();
+        for x in 0..capacity + 5 {
+            buffer.push(paddle_frame(x as f32), capacity);
+        }
+        assert_eq!(buffer.frames.len(), capacity);
+        // The oldest frames made room for the newest
+        assert_eq!(buffer.frames[0].paddle[0], 5.);
+        assert_eq!(buffer.frames[capacity - 1].paddle[0], (capacity + 4) as f32);
+    }
+
+    fn next_frame(app: &mut App) {
+        advance_time(app, HIGHLIGHT_FRAME_INTERVAL * 1.01);
+        app.update();
+    }
+
+    // Where the highlight's paddle is drawn
+    fn played_paddle_x(app: &mut App) -> f32 {
+        let mut sprite_q = app
+            .world
+            .query_filtered::<&Transform, With<HighlightSprite>>();
+        let paddle = sprite_q
+            .iter(&app.world)
+            .find(|tform| tform.scale.truncate() == PADDLE_SIZE.truncate())
+            .unwrap();
+        paddle.translation.x
+    }
+
+    #[test]
+    fn the_saved_highlight_plays_back_the_recorded_positions() {
+        let mut app = test_app();
+        app.insert_resource(Theme::Classic.colors())
+            .init_resource::<HighlightBuffer>()
+            .init_resource::<HighlightPlayer>()
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (record_highlight_frame, save_highlight, play_highlight).chain(),
+            );
+        app.world.resource_mut::<GameSettings>().highlight.enabled = true;
+        let paddle = app
+            .world
+            .spawn((Transform::from_scale(PADDLE_SIZE), Paddle))
+            .id();
+        for x in [0., 10., 20.] {
+            app.world
+                .get_mut::<Transform>(paddle)
+                .unwrap()
+                .translation
+                .x = x;
+            next_frame(&mut app);
+        }
+
+        app.world.send_event(GameStateTransition::ToGameOver);
+        app.world.despawn(paddle);
+        app.update();
+        let mut player = app.world.resource_mut::<HighlightPlayer>();
+        assert!(player.available());
+        player.play();
+
+        // Shows the first frame straight away
+        advance_time(&mut app, 0.);
+        app.update();
+        assert_eq!(played_paddle_x(&mut app), 0.);
+        next_frame(&mut app);
+        assert_eq!(played_paddle_x(&mut app), 10.);
+        next_frame(&mut app);
+        assert_eq!(played_paddle_x(&mut app), 20.);
+    }
+}
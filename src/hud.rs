@@ -0,0 +1,167 @@
+use bevy::{prelude::*, window::WindowResized};
+
+const HUD_PADDING: f32 = 5.;
+const HUD_LINE_HEIGHT: f32 = 45.;
+const HEALTH_DIST_FROM_BOTTOM: f32 = 70.;
+// Roughly half the level display's width, so it sits centered along the top
+const LEVEL_HALF_WIDTH: f32 = 60.;
+// Wide enough for the longest seed, so it sits against the right edge
+const SEED_WIDTH: f32 = 260.;
+const DEFAULT_WINDOW_SIZE: Vec2 = Vec2::new(1280., 720.);
+
+// Identifies which HUD element a text entity is, so it can be placed by the HudLayout
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HudElement {
+    Score,
+    BallCount,
+    Health,
+    Hardcore,
+    Grace,
+    Speed,
+    Combo,
+    WallBonus,
+    Level,
+    Seed,
+}
+
+// The top left placement of every HUD element, in logical pixels
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct HudLayout {
+    pub score: Vec2,
+    pub ball_count: Vec2,
+    pub health: Vec2,
+    pub hardcore: Vec2,
+    pub grace: Vec2,
+    pub speed: Vec2,
+    pub combo: Vec2,
+    pub wall_bonus: Vec2,
+    pub level: Vec2,
+    pub seed: Vec2,
+}
+
+impl HudLayout {
+    // Stacks the counters in the top left, centers the level along the top, puts the seed in the
+    // top right and anchors health and speed to the bottom of the window
+    pub fn for_window(window_size: Vec2) -> Self {
+        HudLayout {
+            score: Vec2::new(HUD_PADDING, HUD_PADDING),
+            ball_count: Vec2::new(HUD_PADDING, HUD_PADDING + HUD_LINE_HEIGHT),
+            health: Vec2::new(HUD_PADDING, window_size.y - HEALTH_DIST_FROM_BOTTOM),
+            hardcore: Vec2::new(HUD_PADDING, HUD_PADDING + 2. * HUD_LINE_HEIGHT),
+            grace: Vec2::new(HUD_PADDING, HUD_PADDING + 3. * HUD_LINE_HEIGHT),
+            speed: Vec2::new(
+                HUD_PADDING,
+                window_size.y - HEALTH_DIST_FROM_BOTTOM - HUD_LINE_HEIGHT,
+            ),
+            combo: Vec2::new(HUD_PADDING, HUD_PADDING + 4. * HUD_LINE_HEIGHT),
+            wall_bonus: Vec2::new(HUD_PADDING, HUD_PADDING + 5. * HUD_LINE_HEIGHT),
+            level: Vec2::new(window_size.x / 2. - LEVEL_HALF_WIDTH, HUD_PADDING),
+            seed: Vec2::new(window_size.x - SEED_WIDTH, HUD_PADDING),
+        }
+    }
+
+    pub fn position(&self, element: HudElement) -> Vec2 {
+        match element {
+            HudElement::Score => self.score,
+            HudElement::BallCount => self.ball_count,
+            HudElement::Health => self.health,
+            HudElement::Hardcore => self.hardcore,
+            HudElement::Grace => self.grace,
+            HudElement::Speed => self.speed,
+            HudElement::Combo => self.combo,
+            HudElement::WallBonus => self.wall_bonus,
+            HudElement::Level => self.level,
+            HudElement::Seed => self.seed,
+        }
+    }
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        HudLayout::for_window(DEFAULT_WINDOW_SIZE)
+    }
+}
+
+// Recomputes the layout whenever the window changes size
+pub fn fit_hud_to_window(
+    mut resize_events: EventReader<WindowResized>,
+    mut layout: ResMut<HudLayout>,
+) {
+    if let Some(resized) = resize_events.iter().last() {
+        let fitted = HudLayout::for_window(Vec2::new(resized.width, resized.height));
+        if *layout != fitted {
+            *layout = fitted;
+        }
+    }
+}
+
+// Moves the HUD elements to their new positions when the layout changes
+pub fn apply_hud_layout(layout: Res<HudLayout>, mut hud_q: Query<(&HudElement, &mut Style)>) {
+    if !layout.is_changed() {
+        return;
+    }
+    for (element, mut style) in hud_q.iter_mut() {
+        let position = layout.position(*element);
+        style.top = Val::Px(position.y);
+        style.left = Val::Px(position.x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hud_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<HudLayout>()
+            .add_event::<WindowResized>()
+            .add_systems(Update, (fit_hud_to_window, apply_hud_layout).chain());
+        app
+    }
+
+    fn placement(app: &mut App, element: HudElement) -> (Val, Val) {
+        let mut hud_q = app.world.query::<(&HudElement, &Style)>();
+        let (_, style) = hud_q
+            .iter(&app.world)
+            .find(|(placed, _)| **placed == element)
+            .unwrap();
+        (style.left, style.top)
+    }
+
+    #[test]
+    fn changing_the_layout_moves_the_hud() {
+        let mut app = hud_app();
+        app.world.spawn((HudElement::Score, Style::default()));
+        app.update();
+        assert_eq!(
+            placement(&mut app, HudElement::Score),
+            (Val::Px(HUD_PADDING), Val::Px(HUD_PADDING))
+        );
+
+        app.world.resource_mut::<HudLayout>().score = Vec2::new(100., 200.);
+        app.update();
+        assert_eq!(
+            placement(&mut app, HudElement::Score),
+            (Val::Px(100.), Val::Px(200.))
+        );
+    }
+
+    #[test]
+    fn resizing_the_window_keeps_health_anchored_to_the_bottom() {
+        let mut app = hud_app();
+        app.world.spawn((HudElement::Health, Style::default()));
+        app.world.send_event(WindowResized {
+            window: Entity::PLACEHOLDER,
+            width: 800.,
+            height: 1000.,
+        });
+        app.update();
+        assert_eq!(
+            placement(&mut app, HudElement::Health),
+            (
+                Val::Px(HUD_PADDING),
+                Val::Px(1000. - HEALTH_DIST_FROM_BOTTOM)
+            )
+        );
+    }
+}
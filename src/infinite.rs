@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    app_state::AppState,
+    breaker::{BallSpeed, BrickTracker, CurrentState, GameState, GameStateTransition},
+    brick_descent::{Descending, BREACH_LINE},
+    bricks::{
+        brick_grid, spawn_brick_row, Brick, BrickDimensions, BrickKind, BrickRow, BrickShape,
+        Destroying, RowStrength,
+    },
+    hud::{HudElement, HudLayout},
+    misc::{live_text::set_or_spawn_text, rng::GameRng},
+    settings::GameSettings,
+};
+
+// A new row comes down this often at the start of a run, then a little sooner with every row
+const INFINITE_ROW_INTERVAL: f32 = 12.;
+const INFINITE_ROW_INTERVAL_DECAY: f32 = 0.95;
+const MIN_INFINITE_ROW_INTERVAL: f32 = 3.;
+// The ball speeds up as if a level was cleared every this many rows
+const ROWS_PER_SPEEDUP: usize = 5;
+// New rows get stronger bricks every this many rows, up to the strongest brick
+const ROWS_PER_STRENGTH: usize = 8;
+const MAX_INFINITE_STRENGTH: u8 = 5;
+// The chance of each cell in a new row being left empty
+const EMPTY_CELL_CHANCE: f64 = 0.2;
+const SEED_FONT_SIZE: f32 = 18.;
+const SEED_COLOR: Color = Color::rgb(0.6, 0.6, 0.6);
+
+// Chosen in the main menu. Infinite runs never finish a level, rows of bricks keep coming down
+// until they reach the paddle and the score is all that counts
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Levels,
+    Infinite,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [GameMode::Levels, GameMode::Infinite];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Levels => "Levels",
+            GameMode::Infinite => "Infinite",
+        }
+    }
+}
+
+pub struct InfinitePlugin;
+
+impl Plugin for InfinitePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<InfiniteRows>()
+            .add_systems(OnEnter(AppState::InGame), start_infinite_rows)
+            .add_systems(
+                Update,
+                (
+                    reset_infinite_rows,
+                    show_infinite_seed,
+                    advance_infinite_rows
+                        .after(reset_infinite_rows)
+                        .run_if(resource_equals(CurrentState(GameState::Playing))),
+                )
+                    .run_if(resource_equals(GameMode::Infinite))
+                    .run_if(state_exists_and_equals(AppState::InGame)),
+            );
+    }
+}
+
+// Counts down to the next row of an infinite run
+#[derive(Resource)]
+pub struct InfiniteRows {
+    timer: Timer,
+    rows: usize, // Rows brought down so far this run
+    // Set once the bricks start moving down, the new row is spawned after they have made room
+    pending: bool,
+}
+
+impl InfiniteRows {
+    fn interval(rows: usize) -> f32 {
+        let interval = INFINITE_ROW_INTERVAL * INFINITE_ROW_INTERVAL_DECAY.powi(rows as i32);
+        interval.max(MIN_INFINITE_ROW_INTERVAL)
+    }
+}
+
+impl Default for InfiniteRows {
+    fn default() -> Self {
+        InfiniteRows {
+            timer: Timer::from_seconds(InfiniteRows::interval(0), TimerMode::Once),
+            rows: 0,
+            pending: false,
+        }
+    }
+}
+
+pub fn start_infinite_rows(mut rows: ResMut<InfiniteRows>) {
+    *rows = InfiniteRows::default();
+}
+
+pub fn reset_infinite_rows(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut rows: ResMut<InfiniteRows>,
+) {
+    if game_msgs
+        .iter()
+        .any(|msg| matches!(msg, GameStateTransition::RestartGame))
+    {
+        *rows = InfiniteRows::default();
+    }
+}
+
+// Moves every brick down a row when the timer finishes, or right away once every brick is cleared,
+// then spawns a new row at the top. If that would bring any brick down to the paddle, the run is
+// over instead
+#[allow(clippy::too_many_arguments)]
+pub fn advance_infinite_rows(
+    mut commands: Commands,
+    mut rows: ResMut<InfiniteRows>,
+    mut brick_tracker: ResMut<BrickTracker>,
+    mut ball_speed: ResMut<BallSpeed>,
+    mut game_msgs: EventWriter<GameStateTransition>,
+    mut rng: ResMut<GameRng>,
+    brick_q: Query<(Entity, &Transform, Option<&Descending>), (With<Brick>, Without<Destroying>)>,
+    brick_dims: Res<BrickDimensions>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if rows.pending {
+        // Waits until the old top row has slid out of the way
+        if brick_q.iter().any(|(_, _, sliding)| sliding.is_some()) {
+            return;
+        }
+        rows.pending = false;
+        let Some(grid) = brick_grid(&brick_dims) else {
+            return;
+        };
+        let row = infinite_row(rows.rows, grid.cols, &mut rng);
+        **brick_tracker += spawn_brick_row(
+            &mut commands,
+            &row,
+            grid.top_y,
+            grid.left_edge,
+            grid.cols,
+            &brick_dims,
+            settings.gameplay.arena_shape,
+            settings.visual.brick_palette,
+            &asset_server,
+        );
+        return;
+    }
+    let finished = rows.timer.tick(time.delta()).finished();
+    if !finished && **brick_tracker > 0 {
+        return;
+    }
+
+    let row_height = brick_dims.size.y + brick_dims.margin;
+    // Bricks still sliding down from the brick descent challenge count from where they are headed
+    let target_y = |tform: &Transform, descending: Option<&Descending>| {
+        descending.map_or(tform.translation.y, |d| d.target_y) - row_height
+    };
+    let breached = brick_q.iter().any(|(_, tform, descending)| {
+        target_y(tform, descending) - tform.scale.y / 2. <= BREACH_LINE
+    });
+    if breached {
+        game_msgs.send(GameStateTransition::ToGameOver);
+        return;
+    }
+    for (brick, tform, descending) in brick_q.iter() {
+        commands.entity(brick).insert(Descending {
+            target_y: target_y(tform, descending),
+        });
+    }
+    rows.pending = true;
+    rows.rows += 1;
+    rows.timer = Timer::from_seconds(InfiniteRows::interval(rows.rows), TimerMode::Once);
+    ball_speed.set_level(1 + rows.rows / ROWS_PER_SPEEDUP);
+}
+
+// Marker for the seed shown on the HUD during infinite runs
+#[derive(Component)]
+pub struct SeedDisplay;
+
+// Shows the run's seed, so a good infinite run can be shared and played again
+pub fn show_infinite_seed(
+    mut commands: Commands,
+    mut text_q: Query<&mut Text, With<SeedDisplay>>,
+    rng: Res<GameRng>,
+    hud_layout: Res<HudLayout>,
+) {
+    let shown = format!("Seed: {}", rng.seed());
+    let position = hud_layout.position(HudElement::Seed);
+    let text = text_q.get_single_mut().ok();
+    set_or_spawn_text(&mut commands, text, shown, |shown| {
+        (
+            TextBundle::from_section(
+                shown,
+                TextStyle {
+                    font_size: SEED_FONT_SIZE,
+                    color: SEED_COLOR,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(position.y),
+                left: Val::Px(position.x),
+                ..default()
+            }),
+            HudElement::Seed,
+            SeedDisplay,
+            Name::new("SeedDisplay"),
+        )
+    });
+}
+
+// A row of plain bricks with a few gaps, getting stronger the longer the run goes
+fn infinite_row(rows: usize, cols: u32, rng: &mut GameRng) -> BrickRow {
+    let max_strength = (1 + rows / ROWS_PER_STRENGTH).min(MAX_INFINITE_STRENGTH as usize) as u8;
+    let mut cells: Vec<u8> = (0..cols)
+        .map(|_| {
+            if rng.gen_bool(EMPTY_CELL_CHANCE) {
+                0
+            } else {
+                rng.gen_range(1..=max_strength)
+            }
+        })
+        .collect();
+    // Never an empty row, there'd be nothing to clear
+    if cells.iter().all(|strength| *strength == 0) {
+        cells[rng.gen_range(0..cols as usize)] = max_strength;
+    }
+    BrickRow {
+        strength: RowStrength::Cells(cells),
+        kind: BrickKind::default(),
+        shape: BrickShape::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::test_app;
+
+    const TEST_COLS: u32 = 10;
+
+    // The cells of the first few rows a run would bring down
+    fn first_rows(seed: u64) -> Vec<Vec<u8>> {
+        let mut rng = GameRng::from_seed(seed);
+        (0..ROWS_PER_STRENGTH)
+            .map(
+                |rows| match infinite_row(rows, TEST_COLS, &mut rng).strength {
+                    RowStrength::Cells(cells) => cells,
+                    RowStrength::Uniform(strength) => vec![strength; TEST_COLS as usize],
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_brings_down_the_same_rows() {
+        assert_eq!(first_rows(42), first_rows(42));
+        assert_ne!(first_rows(42), first_rows(43));
+    }
+
+    #[test]
+    fn the_seed_is_shown_on_the_hud() {
+        let mut app = test_app();
+        app.init_resource::<HudLayout>()
+            .add_systems(Update, show_infinite_seed);
+        app.update();
+
+        let mut text_q = app.world.query_filtered::<&Text, With<SeedDisplay>>();
+        let text = text_q.single(&app.world);
+        assert_eq!(text.sections[0].value, "Seed: 0");
+    }
+}
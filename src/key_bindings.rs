@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    breaker::PADDLE_INPUT_TOGGLE_KEY, debug_overlay::DEBUG_OVERLAY_TOGGLE_KEY,
+    theme::THEME_CYCLE_KEY,
+};
+
+// How many keys can be bound to each action
+pub const BINDING_SLOTS: usize = 2;
+// Keys with fixed uses elsewhere, the other function keys are the dev tool toggles
+const RESERVED_KEYS: [KeyCode; 7] = [
+    THEME_CYCLE_KEY,
+    PADDLE_INPUT_TOGGLE_KEY,
+    DEBUG_OVERLAY_TOGGLE_KEY,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F4,
+    KeyCode::F5,
+];
+
+// Everything the player can rebind
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Start, // Starts a previewed level and resumes from pause
+    Pause,
+    FireTurret, // Only used in turret mode
+    Launch,     // Launches a ball held on the paddle
+    Restart,    // Starts a fresh run from the first level
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Start,
+        Action::Pause,
+        Action::FireTurret,
+        Action::Launch,
+        Action::Restart,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Start => "Start",
+            Action::Pause => "Pause",
+            Action::FireTurret => "Fire Turret",
+            Action::Launch => "Launch",
+            Action::Restart => "Restart",
+        }
+    }
+}
+
+// Why a key could not be bound
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindError {
+    Reserved,
+    Duplicate(Action), // The key is already bound to this action
+}
+
+// The keys bound to each action, a key is never bound twice
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_left: Vec<KeyCode>,
+    pub move_right: Vec<KeyCode>,
+    pub start: Vec<KeyCode>,
+    pub pause: Vec<KeyCode>,
+    pub fire_turret: Vec<KeyCode>,
+    pub launch: Vec<KeyCode>,
+    pub restart: Vec<KeyCode>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            move_left: vec![KeyCode::A, KeyCode::Left],
+            move_right: vec![KeyCode::D, KeyCode::Right],
+            start: vec![KeyCode::Return],
+            pause: vec![KeyCode::Escape],
+            fire_turret: vec![KeyCode::Space],
+            launch: vec![KeyCode::W, KeyCode::Up],
+            restart: vec![KeyCode::R],
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        match action {
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::Start => &self.start,
+            Action::Pause => &self.pause,
+            Action::FireTurret => &self.fire_turret,
+            Action::Launch => &self.launch,
+            Action::Restart => &self.restart,
+        }
+    }
+
+    fn keys_mut(&mut self, action: Action) -> &mut Vec<KeyCode> {
+        match action {
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::Start => &mut self.start,
+            Action::Pause => &mut self.pause,
+            Action::FireTurret => &mut self.fire_turret,
+            Action::Launch => &mut self.launch,
+            Action::Restart => &mut self.restart,
+        }
+    }
+
+    // The action's first key, as named in on screen prompts
+    pub fn prompt_label(&self, action: Action) -> String {
+        match self.keys(action).first() {
+            Some(key) => format!("{key:?}"),
+            None => action.label().to_string(),
+        }
+    }
+
+    pub fn pressed(&self, action: Action, input: &Input<KeyCode>) -> bool {
+        input.any_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn just_pressed(&self, action: Action, input: &Input<KeyCode>) -> bool {
+        input.any_just_pressed(self.keys(action).iter().copied())
+    }
+
+    // Binds the key to one of the action's slots, replacing the key in that slot. Rebinding a
+    // key to the slot it is already in is allowed
+    pub fn bind(&mut self, action: Action, slot: usize, key: KeyCode) -> Result<(), BindError> {
+        if RESERVED_KEYS.contains(&key) {
+            return Err(BindError::Reserved);
+        }
+        for other in Action::ALL {
+            let keys = self.keys(other);
+            if let Some(bound_slot) = keys.iter().position(|bound| *bound == key) {
+                if other != action || bound_slot != slot {
+                    return Err(BindError::Duplicate(other));
+                }
+            }
+        }
+        let keys = self.keys_mut(action);
+        match keys.get_mut(slot) {
+            Some(bound) => *bound = key,
+            None if slot < BINDING_SLOTS => keys.push(key),
+            None => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, Level},
+    misc::labeled_display::{update_labeled_display, DisplayedValue, LabeledDisplayBundle},
+};
+
+const BANNER_FONT_SIZE: f32 = 80.;
+const BANNER_COLOR: Color = Color::rgb(1., 1., 1.);
+// How long the banner stays up, fading out over the whole time
+const BANNER_SECS: f32 = 1.5;
+// The banner's top edge, as a percentage of the window height
+const BANNER_TOP_PERCENT: f32 = 40.;
+
+pub struct LevelDisplayPlugin;
+
+impl Plugin for LevelDisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                update_labeled_display::<Level, LevelDisplay>.run_if(game_running),
+                show_level_banner,
+                fade_level_banner.after(show_level_banner),
+            )
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// Marker for the ui text bundle
+#[derive(Component)]
+pub struct LevelDisplay;
+
+#[derive(Bundle)]
+pub struct LevelDisplayBundle {
+    display: LabeledDisplayBundle<LevelDisplay>,
+}
+
+impl LevelDisplayBundle {
+    pub fn new<L: Into<String>, V: Into<String>>(
+        font_size: f32,
+        label_color: Color,
+        level_color: Color,
+        label: L,
+        top_left_placement: Vec2,
+        initial_value: Option<V>,
+    ) -> Self {
+        LevelDisplayBundle {
+            display: LabeledDisplayBundle::new(
+                LevelDisplay,
+                "Level",
+                font_size,
+                label_color,
+                level_color,
+                label,
+                top_left_placement,
+                initial_value,
+            ),
+        }
+    }
+}
+
+// Shown by the level display, see update_labeled_display
+impl DisplayedValue for Level {
+    fn displayed_value(&self) -> String {
+        (**self).to_string()
+    }
+}
+
+// A large "Level N" across the middle of the arena, fading out shortly after a level starts
+#[derive(Component)]
+pub struct LevelBanner(Timer);
+
+// Announces each level as it starts, replacing any banner still fading out
+pub fn show_level_banner(
+    mut commands: Commands,
+    level: Res<Level>,
+    banner_q: Query<Entity, With<LevelBanner>>,
+) {
+    if !level.is_changed() {
+        return;
+    }
+    for ent in banner_q.iter() {
+        commands.entity(ent).despawn_recursive();
+    }
+    commands.spawn((
+        TextBundle::from_section(
+            format!("Level {}", **level),
+            TextStyle {
+                font_size: BANNER_FONT_SIZE,
+                color: BANNER_COLOR,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(BANNER_TOP_PERCENT),
+            width: Val::Percent(100.),
+            justify_content: JustifyContent::Center,
+            ..default()
+        }),
+        LevelBanner(Timer::from_seconds(BANNER_SECS, TimerMode::Once)),
+        Name::new("LevelBanner"),
+    ));
+}
+
+pub fn fade_level_banner(
+    mut commands: Commands,
+    mut banner_q: Query<(Entity, &mut LevelBanner, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (ent, mut banner, mut text) in banner_q.iter_mut() {
+        if banner.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+            continue;
+        }
+        let alpha = banner.0.percent_left();
+        text.sections[0].style.color.set_a(alpha);
+    }
+}
@@ -0,0 +1,203 @@
+use std::fs;
+
+use bevy::{asset::FileAssetIo, ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+
+use crate::{
+    background::LevelBackground,
+    bricks::{spawn_bricks, BrickDimensions, BrickLayout, BrickRow, RowStrength},
+    infinite::GameMode,
+    settings::GameSettings,
+};
+
+// Level files are read from this folder under the assets folder, in file name order
+const LEVELS_DIR: &str = "assets/levels";
+const LEVEL_EXTENSION: &str = "ron";
+
+// A level's bricks and the background they are played in front of, read from a RON file
+#[derive(Clone, Debug, Deserialize)]
+pub struct LevelDefinition {
+    #[serde(default)]
+    pub background: LevelBackground,
+    pub rows: BrickLayout,
+}
+
+impl Default for LevelDefinition {
+    // A single row of the weakest bricks, played when no level files could be read
+    fn default() -> Self {
+        LevelDefinition {
+            background: LevelBackground::default(),
+            rows: BrickLayout(vec![BrickRow {
+                strength: RowStrength::Uniform(1),
+                kind: default(),
+                shape: default(),
+            }]),
+        }
+    }
+}
+
+// Every level in play order, loaded once at startup. Never empty
+#[derive(Resource)]
+pub struct Levels(Vec<LevelDefinition>);
+
+impl Levels {
+    // Reads every level file, skipping any that can't be read or parsed
+    pub fn load() -> Self {
+        let dir = FileAssetIo::get_base_path().join(LEVELS_DIR);
+        let mut paths: Vec<_> = match fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == LEVEL_EXTENSION))
+                .collect(),
+            Err(e) => {
+                warn!("Couldn't read the levels in {}: {e}", dir.display());
+                Vec::new()
+            }
+        };
+        paths.sort();
+
+        let levels: Vec<_> = paths
+            .iter()
+            .filter_map(|path| {
+                let parsed = fs::read_to_string(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|contents| parse_level(&contents).map_err(|e| e.to_string()));
+                match parsed {
+                    Ok(level) => Some(level),
+                    Err(e) => {
+                        warn!("Skipping level {}: {e}", path.display());
+                        None
+                    }
+                }
+            })
+            .collect();
+        if levels.is_empty() {
+            warn!("No levels loaded, playing a single fallback level");
+            return Levels(vec![LevelDefinition::default()]);
+        }
+        info!("Loaded {} levels", levels.len());
+        Levels(levels)
+    }
+
+    // The definition of a level, counting from level 1. Levels past the last one get the last
+    // one's definition, though the run is won before they can be reached
+    pub fn get(&self, level: usize) -> &LevelDefinition {
+        &self.0[(level.max(1) - 1).min(self.0.len() - 1)]
+    }
+
+    // The number of the last level, clearing it wins the run
+    pub fn final_level(&self) -> usize {
+        self.0.len()
+    }
+
+    // Levels made up by tests rather than read from the level files
+    #[cfg(test)]
+    pub fn from_definitions(levels: Vec<LevelDefinition>) -> Self {
+        assert!(!levels.is_empty());
+        Levels(levels)
+    }
+}
+
+// Everything needed to lay out a level's bricks, grouped so the systems that spawn levels stay
+// under Bevy's system parameter limit
+#[derive(SystemParam)]
+pub struct LevelSpawner<'w> {
+    levels: Res<'w, Levels>,
+    dims: Res<'w, BrickDimensions>,
+    asset_server: Res<'w, AssetServer>,
+    settings: Res<'w, GameSettings>,
+    mode: Res<'w, GameMode>,
+}
+
+impl LevelSpawner<'_> {
+    // Whether the run goes on past the level's bricks, so clearing them doesn't finish the level
+    pub fn is_endless(&self) -> bool {
+        *self.mode == GameMode::Infinite
+    }
+
+    // Whether clearing the level wins the run, as there is no level after it
+    pub fn is_final_level(&self, level: usize) -> bool {
+        level >= self.levels.final_level()
+    }
+
+    // Spawns the level's bricks, returning how many were spawned
+    pub fn spawn_bricks(&self, commands: &mut Commands, level: usize) -> usize {
+        spawn_bricks(
+            commands,
+            &self.levels.get(level).rows,
+            &self.dims,
+            self.settings.gameplay.arena_shape,
+            self.settings.visual.brick_palette,
+            &self.asset_server,
+        )
+    }
+}
+
+pub fn parse_level(contents: &str) -> Result<LevelDefinition, ron::error::SpannedError> {
+    ron::from_str(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bricks::{brick_grid, Brick},
+        misc::test_utils::test_app,
+    };
+
+    const SAMPLE_LEVEL: &str = "(
+        background: Cityscape,
+        rows: [
+            (strength: Uniform(2)),
+            (strength: Cells([1, 0, 3])),
+        ],
+    )";
+
+    #[derive(Resource, Default)]
+    struct Spawned(usize);
+
+    fn spawn_first_level(
+        mut commands: Commands,
+        spawner: LevelSpawner,
+        mut spawned: ResMut<Spawned>,
+    ) {
+        spawned.0 = spawner.spawn_bricks(&mut commands, 1);
+    }
+
+    #[test]
+    fn a_parsed_level_spawns_a_brick_for_every_filled_cell() {
+        let level = parse_level(SAMPLE_LEVEL).unwrap();
+        assert_eq!(level.background, LevelBackground::Cityscape);
+
+        let mut app = test_app();
+        // Bricks load their textures
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .insert_resource(Levels::from_definitions(vec![level]))
+            .init_resource::<BrickDimensions>()
+            .init_resource::<GameMode>()
+            .init_resource::<Spawned>()
+            .add_systems(Update, spawn_first_level);
+        app.update();
+
+        // A full row, then two of the three cells
+        let cols = brick_grid(&BrickDimensions::default()).unwrap().cols as usize;
+        assert_eq!(app.world.resource::<Spawned>().0, cols + 2);
+        let mut brick_q = app.world.query_filtered::<(), With<Brick>>();
+        assert_eq!(brick_q.iter(&app.world).count(), cols + 2);
+    }
+
+    #[test]
+    fn every_shipped_level_parses() {
+        let dir = FileAssetIo::get_base_path().join(LEVELS_DIR);
+        let mut parsed = 0;
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let contents = fs::read_to_string(&path).unwrap();
+            if let Err(e) = parse_level(&contents) {
+                panic!("{} doesn't parse: {e}", path.display());
+            }
+            parsed += 1;
+        }
+        assert!(parsed > 0);
+    }
+}
@@ -1,21 +1,88 @@
 use bevy::prelude::*;
+#[cfg(debug_assertions)]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
 use app_state::AppStatePlugin;
+use background::BackgroundPlugin;
+use ball_trail::BallTrailPlugin;
 use breaker::BreakoutGamePlugin;
+use combo::ComboPlugin;
+use countdown::CountdownPlugin;
+use crt::CrtPlugin;
+use debug_overlay::DebugOverlayPlugin;
+use ghost_ball::GhostBallPlugin;
+use high_scores::HighScoresPlugin;
+use highlight::HighlightPlugin;
+use infinite::InfinitePlugin;
+use level_display::LevelDisplayPlugin;
+use music::MusicPlugin;
+use net::NetPlugin;
+use pickups::PickupPlugin;
+use screen_shake::ScreenShakePlugin;
+use strong_ball::StrongBallPlugin;
+use telemetry::TelemetryPlugin;
+use theme::ThemePlugin;
+use tilt::TiltPlugin;
 use ui::UIPlugin;
+use wall_bonus::WallBonusPlugin;
 
 pub mod app_state;
+pub mod background;
+pub mod ball_count;
+pub mod ball_tints;
+pub mod ball_trail;
+pub mod bonus_round;
 pub mod breaker;
+pub mod breakthrough;
+pub mod brick_descent;
 pub mod bricks;
+pub mod catch;
+pub mod combo;
+pub mod countdown;
+pub mod crt;
+pub mod debug_overlay;
+pub mod difficulty;
+#[cfg(feature = "dev")]
+pub mod dev;
+pub mod elements;
+pub mod events;
+pub mod gamepad;
+pub mod ghost_ball;
+pub mod grace;
+pub mod hardcore;
+pub mod hazards;
 pub mod health;
+pub mod high_scores;
+pub mod highlight;
+pub mod hud;
+pub mod infinite;
+pub mod key_bindings;
+pub mod level_display;
+pub mod levels;
 pub mod misc;
+pub mod music;
+pub mod net;
+pub mod paddle_width;
+pub mod physics;
+pub mod pickups;
+pub mod powerups;
+pub mod preview;
+pub mod profile;
+pub mod returning;
 pub mod scoreboard;
+pub mod screen_shake;
+pub mod settings;
+pub mod speed;
+pub mod stats;
+pub mod strong_ball;
+pub mod telemetry;
+pub mod theme;
+pub mod tilt;
+pub mod turret;
 pub mod ui;
+pub mod wall_bonus;
 pub mod walls;
 
-const CLEAR_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
-
 fn main() {
     let mut app = App::new();
     app.add_plugins((
@@ -23,9 +90,35 @@ fn main() {
         BreakoutGamePlugin,
         UIPlugin,
         AppStatePlugin,
-        WorldInspectorPlugin::new(),
-    ))
-    .insert_resource(ClearColor(CLEAR_COLOR));
+        PickupPlugin,
+        StrongBallPlugin,
+        InfinitePlugin,
+        CountdownPlugin,
+        DebugOverlayPlugin,
+    ));
+    // The inspector is heavy, release builds make do with the debug overlay
+    #[cfg(debug_assertions)]
+    app.add_plugins(WorldInspectorPlugin::new());
+    // Added separately, Bevy only accepts up to 15 plugins in one tuple
+    app.add_plugins((
+        ThemePlugin,
+        BackgroundPlugin,
+        BallTrailPlugin,
+        CrtPlugin,
+        TiltPlugin,
+        ScreenShakePlugin,
+        MusicPlugin,
+        HighlightPlugin,
+        HighScoresPlugin,
+        LevelDisplayPlugin,
+        ComboPlugin,
+        GhostBallPlugin,
+        WallBonusPlugin,
+        NetPlugin,
+        TelemetryPlugin,
+    ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(dev::DevPlugin);
 
     app.run()
 }
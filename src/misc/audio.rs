@@ -0,0 +1,60 @@
+use bevy::{asset::LoadState, prelude::*};
+
+// A sound effect the game can do without. If its file is missing or fails to load, a warning is
+// logged once and the sound is skipped from then on, rather than playing a handle that never loads
+pub struct OptionalSound {
+    path: &'static str,
+    handle: Handle<AudioSource>,
+    warned: bool,
+}
+
+impl OptionalSound {
+    pub fn load(asset_server: &AssetServer, path: &'static str) -> Self {
+        OptionalSound {
+            path,
+            handle: asset_server.load(path),
+            warned: false,
+        }
+    }
+
+    // The sound to play, None while it is still loading or if it failed to load
+    pub fn playable(&mut self, asset_server: &AssetServer) -> Option<Handle<AudioSource>> {
+        match asset_server.get_load_state(&self.handle) {
+            LoadState::Loaded => Some(self.handle.clone()),
+            LoadState::Failed => {
+                if !self.warned {
+                    warn!("Couldn't load {}, playing without it", self.path);
+                    self.warned = true;
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn a_missing_sound_is_skipped_with_a_single_warning() {
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()));
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let mut sound = OptionalSound::load(&asset_server, "sounds/missing.ogg");
+        // Loading happens on the IO task pool, there's nothing to play in the meantime
+        assert!(sound.playable(&asset_server).is_none());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while asset_server.get_load_state(&sound.handle) != LoadState::Failed {
+            assert!(Instant::now() < deadline, "never failed to load");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        for _ in 0..3 {
+            assert!(sound.playable(&asset_server).is_none());
+        }
+        assert!(sound.warned);
+    }
+}
@@ -1,32 +1,128 @@
 use bevy::prelude::*;
 
+use crate::settings::GameSettings;
+
+// Under reduce motion a blinking sprite holds this tint instead of flashing
+const REDUCED_MOTION_BLINK_COLOR: Color = Color::rgb(1., 0.35, 0.35);
+
 // Causes the associated entity to blink (if it also has a Visibility component)
 #[derive(Component)]
 pub struct Blinking(pub Timer);
 
+// The color a sprite had before reduce motion tinted it, put back once the blinking is over
+#[derive(Component)]
+pub struct BlinkTint(Color);
+
 pub fn blink(
     mut commands: Commands,
-    mut blinker_q: Query<(Entity, &mut Blinking, &mut Visibility)>,
+    mut blinker_q: Query<(
+        Entity,
+        &mut Blinking,
+        &mut Visibility,
+        Option<&mut Sprite>,
+        Option<&BlinkTint>,
+    )>,
+    settings: Res<GameSettings>,
     time: Res<Time>,
 ) {
-    for (blinker_ent, mut blink_time, mut visible) in blinker_q.iter_mut() {
+    for (blinker_ent, mut blink_time, mut visible, sprite, tint) in blinker_q.iter_mut() {
         blink_time.0.tick(time.delta()); // Advance the blinker
 
         // Blink 5 times during the lifetime of the blinker
 
         // Mod by 1/5 to focus on fifths of a second, then rescale
         // so that 0 to 50 is half the time, 50 to 100 is the other half
+        // Reduce motion keeps the entity steady and tints it instead, the timer still runs since it
+        // doubles as i-frames
         let r = ((blink_time.0.percent_left()) % (1. / 5.)) * 100. * 5.;
-        if r <= 50.0 {
+        if r <= 50.0 || settings.accessibility.reduce_motion {
             *visible = Visibility::Inherited;
         } else {
             *visible = Visibility::Hidden;
         }
+        let finished = blink_time.0.finished();
+        if let Some(mut sprite) = sprite {
+            match tint {
+                Some(BlinkTint(original)) if finished => {
+                    sprite.color = *original;
+                    commands.entity(blinker_ent).remove::<BlinkTint>();
+                }
+                None if settings.accessibility.reduce_motion && !finished => {
+                    commands.entity(blinker_ent).insert(BlinkTint(sprite.color));
+                    sprite.color = REDUCED_MOTION_BLINK_COLOR;
+                }
+                _ => {}
+            }
+        }
 
         // Delete the Blinking component if the contained timer finishes, reset visibility
-        if blink_time.0.finished() {
+        if finished {
             commands.entity(blinker_ent).remove::<Blinking>();
             *visible = Visibility::Inherited;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{advance_time, test_app};
+
+    const BLINK_SECS: f32 = 1.;
+
+    fn blink_app(reduce_motion: bool) -> (App, Entity) {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .accessibility
+            .reduce_motion = reduce_motion;
+        app.add_systems(Update, blink);
+        let paddle = app
+            .world
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::BLUE,
+                        ..default()
+                    },
+                    ..default()
+                },
+                Blinking(Timer::from_seconds(BLINK_SECS, TimerMode::Once)),
+            ))
+            .id();
+        advance_time(&mut app, 0.);
+        app.update();
+        (app, paddle)
+    }
+
+    fn step(app: &mut App, paddle: Entity, secs: f32) -> (Visibility, Color) {
+        advance_time(app, secs);
+        app.update();
+        let visibility = *app.world.get::<Visibility>(paddle).unwrap();
+        (visibility, app.world.get::<Sprite>(paddle).unwrap().color)
+    }
+
+    #[test]
+    fn blinking_hides_the_sprite_on_and_off() {
+        let (mut app, paddle) = blink_app(false);
+        let hidden = (0..10)
+            .map(|_| step(&mut app, paddle, BLINK_SECS / 20.))
+            .filter(|(visibility, _)| *visibility == Visibility::Hidden)
+            .count();
+        assert!(hidden > 0);
+    }
+
+    #[test]
+    fn reduce_motion_tints_the_sprite_instead_of_hiding_it() {
+        let (mut app, paddle) = blink_app(true);
+        for _ in 0..10 {
+            let (visibility, color) = step(&mut app, paddle, BLINK_SECS / 20.);
+            assert_eq!(visibility, Visibility::Inherited);
+            assert_eq!(color, REDUCED_MOTION_BLINK_COLOR);
+        }
+
+        let (_, color) = step(&mut app, paddle, BLINK_SECS);
+        assert_eq!(color, Color::BLUE);
+        assert!(app.world.get::<Blinking>(paddle).is_none());
+    }
+}
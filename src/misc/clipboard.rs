@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+// The system clipboard, opened on the first copy and then held for the whole session, since on
+// X11 the copied text is gone as soon as the clipboard that set it is dropped. Not every platform
+// has one, so copying is best effort
+#[derive(Default)]
+pub struct Clipboard(Option<arboard::Clipboard>);
+
+impl Clipboard {
+    pub fn copy(&mut self, text: String) {
+        let clipboard = match self.0.take().map_or_else(arboard::Clipboard::new, Ok) {
+            Ok(clipboard) => self.0.insert(clipboard),
+            Err(e) => {
+                warn!("No clipboard available, not copying: {e}");
+                return;
+            }
+        };
+        match clipboard.set_text(text) {
+            Ok(()) => info!("Copied to the clipboard"),
+            Err(e) => warn!("Could not copy to the clipboard: {e}"),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+
+use crate::{breaker::BrickDestroyed, settings::GameSettings};
+
+pub const HIT_STOP_DURATION: f32 = 0.05;
+// Minimum time between hit-stops so rapid breaks don't chain into a noticeable stall
+const HIT_STOP_COOLDOWN: f32 = 0.25;
+
+// Briefly freezes all gameplay motion when a brick breaks, giving impacts some weight. Turned off
+// in the visual settings
+#[derive(Resource)]
+pub struct HitStop {
+    timer: Timer,
+    cooldown: Timer,
+}
+
+impl HitStop {
+    pub fn active(&self) -> bool {
+        !self.timer.finished()
+    }
+}
+
+impl Default for HitStop {
+    fn default() -> Self {
+        HitStop {
+            timer: finished_timer(HIT_STOP_DURATION),
+            cooldown: finished_timer(HIT_STOP_COOLDOWN),
+        }
+    }
+}
+
+// Creates a timer that starts out finished, so nothing happens until it is reset
+fn finished_timer(secs: f32) -> Timer {
+    let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+    let duration = timer.duration();
+    timer.tick(duration);
+    timer
+}
+
+// Starts a hit-stop whenever a brick is destroyed, unless one just happened
+pub fn trigger_hit_stop(
+    mut hit_stop: ResMut<HitStop>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    settings: Res<GameSettings>,
+) {
+    if destroyed_events.is_empty() {
+        return;
+    }
+    destroyed_events.clear();
+
+    if !settings.visual.hit_stop || settings.accessibility.reduce_motion {
+        return;
+    }
+    if hit_stop.active() || !hit_stop.cooldown.finished() {
+        return;
+    }
+    hit_stop.timer.reset();
+    hit_stop.cooldown.reset();
+}
+
+// Advances the hit-stop, then its cooldown once the freeze is over
+pub fn tick_hit_stop(mut hit_stop: ResMut<HitStop>, time: Res<Time>) {
+    if hit_stop.active() {
+        hit_stop.timer.tick(time.delta());
+    } else {
+        hit_stop.cooldown.tick(time.delta());
+    }
+}
+
+// Run condition for the physics systems, which skip their ticks during a hit-stop
+pub fn hit_stop_inactive(hit_stop: Res<HitStop>) -> bool {
+    !hit_stop.active()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bricks::BrickKind,
+        misc::test_utils::{advance_time, test_app},
+    };
+
+    fn hit_stop_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<HitStop>()
+            .add_event::<BrickDestroyed>()
+            .add_systems(Update, (tick_hit_stop, trigger_hit_stop).chain());
+        advance_time(&mut app, 0.);
+        app.update();
+        app
+    }
+
+    // Moves the clock on, breaking a brick on the way if asked
+    fn step(app: &mut App, secs: f32, break_brick: bool) -> bool {
+        if break_brick {
+            app.world.send_event(BrickDestroyed {
+                position: Vec3::ZERO,
+                kind: BrickKind::default(),
+                ball: Entity::PLACEHOLDER,
+                color: Color::WHITE,
+            });
+        }
+        advance_time(app, secs);
+        app.update();
+        app.world.resource::<HitStop>().active()
+    }
+
+    #[test]
+    fn a_break_freezes_play_until_the_hit_stop_is_over() {
+        let mut app = hit_stop_app();
+        assert!(step(&mut app, 0., true));
+        assert!(step(&mut app, HIT_STOP_DURATION * 0.9, false));
+        assert!(!step(&mut app, HIT_STOP_DURATION * 0.2, false));
+    }
+
+    #[test]
+    fn breaks_during_the_cooldown_do_not_freeze_again() {
+        let mut app = hit_stop_app();
+        step(&mut app, 0., true);
+        assert!(!step(&mut app, HIT_STOP_DURATION * 1.1, false));
+        assert!(!step(&mut app, 0., true));
+
+        assert!(!step(&mut app, HIT_STOP_COOLDOWN, false));
+        assert!(step(&mut app, 0., true));
+    }
+
+    #[test]
+    fn reduce_motion_never_freezes() {
+        let mut app = hit_stop_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .accessibility
+            .reduce_motion = true;
+        assert!(!step(&mut app, 0., true));
+    }
+}
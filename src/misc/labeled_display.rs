@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+// The section holding the value, right after the label
+const VALUE_SECTION: usize = 1;
+
+// A HUD text made of a label followed by a value, e.g. "Score: 120". The marker tells the
+// displays apart, so each can be found and updated on its own
+#[derive(Bundle)]
+pub struct LabeledDisplayBundle<M: Component> {
+    text: TextBundle,
+    name: Name,
+    marker: M, // Used to uniquely identify the display bundle
+}
+
+impl<M: Component> LabeledDisplayBundle<M> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<L: Into<String>, V: Into<String>>(
+        marker: M,
+        name: &'static str,
+        font_size: f32,
+        label_color: Color,
+        value_color: Color,
+        label: L,
+        top_left_placement: Vec2,
+        initial_value: Option<V>,
+    ) -> Self {
+        LabeledDisplayBundle {
+            text: TextBundle::from_sections([
+                // The label
+                TextSection::new(
+                    label,
+                    TextStyle {
+                        font_size,
+                        color: label_color,
+                        ..default()
+                    },
+                ),
+                // The value
+                TextSection::new(
+                    match initial_value {
+                        Some(s) => s.into(),
+                        None => "".to_string(),
+                    },
+                    TextStyle {
+                        font_size,
+                        color: value_color,
+                        ..default()
+                    },
+                ),
+            ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(top_left_placement.y),
+                left: Val::Px(top_left_placement.x),
+                ..default()
+            }),
+            name: Name::new(name),
+            marker,
+        }
+    }
+
+    // Appends another section after the value, for displays showing more than one thing
+    pub fn with_section(mut self, section: TextSection) -> Self {
+        self.text.text.sections.push(section);
+        self
+    }
+}
+
+// A resource shown as the value of a labeled display
+pub trait DisplayedValue: Resource {
+    fn displayed_value(&self) -> String;
+}
+
+// Writes the value of a labeled display, leaving its label and any extra sections alone
+pub fn set_displayed_value(text: &mut Text, value: impl Into<String>) {
+    text.sections[VALUE_SECTION].value = value.into();
+}
+
+// Keeps every display marked with M showing R, whenever R changes or a display is spawned
+pub fn update_labeled_display<R: DisplayedValue, M: Component>(
+    value: Res<R>,
+    mut text_q: Query<(&mut Text, Ref<M>)>,
+) {
+    for (mut text, marker) in text_q.iter_mut() {
+        if value.is_changed() || marker.is_added() {
+            set_displayed_value(&mut text, value.displayed_value());
+        }
+    }
+}
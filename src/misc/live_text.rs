@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+// Shows value in a text that is kept up to date every frame, spawning the text from bundle when it
+// is missing. Restarting the game despawns every entity, so texts like these can't be spawned once
+// and then only updated. The value is only written when it changes, to keep change detection quiet
+pub fn set_or_spawn_text<B: Bundle>(
+    commands: &mut Commands,
+    text: Option<Mut<Text>>,
+    value: String,
+    bundle: impl FnOnce(String) -> B,
+) {
+    match text {
+        Some(mut text) => {
+            if text.sections[0].value != value {
+                text.sections[0].value = value;
+            }
+        }
+        None => {
+            commands.spawn(bundle(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Shown;
+
+    #[derive(Resource)]
+    struct Value(&'static str);
+
+    fn show_value(
+        mut commands: Commands,
+        mut text_q: Query<&mut Text, With<Shown>>,
+        value: Res<Value>,
+    ) {
+        set_or_spawn_text(
+            &mut commands,
+            text_q.get_single_mut().ok(),
+            value.0.to_string(),
+            |value| (TextBundle::from_section(value, default()), Shown),
+        );
+    }
+
+    fn shown(app: &mut App) -> Vec<String> {
+        let mut text_q = app.world.query_filtered::<&Text, With<Shown>>();
+        text_q
+            .iter(&app.world)
+            .map(|text| text.sections[0].value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn the_text_is_spawned_once_then_updated() {
+        let mut app = App::new();
+        app.insert_resource(Value("first"))
+            .add_systems(Update, show_value);
+        app.update();
+        app.update();
+        assert_eq!(shown(&mut app), ["first"]);
+
+        app.insert_resource(Value("second"));
+        app.update();
+        assert_eq!(shown(&mut app), ["second"]);
+    }
+}
@@ -1 +1,11 @@
+pub mod audio;
 pub mod blink;
+pub mod clipboard;
+pub mod hit_stop;
+pub mod labeled_display;
+pub mod live_text;
+pub mod particles;
+pub mod rng;
+pub mod persist;
+#[cfg(test)]
+pub mod test_utils;
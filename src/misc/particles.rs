@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::{breaker::BrickDestroyed, settings::GameSettings};
+
+const PARTICLES_PER_BURST: usize = 8;
+// Bursts are skipped while this many particles are alive, so clearing a dense level all at once
+// can't flood the world with entities
+const MAX_PARTICLES: usize = 200;
+const PARTICLE_SPEED: f32 = 180.;
+const PARTICLE_LIFETIME: f32 = 0.4;
+const PARTICLE_SIZE: Vec3 = Vec3::new(5., 5., 1.);
+// Drawn in front of the bricks
+const PARTICLE_Z: f32 = 1.;
+
+// A purely visual speck flying out of a destroyed brick, fading out over its lifetime
+#[derive(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    lifetime: Timer,
+}
+
+// Bursts a ring of particles in the brick's color from every destroyed brick
+pub fn spawn_particle_bursts(
+    mut commands: Commands,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    particle_q: Query<(), With<Particle>>,
+    settings: Res<GameSettings>,
+) {
+    if settings.accessibility.reduce_motion {
+        destroyed_events.clear();
+        return;
+    }
+    let mut alive = particle_q.iter().count();
+    for destroyed in destroyed_events.iter() {
+        if alive + PARTICLES_PER_BURST > MAX_PARTICLES {
+            continue;
+        }
+        alive += PARTICLES_PER_BURST;
+        for i in 0..PARTICLES_PER_BURST {
+            let angle = std::f32::consts::TAU * i as f32 / PARTICLES_PER_BURST as f32;
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: destroyed.color,
+                        ..default()
+                    },
+                    transform: Transform::from_translation(
+                        destroyed.position.truncate().extend(PARTICLE_Z),
+                    )
+                    .with_scale(PARTICLE_SIZE),
+                    ..default()
+                },
+                Particle {
+                    velocity: Vec2::from_angle(angle) * PARTICLE_SPEED,
+                    lifetime: Timer::from_seconds(PARTICLE_LIFETIME, TimerMode::Once),
+                },
+                Name::new("Particle"),
+            ));
+        }
+    }
+}
+
+pub fn update_particles(
+    mut commands: Commands,
+    mut particle_q: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (ent, mut particle, mut tform, mut sprite) in particle_q.iter_mut() {
+        if particle.lifetime.tick(time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+            continue;
+        }
+        tform.translation += (particle.velocity * time.delta_seconds()).extend(0.);
+        sprite.color.set_a(particle.lifetime.percent_left());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bricks::BrickKind, misc::test_utils::test_app};
+
+    fn burst_count(reduce_motion: bool) -> usize {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .accessibility
+            .reduce_motion = reduce_motion;
+        app.add_event::<BrickDestroyed>()
+            .add_systems(Update, spawn_particle_bursts);
+        app.world.send_event(BrickDestroyed {
+            position: Vec3::ZERO,
+            kind: BrickKind::default(),
+            ball: Entity::PLACEHOLDER,
+            color: Color::WHITE,
+        });
+        app.update();
+        let mut particle_q = app.world.query::<&Particle>();
+        particle_q.iter(&app.world).count()
+    }
+
+    #[test]
+    fn a_broken_brick_bursts_into_particles_unless_motion_is_reduced() {
+        assert_eq!(burst_count(false), PARTICLES_PER_BURST);
+        assert_eq!(burst_count(true), 0);
+    }
+}
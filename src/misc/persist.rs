@@ -0,0 +1,51 @@
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+const CONFIG_DIR_NAME: &str = "brick-hero";
+
+// The location of a file in the platform config dir, e.g. ~/.config/brick-hero/ on Linux
+pub fn config_path(file_name: &str) -> Option<PathBuf> {
+    if cfg!(test) {
+        return None; // Tests never read or write the player's files
+    }
+    dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(file_name))
+}
+
+// Reads a JSON file from the config dir, falling back to the default if it is missing or invalid
+pub fn load_json<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    let Some(path) = config_path(file_name) else {
+        warn!("No config dir available, using default {file_name}");
+        return T::default();
+    };
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return T::default(); // First run, nothing saved yet
+    };
+    match serde_json::from_str(&contents) {
+        Ok(value) => value,
+        Err(e) => {
+            warn!("Ignoring invalid {}: {e}", path.display());
+            T::default()
+        }
+    }
+}
+
+// Writes a value as JSON to the config dir, logging rather than failing since saving is best effort
+pub fn save_json<T: Serialize>(file_name: &str, value: &T) {
+    let Some(path) = config_path(file_name) else {
+        warn!("No config dir available, not saving {file_name}");
+        return;
+    };
+    let result = serde_json::to_string_pretty(value)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            fs::write(&path, json).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Failed to save {}: {e}", path.display());
+    }
+}
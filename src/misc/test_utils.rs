@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{breaker::FIXED_TIME_TICKS_PER_SECOND, misc::rng::GameRng, settings::GameSettings};
+
+// A bare App with the resources most gameplay systems read, for running systems in unit tests.
+// The settings are the defaults rather than the saved ones, and the rng always has the same seed
+pub fn test_app() -> App {
+    let mut app = App::new();
+    app.insert_resource(GameSettings::default())
+        .insert_resource(GameRng::from_seed(0))
+        .insert_resource(FixedTime::new_from_secs(FIXED_TIME_TICKS_PER_SECOND))
+        .init_resource::<Time>()
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<Gamepads>()
+        .init_resource::<Axis<GamepadAxis>>()
+        .init_resource::<Input<GamepadButton>>();
+    app
+}
+
+// Moves the clock forward, so systems in the next update see secs go by
+pub fn advance_time(app: &mut App, secs: f32) {
+    let mut time = app.world.resource_mut::<Time>();
+    let last = match time.last_update() {
+        Some(last) => last,
+        None => {
+            // The first update only starts the clock
+            let startup = time.startup();
+            time.update_with_instant(startup);
+            startup
+        }
+    };
+    time.update_with_instant(last + Duration::from_secs_f32(secs));
+}
+
+// Every event of type E sent since the events were last cleared
+pub fn sent_events<E: Event + Clone>(app: &App) -> Vec<E> {
+    let events = app.world.resource::<Events<E>>();
+    events.get_reader().iter(events).cloned().collect()
+}
@@ -0,0 +1,349 @@
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    breaker::{
+        Ball, BallSpeed, BrickTracker, CurrentState, GameState, GameStateTransition, Velocity,
+    },
+    misc::audio::OptionalSound,
+    settings::GameSettings,
+};
+
+const MENU_TRACK_PATH: &str = "sounds/music_menu.ogg";
+const BASE_STEM_PATH: &str = "sounds/music_base.ogg";
+const INTENSITY_STEM_PATH: &str = "sounds/music_intensity.ogg";
+const DEFAULT_MUSIC_VOLUME: f32 = 0.5;
+const DEFAULT_MASTER_VOLUME: f32 = 1.;
+// At full intensity the intensity stem plays at this share of the music volume, to keep it subtle
+const MAX_INTENSITY_STEM_VOLUME: f32 = 0.8;
+// A ball moving this many times its base speed counts as full intensity
+const MAX_INTENSITY_BALL_SPEED: f32 = 2.;
+// How quickly the stem volumes follow changes in intensity, per second
+const INTENSITY_FADE_RATE: f32 = 1.5;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicIntensity>()
+            .add_systems(Startup, load_background_music)
+            .add_systems(Update, play_menu_music)
+            .add_systems(OnEnter(AppState::InGame), start_music)
+            .add_systems(OnExit(AppState::InGame), stop_music)
+            .add_systems(
+                Update,
+                (
+                    play_game_music,
+                    track_level_bricks,
+                    update_music_stems.after(track_level_bricks),
+                )
+                    .run_if(state_exists_and_equals(AppState::InGame)),
+            );
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub music: bool,
+    // Fades in a more intense layer of the music as the level nears completion or the ball speeds up
+    pub adaptive_music: bool,
+    pub music_volume: f32,
+    // Scales every sound, from 0 for silence to 1 for full volume
+    pub master_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            music: false,
+            adaptive_music: true,
+            music_volume: DEFAULT_MUSIC_VOLUME,
+            master_volume: DEFAULT_MASTER_VOLUME,
+        }
+    }
+}
+
+impl AudioSettings {
+    // A sound's volume once the master volume is applied
+    pub fn with_master(&self, volume: f32) -> f32 {
+        volume * self.master_volume.clamp(0., 1.)
+    }
+}
+
+// Every music track, loaded at startup so switching between the menus and the game never waits.
+// A track that is missing is skipped, the game plays on without it
+#[derive(Resource)]
+pub struct BackgroundMusic {
+    menu: OptionalSound,
+    base: OptionalSound,
+    intensity: OptionalSound,
+}
+
+// Marker for the track looping behind the main menu and game over screen
+#[derive(Component)]
+pub struct MenuMusic;
+
+// The layers of the music, played in sync and mixed by volume
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum MusicStem {
+    Base,
+    Intensity,
+}
+
+impl MusicStem {
+    // The stem's share of the music volume at the given intensity
+    pub fn volume(&self, music_volume: f32, intensity: f32) -> f32 {
+        match self {
+            MusicStem::Base => music_volume,
+            MusicStem::Intensity => music_volume * MAX_INTENSITY_STEM_VOLUME * intensity,
+        }
+    }
+}
+
+// How intense the music currently is from 0 to 1, eased towards the gameplay's intensity
+#[derive(Resource, Default)]
+pub struct MusicIntensity {
+    current: f32,
+    level_bricks: usize, // The most bricks seen since the level started
+}
+
+// The intensity the music should head for, the greater of how much of the level is cleared and
+// how fast the fastest ball is going
+pub fn target_intensity(bricks_left: usize, level_bricks: usize, fastest_ball: f32) -> f32 {
+    let cleared = if level_bricks == 0 {
+        0.
+    } else {
+        1. - bricks_left as f32 / level_bricks as f32
+    };
+    let speed = (fastest_ball - 1.) / (MAX_INTENSITY_BALL_SPEED - 1.);
+    cleared.max(speed).clamp(0., 1.)
+}
+
+pub fn load_background_music(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(BackgroundMusic {
+        menu: OptionalSound::load(&asset_server, MENU_TRACK_PATH),
+        base: OptionalSound::load(&asset_server, BASE_STEM_PATH),
+        intensity: OptionalSound::load(&asset_server, INTENSITY_STEM_PATH),
+    });
+}
+
+// Keeps exactly one menu track playing while outside the game, and none inside it. Checked every
+// frame, so leaving for the game stops it and coming back never stacks a second copy
+pub fn play_menu_music(
+    mut commands: Commands,
+    track_q: Query<(Entity, Option<&AudioSink>), With<MenuMusic>>,
+    music: Option<ResMut<BackgroundMusic>>,
+    asset_server: Res<AssetServer>,
+    app_state: Res<State<AppState>>,
+    settings: Res<GameSettings>,
+) {
+    let in_menu = matches!(app_state.get(), AppState::MainMenu | AppState::GameOver);
+    let volume = settings.audio.with_master(settings.audio.music_volume);
+    if !in_menu || !settings.audio.music {
+        for (ent, _) in track_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+        return;
+    }
+    if track_q.is_empty() {
+        let Some(track) = music.and_then(|mut music| music.menu.playable(&asset_server)) else {
+            return;
+        };
+        commands.spawn((
+            AudioBundle {
+                source: track,
+                settings: PlaybackSettings::LOOP.with_volume(Volume::new_relative(volume)),
+            },
+            MenuMusic,
+            Name::new("MenuMusic"),
+        ));
+    }
+    // Follows the volume settings while they are adjusted
+    for sink in track_q.iter().filter_map(|(_, sink)| sink) {
+        sink.set_volume(volume);
+    }
+}
+
+// Every run's music starts calm
+pub fn start_music(mut intensity: ResMut<MusicIntensity>) {
+    *intensity = MusicIntensity::default();
+}
+
+// Keeps a stem playing for each track through the game, starting each as soon as its track has
+// loaded. A stem whose track is missing never starts
+pub fn play_game_music(
+    mut commands: Commands,
+    stem_q: Query<&MusicStem>,
+    music: Option<ResMut<BackgroundMusic>>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    let Some(mut music) = music else {
+        return;
+    };
+    if !settings.audio.music {
+        return;
+    }
+    let volume = settings.audio.with_master(settings.audio.music_volume);
+    let music = &mut *music;
+    for (stem, track, stem_volume) in [
+        (MusicStem::Base, &mut music.base, volume),
+        (MusicStem::Intensity, &mut music.intensity, 0.),
+    ] {
+        if stem_q.iter().any(|playing| *playing == stem) {
+            continue;
+        }
+        let Some(track) = track.playable(&asset_server) else {
+            continue;
+        };
+        commands.spawn((
+            AudioBundle {
+                source: track,
+                settings: PlaybackSettings::LOOP.with_volume(Volume::new_relative(stem_volume)),
+            },
+            stem,
+            Name::new("MusicStem"),
+        ));
+    }
+}
+
+// Every stem goes when leaving the game, whether to the main menu or the game over screen
+pub fn stop_music(mut commands: Commands, stem_q: Query<Entity, With<MusicStem>>) {
+    for ent in stem_q.iter() {
+        commands.entity(ent).despawn_recursive();
+    }
+}
+
+// Notes the brick count each level starts with, so intensity can follow how much is cleared
+pub fn track_level_bricks(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut intensity: ResMut<MusicIntensity>,
+    brick_tracker: Res<BrickTracker>,
+) {
+    for msg in game_msgs.iter() {
+        if matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::RestartGame
+        ) {
+            intensity.level_bricks = 0;
+        }
+    }
+    intensity.level_bricks = intensity.level_bricks.max(**brick_tracker);
+}
+
+// Mixes the stems for the current intensity, and holds them while the game is paused
+pub fn update_music_stems(
+    mut intensity: ResMut<MusicIntensity>,
+    stem_q: Query<(&MusicStem, &AudioSink)>,
+    ball_q: Query<&Velocity, With<Ball>>,
+    brick_tracker: Res<BrickTracker>,
+    ball_speed: Res<BallSpeed>,
+    game_state: Res<CurrentState>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let paused = **game_state == GameState::Paused;
+    for (_, sink) in stem_q.iter() {
+        if paused {
+            sink.pause();
+        } else if sink.is_paused() {
+            sink.play();
+        }
+    }
+    if paused {
+        return;
+    }
+
+    let target = if settings.audio.adaptive_music {
+        let fastest_ball =
+            ball_q.iter().map(|v| v.length()).fold(0., f32::max) * ball_speed.multiple();
+        target_intensity(**brick_tracker, intensity.level_bricks, fastest_ball)
+    } else {
+        0.
+    };
+    let step = INTENSITY_FADE_RATE * time.delta_seconds();
+    intensity.current += (target - intensity.current).clamp(-step, step);
+
+    let volume = settings.audio.with_master(settings.audio.music_volume);
+    for (stem, sink) in stem_q.iter() {
+        sink.set_volume(stem.volume(volume, intensity.current));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use bevy::asset::LoadState;
+
+    use super::*;
+    use crate::misc::test_utils::{advance_time, test_app};
+
+    fn intensity_stem_volume(app: &App) -> f32 {
+        let intensity = app.world.resource::<MusicIntensity>().current;
+        MusicStem::Intensity.volume(DEFAULT_MUSIC_VOLUME, intensity)
+    }
+
+    #[test]
+    fn a_ball_at_the_base_speed_adds_no_intensity() {
+        assert_eq!(target_intensity(10, 10, 1.), 0.);
+        assert_eq!(target_intensity(10, 10, MAX_INTENSITY_BALL_SPEED), 1.);
+    }
+
+    #[test]
+    fn clearing_bricks_raises_the_intensity_stem() {
+        let mut app = test_app();
+        app.init_resource::<MusicIntensity>()
+            .insert_resource(BrickTracker(10))
+            .insert_resource(BallSpeed::new(1, 1.))
+            .insert_resource(CurrentState(GameState::Playing))
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (
+                    track_level_bricks,
+                    update_music_stems.after(track_level_bricks),
+                ),
+            );
+        app.update();
+        advance_time(&mut app, 0.5);
+        app.update();
+        assert_eq!(intensity_stem_volume(&app), 0.);
+
+        // Most of the level cleared, the stem fades in rather than jumping to full volume
+        **app.world.resource_mut::<BrickTracker>() = 2;
+        advance_time(&mut app, 0.1);
+        app.update();
+        let fading_in = intensity_stem_volume(&app);
+        assert!(fading_in > 0.);
+        advance_time(&mut app, 1.);
+        app.update();
+        assert!(intensity_stem_volume(&app) > fading_in);
+    }
+
+    #[test]
+    fn missing_tracks_are_skipped() {
+        let mut app = test_app();
+        app.world.resource_mut::<GameSettings>().audio.music = true;
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .add_systems(Startup, load_background_music)
+            .add_systems(Update, play_game_music);
+        app.update();
+        // Loading happens on the IO task pool
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        for path in [BASE_STEM_PATH, INTENSITY_STEM_PATH] {
+            while asset_server.get_load_state(path) != LoadState::Failed {
+                assert!(Instant::now() < deadline, "{path} never failed to load");
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        app.update();
+        let mut stem_q = app.world.query::<&MusicStem>();
+        assert_eq!(stem_q.iter(&app.world).count(), 0);
+    }
+}
@@ -0,0 +1,603 @@
+use std::{
+    io::ErrorKind,
+    net::{SocketAddr, UdpSocket},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    breaker::{
+        bound_paddle_x, game_running, paddle_sprite, update_paddle_momentum, Ball, BallSpawner,
+        BrickTracker, Collider, Level, Paddle, PaddleMomentum, Velocity,
+    },
+    bricks::Brick,
+    health::Health,
+    scoreboard::Scoreboard,
+    settings::GameSettings,
+    theme::ThemeColors,
+};
+
+const DEFAULT_PORT: u16 = 7777;
+// Snapshots and inputs are sent at this rate rather than every frame
+const NET_SEND_INTERVAL: f32 = 1. / 30.;
+// Going this long without hearing from the other player counts as a disconnect
+const DISCONNECT_TIMEOUT: f32 = 3.;
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+// Bricks never move, so a brick in a snapshot matches a local brick within this distance
+const BRICK_MATCH_DISTANCE: f32 = 1.;
+
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(NetConfig::from_args())
+            .init_resource::<RemoteMomentum>()
+            .init_resource::<LatestSnapshot>()
+            .add_systems(Startup, open_socket)
+            // Both sides keep talking while paused, so a pause isn't mistaken for a disconnect
+            .add_systems(
+                Update,
+                (host_receive, host_broadcast.after(host_receive))
+                    .run_if(is_host)
+                    .run_if(state_exists_and_equals(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    client_receive,
+                    client_send_input,
+                    apply_snapshot.after(client_receive).run_if(game_running),
+                )
+                    .run_if(is_client)
+                    .run_if(state_exists_and_equals(AppState::InGame)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    move_remote_paddle.run_if(is_host),
+                    // The client still reads its own keys, the host moves the paddle it sees
+                    update_paddle_momentum.run_if(is_client),
+                )
+                    .run_if(game_running),
+            );
+    }
+}
+
+// The co-op role of this instance, chosen on the command line with `--host [port]` or
+// `--join <ip:port>`. Without either flag the game is single player
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NetConfig {
+    #[default]
+    Offline,
+    Host {
+        port: u16,
+    },
+    Client {
+        host: SocketAddr,
+    },
+}
+
+impl NetConfig {
+    pub fn from_args() -> Self {
+        NetConfig::parse_args(std::env::args().collect())
+    }
+
+    fn parse_args(args: Vec<String>) -> Self {
+        for (i, arg) in args.iter().enumerate() {
+            let value = args.get(i + 1);
+            match arg.as_str() {
+                "--host" => {
+                    let port = value.and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_PORT);
+                    return NetConfig::Host { port };
+                }
+                "--join" => match value.map(|v| v.parse()) {
+                    Some(Ok(host)) => return NetConfig::Client { host },
+                    _ => warn!("--join needs a host address like 192.168.0.2:{DEFAULT_PORT}"),
+                },
+                _ => {}
+            }
+        }
+        NetConfig::Offline
+    }
+}
+
+// Run condition for systems that only the host runs
+pub fn is_host(config: Res<NetConfig>) -> bool {
+    matches!(*config, NetConfig::Host { .. })
+}
+
+// Run condition for systems that only the client runs
+pub fn is_client(config: Res<NetConfig>) -> bool {
+    matches!(*config, NetConfig::Client { .. })
+}
+
+// Run condition for the physics, a client only renders the state the host sends it
+pub fn simulates_locally(config: Res<NetConfig>) -> bool {
+    !matches!(*config, NetConfig::Client { .. })
+}
+
+// Marks the paddle controlled by the other player, on the host it collides like any other paddle
+#[derive(Component)]
+pub struct RemotePaddle;
+
+// The other player's paddle momentum. The host moves the remote paddle with the momentum the
+// client sends, and on either side a ball hitting the remote paddle is steered by it
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RemoteMomentum(f32);
+
+// The most recent state received from the host, waiting to be applied
+#[derive(Resource, Default)]
+struct LatestSnapshot(Option<Snapshot>);
+
+#[derive(Resource)]
+struct NetSocket {
+    socket: UdpSocket,
+    peer: Option<SocketAddr>,
+    // Whether the peer has been heard from yet. Silence only counts from then on, so a client
+    // waiting on a host that hasn't started yet isn't dropped
+    heard: bool,
+    silence: f32, // Seconds since the peer was last heard from
+    send_timer: Timer,
+}
+
+impl NetSocket {
+    fn new(socket: UdpSocket, peer: Option<SocketAddr>) -> Self {
+        NetSocket {
+            socket,
+            peer,
+            heard: false,
+            silence: 0.,
+            send_timer: Timer::from_seconds(NET_SEND_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    fn heard_from(&mut self) {
+        self.heard = true;
+        self.silence = 0.;
+    }
+
+    fn tick_silence(&mut self, delta: f32) {
+        if self.heard {
+            self.silence += delta;
+        }
+    }
+
+    // Whether the peer has gone quiet for too long since it was last heard from
+    fn timed_out(&self) -> bool {
+        self.silence > DISCONNECT_TIMEOUT
+    }
+
+    fn send(&self, msg: &NetMessage) {
+        let Some(peer) = self.peer else {
+            return;
+        };
+        match serde_json::to_vec(msg) {
+            Ok(bytes) => {
+                if let Err(e) = self.socket.send_to(&bytes, peer) {
+                    warn!("Failed to send to {peer}: {e}");
+                }
+            }
+            Err(e) => warn!("Failed to encode net message: {e}"),
+        }
+    }
+
+    // Reads every datagram waiting on the socket without blocking
+    fn receive(&mut self) -> Vec<(NetMessage, SocketAddr)> {
+        let mut received = vec![];
+        let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, addr)) => match serde_json::from_slice(&buf[..len]) {
+                    Ok(msg) => received.push((msg, addr)),
+                    Err(e) => warn!("Ignoring bad datagram from {addr}: {e}"),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    warn!("Socket error: {e}");
+                    break;
+                }
+            }
+        }
+        received
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum NetMessage {
+    Input { momentum: f32 }, // Client -> Host
+    State(Snapshot),         // Host -> Client
+}
+
+// Everything the client needs to draw the host's game
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    host_paddle_x: f32,
+    host_paddle_momentum: f32,
+    remote_paddle_x: f32,
+    balls: Vec<BallState>,
+    bricks: Vec<BrickState>,
+    score: usize,
+    health: usize,
+    level: usize,
+}
+
+// Velocities are included so play can continue locally if the host disconnects
+#[derive(Serialize, Deserialize)]
+struct BallState {
+    position: [f32; 2],
+    velocity: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize)]
+struct BrickState {
+    position: [f32; 2],
+    strength: u8,
+}
+
+// Binds the UDP socket for the chosen role, falling back to single player if that fails
+fn open_socket(mut commands: Commands, mut config: ResMut<NetConfig>) {
+    let (bind_addr, peer) = match *config {
+        NetConfig::Offline => return,
+        NetConfig::Host { port } => (SocketAddr::from(([0, 0, 0, 0], port)), None),
+        NetConfig::Client { host } => (SocketAddr::from(([0, 0, 0, 0], 0)), Some(host)),
+    };
+    let socket = UdpSocket::bind(bind_addr).and_then(|socket| {
+        socket.set_nonblocking(true)?;
+        Ok(socket)
+    });
+    match socket {
+        Ok(socket) => {
+            info!("Co-op {:?} listening on {bind_addr}", *config);
+            commands.insert_resource(NetSocket::new(socket, peer));
+        }
+        Err(e) => {
+            warn!("Failed to open co-op socket on {bind_addr}, playing single player: {e}");
+            *config = NetConfig::Offline;
+        }
+    }
+}
+
+// Returns to single player, removing the other player's paddle
+fn go_offline(commands: &mut Commands, remote_q: &Query<Entity, With<RemotePaddle>>) {
+    for paddle in remote_q.iter() {
+        commands.entity(paddle).despawn_recursive();
+    }
+}
+
+// Accepts the first client to send input, spawning their paddle, and drops them if they go quiet
+fn host_receive(
+    mut commands: Commands,
+    socket: Option<ResMut<NetSocket>>,
+    mut remote_momentum: ResMut<RemoteMomentum>,
+    remote_q: Query<Entity, With<RemotePaddle>>,
+    theme: Res<ThemeColors>,
+    time: Res<Time>,
+) {
+    let Some(mut socket) = socket else {
+        return;
+    };
+    socket.tick_silence(time.delta_seconds());
+    for (msg, addr) in socket.receive() {
+        let NetMessage::Input { momentum } = msg else {
+            continue;
+        };
+        if socket.peer.is_some_and(|peer| peer != addr) {
+            continue; // Co-op is limited to two players
+        }
+        if socket.peer.is_none() {
+            info!("Co-op partner joined from {addr}");
+        }
+        socket.peer = Some(addr);
+        socket.heard_from();
+        **remote_momentum = momentum;
+    }
+
+    if socket.peer.is_some() && socket.timed_out() {
+        info!("Co-op partner disconnected, continuing single player");
+        socket.peer = None;
+        socket.heard = false;
+        socket.silence = 0.;
+        **remote_momentum = 0.;
+        go_offline(&mut commands, &remote_q);
+    } else if socket.peer.is_some() && remote_q.is_empty() {
+        // Also covers restarts, which despawn every entity
+        commands.spawn((
+            paddle_sprite(theme.paddle.with_a(0.6)),
+            Paddle,
+            RemotePaddle,
+            Collider,
+            Name::new("RemotePaddle"),
+        ));
+    }
+}
+
+// Moves the client's paddle on the host, the same way the local paddle moves in the fixed timing
+fn move_remote_paddle(
+    mut remote_q: Query<&mut Transform, With<RemotePaddle>>,
+    remote_momentum: Res<RemoteMomentum>,
+    settings: Res<GameSettings>,
+) {
+    for mut tform in remote_q.iter_mut() {
+        let x = tform.translation.x + **remote_momentum;
+        tform.translation.x = bound_paddle_x(x, tform.scale.x, settings.gameplay.paddle_edge);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn host_broadcast(
+    socket: Option<ResMut<NetSocket>>,
+    local_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    remote_q: Query<&Transform, With<RemotePaddle>>,
+    ball_q: Query<(&Transform, &Velocity), With<Ball>>,
+    brick_q: Query<(&Transform, &Brick)>,
+    momentum: Res<PaddleMomentum>,
+    scoreboard: Res<Scoreboard>,
+    health: Res<Health>,
+    level: Res<Level>,
+    time: Res<Time>,
+) {
+    let Some(mut socket) = socket else {
+        return;
+    };
+    if !socket.send_timer.tick(time.delta()).just_finished() || socket.peer.is_none() {
+        return;
+    }
+    let paddle_x = |tform: Option<&Transform>| tform.map_or(0., |t| t.translation.x);
+    let snapshot = Snapshot {
+        host_paddle_x: paddle_x(local_q.iter().next()),
+        host_paddle_momentum: **momentum,
+        remote_paddle_x: paddle_x(remote_q.iter().next()),
+        balls: ball_q
+            .iter()
+            .map(|(t, v)| BallState {
+                position: t.translation.truncate().to_array(),
+                velocity: v.to_array(),
+            })
+            .collect(),
+        bricks: brick_q
+            .iter()
+            .map(|(t, brick)| BrickState {
+                position: t.translation.truncate().to_array(),
+                strength: **brick,
+            })
+            .collect(),
+        score: scoreboard.score,
+        health: **health,
+        level: **level,
+    };
+    socket.send(&NetMessage::State(snapshot));
+}
+
+// Sends the local paddle momentum to the host
+fn client_send_input(
+    socket: Option<ResMut<NetSocket>>,
+    momentum: Res<PaddleMomentum>,
+    time: Res<Time>,
+) {
+    let Some(mut socket) = socket else {
+        return;
+    };
+    if socket.send_timer.tick(time.delta()).just_finished() {
+        socket.send(&NetMessage::Input {
+            momentum: **momentum,
+        });
+    }
+}
+
+// Keeps the newest snapshot from the host, reverting to single player if the host goes quiet
+fn client_receive(
+    mut commands: Commands,
+    socket: Option<ResMut<NetSocket>>,
+    mut latest: ResMut<LatestSnapshot>,
+    mut config: ResMut<NetConfig>,
+    remote_q: Query<Entity, With<RemotePaddle>>,
+    time: Res<Time>,
+) {
+    let Some(mut socket) = socket else {
+        return;
+    };
+    socket.tick_silence(time.delta_seconds());
+    for (msg, addr) in socket.receive() {
+        if Some(addr) != socket.peer {
+            continue;
+        }
+        if let NetMessage::State(snapshot) = msg {
+            socket.heard_from();
+            latest.0 = Some(snapshot);
+        }
+    }
+
+    if socket.timed_out() {
+        // The local state is whatever was last received, so play simply continues from there
+        info!("Lost connection to the co-op host, continuing single player");
+        *config = NetConfig::Offline;
+        latest.0 = None;
+        go_offline(&mut commands, &remote_q);
+    }
+}
+
+// Mirrors the host's game: paddles, balls, brick strengths, score, and health
+#[allow(clippy::too_many_arguments)]
+fn apply_snapshot(
+    mut commands: Commands,
+    mut balls: BallSpawner,
+    mut latest: ResMut<LatestSnapshot>,
+    mut local_q: Query<&mut Transform, (With<Paddle>, Without<RemotePaddle>, Without<Ball>)>,
+    mut remote_q: Query<&mut Transform, (With<RemotePaddle>, Without<Ball>)>,
+    mut ball_q: Query<(Entity, &mut Transform, &mut Velocity), (With<Ball>, Without<Paddle>)>,
+    mut brick_q: Query<
+        (Entity, &Transform, &mut Brick, &mut Sprite),
+        (Without<Paddle>, Without<Ball>),
+    >,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut health: ResMut<Health>,
+    mut brick_tracker: ResMut<BrickTracker>,
+    mut remote_momentum: ResMut<RemoteMomentum>,
+    level: Res<Level>,
+    theme: Res<ThemeColors>,
+    settings: Res<GameSettings>,
+) {
+    let Some(snapshot) = latest.0.take() else {
+        return;
+    };
+
+    // The host's remote paddle is this player's own paddle
+    for mut tform in local_q.iter_mut() {
+        tform.translation.x = snapshot.remote_paddle_x;
+    }
+    if remote_q.is_empty() {
+        commands.spawn((
+            paddle_sprite(theme.paddle.with_a(0.6)),
+            Paddle,
+            RemotePaddle,
+            Name::new("RemotePaddle"),
+        ));
+    }
+    for mut tform in remote_q.iter_mut() {
+        tform.translation.x = snapshot.host_paddle_x;
+    }
+    **remote_momentum = snapshot.host_paddle_momentum;
+
+    // Match the ball count, then positions
+    let mut in_play = ball_q.iter().count().min(snapshot.balls.len());
+    let mut remote_balls = snapshot.balls.iter();
+    for (ball, mut tform, mut velocity) in ball_q.iter_mut() {
+        match remote_balls.next() {
+            Some(state) => {
+                tform.translation = Vec2::from_array(state.position).extend(tform.translation.z);
+                **velocity = Vec2::from_array(state.velocity);
+            }
+            None => commands.entity(ball).despawn_recursive(),
+        }
+    }
+    for state in remote_balls {
+        balls.spawn_ball(
+            &mut commands,
+            in_play,
+            theme.ball,
+            Vec2::from_array(state.position).extend(1.),
+            Vec2::from_array(state.velocity),
+        );
+        in_play += 1;
+    }
+
+    // Bricks missing from the snapshot were destroyed on the host
+    for (ent, tform, mut brick, mut sprite) in brick_q.iter_mut() {
+        let position = tform.translation.truncate();
+        let remote = snapshot
+            .bricks
+            .iter()
+            .find(|b| Vec2::from_array(b.position).distance(position) < BRICK_MATCH_DISTANCE);
+        match remote {
+            Some(remote) if remote.strength > 0 => {
+                if **brick != remote.strength {
+                    **brick = remote.strength;
+                    sprite.color = settings.visual.brick_palette.brick_color(remote.strength);
+                }
+            }
+            _ => commands.entity(ent).despawn_recursive(),
+        }
+    }
+    // Once the host moves on, an empty tracker makes this side load the same next level
+    **brick_tracker = if snapshot.level > **level {
+        0
+    } else {
+        snapshot.bricks.len()
+    };
+
+    scoreboard.score = snapshot.score;
+    **health = snapshot.health;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{advance_time, test_app};
+
+    fn parse(args: &[&str]) -> NetConfig {
+        let args = ["brick-hero"].iter().chain(args);
+        NetConfig::parse_args(args.map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn the_command_line_picks_the_co_op_role() {
+        assert_eq!(parse(&[]), NetConfig::Offline);
+        assert_eq!(parse(&["--host"]), NetConfig::Host { port: DEFAULT_PORT });
+        assert_eq!(parse(&["--host", "9000"]), NetConfig::Host { port: 9000 });
+        let host = "192.168.0.2:7777".parse().unwrap();
+        assert_eq!(
+            parse(&["--join", "192.168.0.2:7777"]),
+            NetConfig::Client { host }
+        );
+        // A join without a usable address stays single player
+        assert_eq!(parse(&["--join"]), NetConfig::Offline);
+        assert_eq!(parse(&["--join", "nowhere"]), NetConfig::Offline);
+    }
+
+    fn local_socket() -> UdpSocket {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_nonblocking(true).unwrap();
+        socket
+    }
+
+    // A client connected to a host that sends one snapshot, then goes quiet
+    fn client_app(host: &UdpSocket) -> App {
+        let client = local_socket();
+        let host_addr = host.local_addr().unwrap();
+        let mut app = test_app();
+        app.insert_resource(NetConfig::Client { host: host_addr })
+            .insert_resource(NetSocket::new(client, Some(host_addr)))
+            .init_resource::<LatestSnapshot>()
+            .add_systems(Update, client_receive);
+        advance_time(&mut app, 0.);
+        app.update();
+        app
+    }
+
+    fn send_snapshot(host: &UdpSocket, app: &App) {
+        let snapshot = NetMessage::State(Snapshot {
+            host_paddle_x: 0.,
+            host_paddle_momentum: 0.,
+            remote_paddle_x: 0.,
+            balls: vec![],
+            bricks: vec![],
+            score: 0,
+            health: 1,
+            level: 1,
+        });
+        let client = &app.world.resource::<NetSocket>().socket;
+        let client_addr = client.local_addr().unwrap();
+        host.send_to(&serde_json::to_vec(&snapshot).unwrap(), client_addr)
+            .unwrap();
+        // Loopback datagrams arrive right away, but give them a moment to be safe
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    fn is_online(app: &App) -> bool {
+        *app.world.resource::<NetConfig>() != NetConfig::Offline
+    }
+
+    #[test]
+    fn a_client_goes_offline_only_once_a_heard_host_goes_quiet() {
+        let host = local_socket();
+        let mut app = client_app(&host);
+        // The host hasn't been heard from yet, so it may still be starting up
+        advance_time(&mut app, DISCONNECT_TIMEOUT * 2.);
+        app.update();
+        assert!(is_online(&app));
+
+        send_snapshot(&host, &app);
+        app.update();
+        assert!(app.world.resource::<LatestSnapshot>().0.is_some());
+        advance_time(&mut app, DISCONNECT_TIMEOUT / 2.);
+        app.update();
+        assert!(is_online(&app));
+
+        advance_time(&mut app, DISCONNECT_TIMEOUT);
+        app.update();
+        assert!(!is_online(&app));
+        assert!(app.world.resource::<LatestSnapshot>().0.is_none());
+    }
+}
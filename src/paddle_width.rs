@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{GameStateTransition, Paddle, PADDLE_SIZE},
+    net::RemotePaddle,
+    settings::GameSettings,
+};
+
+// How many pixels of width the paddle loses each second while the decay modifier is on
+const PADDLE_DECAY_RATE: f32 = 2.;
+// Decay stops once the paddle is this narrow
+const MIN_PADDLE_WIDTH: f32 = 50.;
+// The wide and narrow paddle power-ups change the width by this much, within the min and max
+const WIDE_PADDLE_BONUS: f32 = 30.;
+const NARROW_PADDLE_PENALTY: f32 = 40.;
+const MAX_PADDLE_WIDTH: f32 = 180.;
+// How long a size power-up lasts before the paddle goes back to its usual width
+const RESIZE_DURATION: f32 = 10.;
+
+// The local paddle's width, starting each level at its full size
+#[derive(Resource)]
+pub struct PaddleWidth {
+    full: f32,   // The width each level starts at, set by the difficulty
+    base: f32,   // The full width, less any decay
+    resize: f32, // Added by the last size power-up until it wears off, negative when narrowed
+    resize_timer: Timer,
+}
+
+impl Default for PaddleWidth {
+    fn default() -> Self {
+        PaddleWidth {
+            full: PADDLE_SIZE.x,
+            base: PADDLE_SIZE.x,
+            resize: 0.,
+            resize_timer: Timer::from_seconds(RESIZE_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+impl PaddleWidth {
+    pub fn with_full_width(full: f32) -> Self {
+        PaddleWidth {
+            full,
+            base: full,
+            ..default()
+        }
+    }
+
+    pub fn current(&self) -> f32 {
+        (self.base + self.resize).clamp(MIN_PADDLE_WIDTH, MAX_PADDLE_WIDTH)
+    }
+
+    // Counteracts decay first, so a shrunken paddle gets its width back before growing past full
+    pub fn widen(&mut self) {
+        self.base = self.full;
+        self.resize(WIDE_PADDLE_BONUS);
+    }
+
+    pub fn narrow(&mut self) {
+        self.resize(-NARROW_PADDLE_PENALTY);
+    }
+
+    // Replaces any size power-up still running, restarting the timer
+    fn resize(&mut self, change: f32) {
+        self.resize = change;
+        self.resize_timer.reset();
+    }
+}
+
+// Every level starts with a full width paddle, whether it is the next one or a restart
+pub fn reset_paddle_width(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut width: ResMut<PaddleWidth>,
+) {
+    for msg in game_msgs.iter() {
+        if matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::RestartGame
+        ) {
+            *width = PaddleWidth::with_full_width(width.full);
+        }
+    }
+}
+
+// Shrinks the paddle over time when the decay modifier is on, wears off size power-ups, and sizes
+// the local paddle to match. Collisions and the wall bounds both read the paddle's scale, so they
+// follow along
+pub fn update_paddle_width(
+    mut width: ResMut<PaddleWidth>,
+    mut paddle_q: Query<&mut Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    settings: Res<GameSettings>,
+    time_step: Res<FixedTime>,
+) {
+    if settings.gameplay.paddle_decay && width.base > MIN_PADDLE_WIDTH {
+        let decayed = width.base - PADDLE_DECAY_RATE * time_step.period.as_secs_f32();
+        width.base = decayed.max(MIN_PADDLE_WIDTH);
+    }
+    if width.resize != 0. && width.resize_timer.tick(time_step.period).finished() {
+        width.resize = 0.;
+    }
+    for mut tform in paddle_q.iter_mut() {
+        tform.scale.x = width.current();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::test_app;
+
+    fn paddle_width_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<PaddleWidth>()
+            .add_event::<GameStateTransition>()
+            .add_systems(
+                Update,
+                (
+                    reset_paddle_width,
+                    update_paddle_width.after(reset_paddle_width),
+                ),
+            );
+        app.world
+            .resource_mut::<GameSettings>()
+            .gameplay
+            .paddle_decay = true;
+        app.world
+            .spawn((Transform::from_scale(PADDLE_SIZE), Paddle));
+        app
+    }
+
+    fn paddle_width(app: &mut App) -> f32 {
+        let mut paddle_q = app.world.query_filtered::<&Transform, With<Paddle>>();
+        paddle_q.single(&app.world).scale.x
+    }
+
+    #[test]
+    fn the_paddle_shrinks_until_the_next_level() {
+        let mut app = paddle_width_app();
+        app.update();
+        let first = paddle_width(&mut app);
+        assert!(first < PADDLE_SIZE.x);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(paddle_width(&mut app) < first);
+
+        app.world.send_event(GameStateTransition::NextLevel);
+        app.update();
+        // Back to full, less the one tick of decay that ran after the reset
+        let decay_tick = PADDLE_DECAY_RATE * app.world.resource::<FixedTime>().period.as_secs_f32();
+        assert!((paddle_width(&mut app) - (PADDLE_SIZE.x - decay_tick)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn decay_stops_at_the_min_width() {
+        let mut app = paddle_width_app();
+        for _ in 0..5_000 {
+            app.update();
+        }
+        assert_eq!(paddle_width(&mut app), MIN_PADDLE_WIDTH);
+    }
+}
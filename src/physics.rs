@@ -0,0 +1,254 @@
+use core::f32::consts::PI;
+
+use bevy::{prelude::*, sprite::collide_aabb::Collision};
+use rand::Rng;
+
+use crate::{
+    breaker::{
+        enforce_min_vertical, PaddleMomentum, Velocity, MIN_BALL_VERTICAL, PADDLE_MAX_INFLUENCE,
+        PADDLE_MAX_MOMENTUM,
+    },
+    misc::rng::GameRng,
+};
+
+// Bounce jitter from the settings is capped at this many degrees either way
+pub const MAX_BOUNCE_JITTER_DEGREES: f32 = 15.;
+
+// Changes ball_v based on where along the paddle's width the ball struck it. Hits towards either
+// edge turn the ball further that way, a hit in the middle leaves the reflection as it was
+// NOT A SYSTEM
+pub fn ball_influence_edges(
+    collision: Collision,
+    ball_v: &mut Velocity,
+    ball_t: &Transform,
+    paddle_t: &Transform,
+) {
+    let half_width = paddle_t.scale.x / 2.;
+    let offset = ((ball_t.translation.x - paddle_t.translation.x) / half_width).clamp(-1., 1.);
+    steer_off_paddle(collision, ball_v, PADDLE_MAX_INFLUENCE * offset);
+}
+
+// Changes ball_v based on the momentum of the paddle at the time of collision
+// NOT A SYSTEM
+pub fn ball_influence_momentum(
+    collision: Collision,
+    ball_v: &mut Velocity,
+    paddle_momentum: &PaddleMomentum,
+) {
+    // Convert the current momentum into a [-1, 1] range and scale by max influence
+    let momentum = (**paddle_momentum / PADDLE_MAX_MOMENTUM).clamp(-1., 1.);
+    steer_off_paddle(collision, ball_v, PADDLE_MAX_INFLUENCE * momentum);
+}
+
+// Turns an already reflected ball by influence radians towards +x, measured from straight off the
+// paddle face it hit. The speed is preserved and the ball never ends up flatter than allowed
+fn steer_off_paddle(collision: Collision, ball_v: &mut Velocity, influence: f32) {
+    let away = match collision {
+        Collision::Top => 1.,
+        Collision::Bottom => -1.,
+        _ => return, // Only the paddle's faces steer the ball
+    };
+    let magnitude = ball_v.length(); // Preserve momentum by tracking magnitude
+    let reflected_angle = ball_v.x.atan2(ball_v.y * away);
+    // Past this angle the ball would be flatter than MIN_BALL_VERTICAL allows
+    let max_angle = MIN_BALL_VERTICAL.acos();
+    let desired_angle = (reflected_angle + influence).clamp(-max_angle, max_angle);
+    ball_v.0 = Vec2::new(desired_angle.sin(), desired_angle.cos() * away) * magnitude;
+}
+
+// Adjusts the ball velocity as a result of the collision type
+pub fn ball_ricochet(collision: Collision, ball_v: &mut Velocity) {
+    let reflect_x: bool;
+    let reflect_y: bool;
+    match collision {
+        Collision::Left => {
+            reflect_x = ball_v.x > 0.;
+            reflect_y = false
+        }
+        Collision::Right => {
+            reflect_x = ball_v.x < 0.;
+            reflect_y = false
+        }
+        Collision::Top => {
+            reflect_y = ball_v.y < 0.;
+            reflect_x = false
+        }
+        Collision::Bottom => {
+            reflect_y = ball_v.y > 0.;
+            reflect_x = false
+        }
+        Collision::Inside => {
+            reflect_x = false;
+            reflect_y = false
+        }
+    }
+    if reflect_x {
+        ball_v.x *= -1.;
+    }
+    if reflect_y {
+        ball_v.y *= -1.;
+    }
+}
+
+// Turns a ball that just bounced by a random angle of up to max_degrees either way. The turn never
+// flips the ball's direction on either axis or leaves it in a near horizontal path
+pub fn jitter_bounce(ball_v: &mut Velocity, max_degrees: f32, rng: &mut GameRng) {
+    if max_degrees <= 0. {
+        return; // Bounces stay perfect mirrors
+    }
+    let max_angle = max_degrees.min(MAX_BOUNCE_JITTER_DEGREES).to_radians();
+    let turned = Vec2::from_angle(rng.gen_range(-max_angle..=max_angle)).rotate(ball_v.0);
+    if turned.x * ball_v.x < 0. || turned.y * ball_v.y < 0. {
+        return; // Keep the plain reflection rather than send the ball back the way it came
+    }
+    ball_v.0 = turned;
+    enforce_min_vertical(ball_v);
+}
+
+// The normal pointing out of the side of an AABB collider the ball hit, ball_ricochet reflects
+// about this. The ball is still inside the collider when there is none
+pub fn side_normal(collision: Collision) -> Vec2 {
+    match collision {
+        Collision::Left => Vec2::NEG_X,
+        Collision::Right => Vec2::X,
+        Collision::Top => Vec2::Y,
+        Collision::Bottom => Vec2::NEG_Y,
+        Collision::Inside => Vec2::ZERO,
+    }
+}
+
+// The area where two AABBs overlap, 0 if they don't
+pub fn overlap_area(a_pos: Vec2, a_size: Vec2, b_pos: Vec2, b_size: Vec2) -> f32 {
+    let overlap = (a_size + b_size) / 2. - (a_pos - b_pos).abs();
+    overlap.max(Vec2::ZERO).x * overlap.max(Vec2::ZERO).y
+}
+
+// The point on a collider's AABB closest to the ball's center, used as the contact point
+pub fn aabb_contact_point(ball_pos: Vec2, collider_pos: Vec2, collider_size: Vec2) -> Vec2 {
+    let half_size = collider_size / 2.;
+    ball_pos.clamp(collider_pos - half_size, collider_pos + half_size)
+}
+
+// Finds the normal pointing from a circular collider towards the ball, if the two overlap
+pub fn circle_contact_normal(
+    ball_pos: Vec2,
+    ball_radius: f32,
+    circle_pos: Vec2,
+    circle_radius: f32,
+) -> Option<Vec2> {
+    let offset = ball_pos - circle_pos;
+    if offset.length() >= ball_radius + circle_radius {
+        return None;
+    }
+    // Perfectly concentric overlap has no meaningful normal, send the ball back up
+    Some(offset.try_normalize().unwrap_or(Vec2::Y))
+}
+
+// Reflects the ball velocity about the contact normal, unless it is already moving away
+pub fn ball_reflect(normal: Vec2, ball_v: &mut Velocity) {
+    let along_normal = ball_v.dot(normal);
+    if along_normal < 0. {
+        ball_v.0 -= 2. * along_normal * normal;
+    }
+}
+
+// Steps back along the ball's movement this tick, returning the earliest position where touches
+// holds. Moves no longer than max_step return None, the usual check where the ball ended up can't
+// miss anything then. Fast balls move further than a brick is tall in a single tick, so checking
+// only the end position would let them skip over thin bricks
+// NOT A SYSTEM
+pub fn first_swept_contact(
+    end: Vec2,
+    movement: Vec2,
+    max_step: f32,
+    touches: impl Fn(Vec2) -> bool,
+) -> Option<Vec2> {
+    let steps = (movement.length() / max_step).ceil() as usize;
+    if steps <= 1 {
+        return None;
+    }
+    let start = end - movement;
+    // The end position is left to the usual check
+    (1..steps)
+        .map(|step| start + movement * step as f32 / steps as f32)
+        .find(|pos| touches(*pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_jitter_the_bounce_is_an_exact_mirror() {
+        let mut rng = GameRng::from_seed(0);
+        let mut ball_v = Velocity(Vec2::new(0.6, -0.8));
+        ball_ricochet(Collision::Top, &mut ball_v);
+        jitter_bounce(&mut ball_v, 0., &mut rng);
+        assert_eq!(ball_v.0, Vec2::new(0.6, 0.8));
+    }
+
+    #[test]
+    fn jitter_turns_the_bounce_within_bounds() {
+        let mut rng = GameRng::from_seed(0);
+        let mirrored = Vec2::new(0.6, -0.8);
+        let max_turn = MAX_BOUNCE_JITTER_DEGREES.to_radians() + 1e-4;
+        let mut varied = false;
+        for _ in 0..50 {
+            let mut ball_v = Velocity(mirrored);
+            jitter_bounce(&mut ball_v, MAX_BOUNCE_JITTER_DEGREES, &mut rng);
+            assert!(mirrored.angle_between(ball_v.0).abs() <= max_turn);
+            assert!(ball_v.x > 0. && ball_v.y < 0.);
+            assert!((ball_v.length() - 1.).abs() < 1e-4);
+            varied |= ball_v.0 != mirrored;
+        }
+        assert!(varied);
+    }
+
+    #[test]
+    fn circular_brick_reflects_the_ball_along_the_contact_normal() {
+        // The ball strikes the brick's upper right side, where the normal is 45 degrees up
+        let brick_pos = Vec2::ZERO;
+        let ball_pos = Vec2::new(10., 10.);
+        let normal = circle_contact_normal(ball_pos, 5., brick_pos, 12.).unwrap();
+        assert!(normal.abs_diff_eq(Vec2::new(1., 1.).normalize(), 1e-5));
+
+        // Falling straight down onto a 45 degree face sends the ball off sideways
+        let mut ball_v = Velocity(Vec2::new(0., -100.));
+        ball_reflect(normal, &mut ball_v);
+        assert!(ball_v.abs_diff_eq(Vec2::new(100., 0.), 1e-3));
+    }
+
+    #[test]
+    fn ball_moving_away_from_a_circular_brick_keeps_its_velocity() {
+        let normal = circle_contact_normal(Vec2::new(0., 15.), 5., Vec2::ZERO, 12.).unwrap();
+        let mut ball_v = Velocity(Vec2::new(30., 40.));
+        ball_reflect(normal, &mut ball_v);
+        assert_eq!(ball_v.0, Vec2::new(30., 40.));
+    }
+
+    #[test]
+    fn separate_circles_have_no_contact() {
+        assert_eq!(
+            circle_contact_normal(Vec2::new(0., 17.), 5., Vec2::ZERO, 12.),
+            None
+        );
+    }
+
+    // A 10 wide wall standing across x = 50
+    fn touches_wall(pos: Vec2) -> bool {
+        (pos.x - 50.).abs() < 5.
+    }
+
+    #[test]
+    fn a_long_move_stops_at_the_first_touch_along_the_way() {
+        let end = Vec2::new(100., 0.);
+        let contact = first_swept_contact(end, Vec2::new(100., 0.), 10., touches_wall);
+        assert_eq!(contact, Some(Vec2::new(50., 0.)));
+    }
+
+    #[test]
+    fn short_moves_are_left_to_the_usual_check() {
+        let end = Vec2::new(52., 0.);
+        assert!(first_swept_contact(end, Vec2::new(8., 0.), 10., touches_wall).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::{prelude::*, sprite::collide_aabb::collide};
+use rand::Rng;
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, BrickDestroyed, MoveSet, Paddle, Velocity},
+    bricks::BrickKind,
+    misc::{hit_stop::hit_stop_inactive, rng::GameRng},
+    net::{simulates_locally, RemotePaddle},
+    powerups::{ApplyPowerUp, PowerUpKind},
+    walls::BOTTOM_WALL,
+};
+
+// The chance a destroyed brick drops a power-up for the paddle to catch
+const DROP_CHANCE: f64 = 0.1;
+const PICKUP_FALL_SPEED: f32 = 150.;
+const PICKUP_SIZE: Vec3 = Vec3::new(30., 12., 1.);
+// Drawn in front of the bricks
+const PICKUP_Z: f32 = 1.;
+
+// Destroyed bricks sometimes drop a power-up, granted if it lands on the paddle
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            drop_power_ups
+                .run_if(game_running)
+                .run_if(simulates_locally)
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        )
+        .add_systems(
+            FixedUpdate,
+            check_powerup_collision
+                .after(MoveSet)
+                .run_if(game_running)
+                .run_if(hit_stop_inactive)
+                .run_if(simulates_locally)
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// A power-up falling towards the paddle, moved by apply_velocity
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+}
+
+// Rolls a drop for each destroyed brick. Mystery bricks already grant theirs outright
+pub fn drop_power_ups(
+    mut commands: Commands,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut rng: ResMut<GameRng>,
+) {
+    for destroyed in destroyed_events.iter() {
+        if destroyed.kind == BrickKind::Mystery || !rng.gen_bool(DROP_CHANCE) {
+            continue;
+        }
+        let kind = PowerUpKind::roll(&mut rng);
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: kind.color(),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    destroyed.position.truncate().extend(PICKUP_Z),
+                )
+                .with_scale(PICKUP_SIZE),
+                ..default()
+            },
+            PowerUp { kind },
+            Velocity(Vec2::new(0., -PICKUP_FALL_SPEED)),
+            Name::new("PowerUp"),
+        ));
+    }
+}
+
+// Grants the power-ups landing on the paddle. Any that fall past the bottom wall are just lost
+pub fn check_powerup_collision(
+    mut commands: Commands,
+    pickup_q: Query<(Entity, &Transform, &PowerUp)>,
+    paddle_q: Query<&Transform, (With<Paddle>, Without<RemotePaddle>)>,
+    mut power_ups: EventWriter<ApplyPowerUp>,
+) {
+    for (ent, tform, power_up) in pickup_q.iter() {
+        let caught = paddle_q.iter().any(|paddle_t| {
+            collide(
+                tform.translation,
+                tform.scale.truncate(),
+                paddle_t.translation,
+                paddle_t.scale.truncate(),
+            )
+            .is_some()
+        });
+        if caught {
+            info!("Caught {:?}", power_up.kind);
+            power_ups.send(ApplyPowerUp(power_up.kind));
+            commands.entity(ent).despawn_recursive();
+        } else if tform.translation.y < BOTTOM_WALL {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    breaker::BrickDestroyed, bricks::BrickKind, health::Health, misc::rng::GameRng,
+    paddle_width::PaddleWidth, scoreboard::Scoreboard, settings::GameSettings,
+};
+
+const BONUS_SCORE: usize = 100;
+const SLOW_BALL_DURATION: f32 = 5.0;
+pub const SLOW_BALL_FACTOR: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerUpKind {
+    BonusScore,
+    SlowBall,
+    ExtraLife,
+    WidePaddle,   // Also undoes paddle decay
+    NarrowPaddle, // A setback, shrinks the paddle for a while
+    GhostBall,    // Balls pass through bricks for a while, see ghost_ball.rs
+    StrongBall,   // Balls deal extra damage for a while, see strong_ball.rs
+}
+
+// Every power-up that can be rolled, weighted so that rare power-ups stay rare
+const POWER_UP_POOL: [(PowerUpKind, u32); 7] = [
+    (PowerUpKind::BonusScore, 6),
+    (PowerUpKind::SlowBall, 3),
+    (PowerUpKind::WidePaddle, 3),
+    (PowerUpKind::NarrowPaddle, 2),
+    (PowerUpKind::GhostBall, 2),
+    (PowerUpKind::StrongBall, 2),
+    (PowerUpKind::ExtraLife, 1),
+];
+
+impl PowerUpKind {
+    // Picks a power-up from the pool according to the weights
+    pub fn roll(rng: &mut GameRng) -> PowerUpKind {
+        let total: u32 = POWER_UP_POOL.iter().map(|(_, weight)| weight).sum();
+        let mut pick = rng.gen_range(0..total);
+        for (kind, weight) in POWER_UP_POOL {
+            if pick < weight {
+                return kind;
+            }
+            pick -= weight;
+        }
+        unreachable!("pick is always less than the total weight")
+    }
+
+    // The color of a falling pickup, so each power-up can be told apart before it is caught
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::BonusScore => Color::rgb(1.0, 0.84, 0.0),
+            PowerUpKind::SlowBall => Color::rgb(0.3, 0.6, 1.0),
+            PowerUpKind::ExtraLife => Color::rgb(1.0, 0.3, 0.4),
+            PowerUpKind::WidePaddle => Color::rgb(0.3, 0.9, 0.4),
+            PowerUpKind::NarrowPaddle => Color::rgb(0.6, 0.3, 0.1),
+            PowerUpKind::GhostBall => Color::rgba(0.85, 0.85, 1.0, 0.6),
+            PowerUpKind::StrongBall => Color::rgb(1.0, 0.45, 0.1),
+        }
+    }
+}
+
+// Write an ApplyPowerUp to grant the player the contained power-up
+#[derive(Event, Clone, Debug)]
+pub struct ApplyPowerUp(pub PowerUpKind);
+
+// Slows every ball down while the contained timer runs
+#[derive(Resource)]
+pub struct SlowBall(pub Timer);
+
+impl SlowBall {
+    pub fn active(&self) -> bool {
+        !self.0.finished()
+    }
+}
+
+impl Default for SlowBall {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(SLOW_BALL_DURATION, TimerMode::Once);
+        let duration = timer.duration();
+        timer.tick(duration); // Start inactive
+        SlowBall(timer)
+    }
+}
+
+// Mystery bricks roll a random power-up and grant it immediately when destroyed
+pub fn grant_mystery_power_ups(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut rng: ResMut<GameRng>,
+    mut power_ups: EventWriter<ApplyPowerUp>,
+) {
+    for destroyed in destroyed_events.iter() {
+        if destroyed.kind != BrickKind::Mystery {
+            continue;
+        }
+        let kind = PowerUpKind::roll(&mut rng);
+        info!("Mystery brick granted {:?}", kind);
+        power_ups.send(ApplyPowerUp(kind));
+    }
+}
+
+pub fn apply_power_ups(
+    mut power_ups: EventReader<ApplyPowerUp>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut health: ResMut<Health>,
+    mut slow_ball: ResMut<SlowBall>,
+    mut paddle_width: ResMut<PaddleWidth>,
+    settings: Res<GameSettings>,
+) {
+    for ApplyPowerUp(kind) in power_ups.iter() {
+        match kind {
+            PowerUpKind::BonusScore => scoreboard.score += BONUS_SCORE,
+            PowerUpKind::SlowBall => slow_ball.0.reset(),
+            PowerUpKind::ExtraLife => {
+                // A hardcore run never gets more than its one life
+                if !settings.hardcore.enabled {
+                    **health += 1
+                }
+            }
+            PowerUpKind::WidePaddle => paddle_width.widen(),
+            PowerUpKind::NarrowPaddle => paddle_width.narrow(),
+            PowerUpKind::GhostBall => {} // Granted to the balls by grant_ghost_balls
+            PowerUpKind::StrongBall => {} // Granted to the balls by grant_strong_balls
+        }
+    }
+}
+
+pub fn tick_power_ups(mut slow_ball: ResMut<SlowBall>, time: Res<Time>) {
+    slow_ball.0.tick(time.delta());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{sent_events, test_app};
+
+    fn destroyed(kind: BrickKind) -> BrickDestroyed {
+        BrickDestroyed {
+            position: Vec3::ZERO,
+            kind,
+            ball: Entity::PLACEHOLDER,
+            color: Color::WHITE,
+        }
+    }
+
+    #[test]
+    fn destroying_a_mystery_brick_grants_exactly_one_power_up() {
+        let mut app = test_app();
+        app.add_event::<BrickDestroyed>()
+            .add_event::<ApplyPowerUp>()
+            .add_systems(Update, grant_mystery_power_ups);
+        app.world.send_event(destroyed(BrickKind::Mystery));
+        app.world.send_event(destroyed(BrickKind::Normal));
+        app.update();
+
+        let granted = sent_events::<ApplyPowerUp>(&app);
+        assert_eq!(granted.len(), 1);
+        assert!(POWER_UP_POOL.iter().any(|(kind, _)| *kind == granted[0].0));
+    }
+
+    #[test]
+    fn rare_power_ups_are_rolled_less_often() {
+        let mut rng = GameRng::from_seed(0);
+        let rolls: Vec<_> = (0..1000).map(|_| PowerUpKind::roll(&mut rng)).collect();
+        let count = |kind| rolls.iter().filter(|rolled| **rolled == kind).count();
+        assert!(count(PowerUpKind::ExtraLife) < count(PowerUpKind::BonusScore));
+    }
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{CurrentState, GameState},
+    key_bindings::Action,
+    settings::GameSettings,
+};
+
+const PROMPT_FONT_SIZE: f32 = 40.;
+const PROMPT_COLOR: Color = Color::rgb(1., 1., 1.);
+
+// Marker for the text shown while a level is being previewed
+#[derive(Component)]
+pub struct PreviewPrompt;
+
+// Shows the start prompt for as long as the game is in GS::Preview
+pub fn show_preview_prompt(
+    mut commands: Commands,
+    game_state: Res<CurrentState>,
+    prompt_q: Query<Entity, With<PreviewPrompt>>,
+    settings: Res<GameSettings>,
+) {
+    let previewing = **game_state == GameState::Preview;
+    if previewing && prompt_q.is_empty() {
+        let start_key = settings.controls.bindings.prompt_label(Action::Start);
+        commands.spawn((
+            TextBundle::from_section(
+                format!("Press {start_key} to start"),
+                TextStyle {
+                    font_size: PROMPT_FONT_SIZE,
+                    color: PROMPT_COLOR,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Percent(30.),
+                left: Val::Percent(38.),
+                ..default()
+            }),
+            PreviewPrompt,
+            Name::new("PreviewPrompt"),
+        ));
+    } else if !previewing {
+        for ent in prompt_q.iter() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::misc::persist;
+
+const PROFILE_FILE: &str = "profile.json";
+
+// Records kept across runs
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub fastest_speed: f32, // The fastest any ball has gone, as a multiple of the base ball speed
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        persist::load_json(PROFILE_FILE)
+    }
+
+    pub fn save(&self) {
+        persist::save_json(PROFILE_FILE, self)
+    }
+}
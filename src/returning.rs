@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+
+use crate::{breaker::PaddleMomentum, settings::GameSettings};
+
+// How long the paddle and ball take to slide back to their starting positions between levels
+const RETURN_DURATION: f32 = 0.5;
+
+// Slides an entity back to where it starts a level. Physics is held until every entity is back,
+// so nothing collides on the way. Under reduce motion it is put straight back instead
+#[derive(Component)]
+pub struct ReturningToStart {
+    from: Vec3,
+    target: Vec3,
+    timer: Timer,
+}
+
+impl ReturningToStart {
+    pub fn new(from: Vec3, target: Vec3) -> Self {
+        ReturningToStart {
+            from,
+            target,
+            timer: Timer::from_seconds(RETURN_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+pub fn return_to_start(
+    mut commands: Commands,
+    mut returning_q: Query<(Entity, &mut Transform, &mut ReturningToStart)>,
+    mut paddle_momentum: ResMut<PaddleMomentum>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if returning_q.is_empty() {
+        return;
+    }
+    // Momentum built up before the level ended would fight the slide, and carry into the level
+    **paddle_momentum = 0.;
+    for (ent, mut tform, mut returning) in returning_q.iter_mut() {
+        if settings.accessibility.reduce_motion {
+            let duration = returning.timer.duration();
+            returning.timer.tick(duration);
+        } else {
+            returning.timer.tick(time.delta());
+        }
+        // Eases out, so the slide settles gently into place
+        let t = 1. - (1. - returning.timer.percent()).powi(2);
+        tform.translation = returning.from.lerp(returning.target, t);
+        if returning.timer.finished() {
+            commands.entity(ent).remove::<ReturningToStart>();
+        }
+    }
+}
+
+// Run condition for the physics, which waits until everything is back at the start
+pub fn settled(returning_q: Query<(), With<ReturningToStart>>) -> bool {
+    returning_q.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::{advance_time, test_app};
+
+    const FROM: Vec3 = Vec3::new(200., -250., 0.);
+    const TARGET: Vec3 = Vec3::new(0., -300., 0.);
+
+    #[test]
+    fn the_paddle_reaches_the_start_by_the_end_of_the_slide() {
+        let mut app = test_app();
+        app.init_resource::<PaddleMomentum>()
+            .add_systems(Update, return_to_start);
+        **app.world.resource_mut::<PaddleMomentum>() = 5.;
+        let paddle = app
+            .world
+            .spawn((
+                Transform::from_translation(FROM),
+                ReturningToStart::new(FROM, TARGET),
+            ))
+            .id();
+
+        advance_time(&mut app, 0.);
+        app.update();
+        advance_time(&mut app, RETURN_DURATION / 2.);
+        app.update();
+        let halfway = app.world.get::<Transform>(paddle).unwrap().translation;
+        assert!(halfway.distance(TARGET) > 0. && halfway.distance(TARGET) < FROM.distance(TARGET));
+        assert_eq!(**app.world.resource::<PaddleMomentum>(), 0.);
+
+        advance_time(&mut app, RETURN_DURATION / 2.);
+        app.update();
+        let tform = app.world.get::<Transform>(paddle).unwrap();
+        assert!(tform.translation.abs_diff_eq(TARGET, 1e-4));
+        assert!(app.world.get::<ReturningToStart>(paddle).is_none());
+    }
+
+    #[test]
+    fn reduce_motion_puts_the_paddle_straight_back() {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .accessibility
+            .reduce_motion = true;
+        app.init_resource::<PaddleMomentum>()
+            .add_systems(Update, return_to_start);
+        let paddle = app
+            .world
+            .spawn((
+                Transform::from_translation(FROM),
+                ReturningToStart::new(FROM, TARGET),
+            ))
+            .id();
+
+        advance_time(&mut app, 0.);
+        app.update();
+        let tform = app.world.get::<Transform>(paddle).unwrap();
+        assert_eq!(tform.translation, TARGET);
+        assert!(app.world.get::<ReturningToStart>(paddle).is_none());
+    }
+}
@@ -1,5 +1,13 @@
 use bevy::prelude::*;
 
+use crate::{
+    misc::labeled_display::{set_displayed_value, LabeledDisplayBundle},
+    settings::GameSettings,
+};
+
+// The shown score is always within this many points of the real score
+const MAX_SCORE_LAG: f32 = 500.;
+
 #[derive(Resource)]
 pub struct Scoreboard {
     pub score: usize,
@@ -11,9 +19,7 @@ pub struct ScoreDisplay;
 
 #[derive(Bundle)]
 pub struct ScoreboardBundle {
-    text: TextBundle,
-    name: Name,
-    marker: ScoreDisplay, // Used to uniquely identify the display bundle
+    display: LabeledDisplayBundle<ScoreDisplay>,
 }
 
 impl ScoreboardBundle {
@@ -26,47 +32,53 @@ impl ScoreboardBundle {
         initial_value: Option<V>,
     ) -> Self {
         ScoreboardBundle {
-            text: TextBundle::from_sections([
-                // Labels the score
-                TextSection::new(
-                    label,
-                    TextStyle {
-                        font_size: font_size,
-                        color: label_color,
-                        ..default()
-                    },
-                ),
-                // The score value
-                TextSection::new(
-                    match initial_value {
-                        Some(s) => s.into(),
-                        None => "".to_string(),
-                    },
-                    TextStyle {
-                        font_size: font_size,
-                        color: score_color,
-                        ..default()
-                    },
-                ),
-            ])
-            .with_style(Style {
-                position_type: PositionType::Absolute,
-                top: Val::Px(top_left_placement.y),
-                left: Val::Px(top_left_placement.x),
-                ..default()
-            }),
-            name: Name::new("Scoreboard"),
-            marker: ScoreDisplay,
+            display: LabeledDisplayBundle::new(
+                ScoreDisplay,
+                "Scoreboard",
+                font_size,
+                label_color,
+                score_color,
+                label,
+                top_left_placement,
+                initial_value,
+            )
+            // The combo multiplier, see combo::update_multiplier_display
+            .with_section(TextSection::new(
+                "",
+                TextStyle {
+                    font_size,
+                    color: score_color,
+                    ..default()
+                },
+            )),
         }
     }
 }
 
+// Counts the shown score up towards the real score, or snaps to it when counting up is off
 pub fn update_scoreboard(
+    mut displayed: Local<f32>,
     scoreboard: Res<Scoreboard>,
     mut text_q: Query<&mut Text, With<ScoreDisplay>>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
 ) {
-    // text_q holds the setup values put in the TextBundle
+    let target = scoreboard.score as f32;
+    let count_up_secs = settings.visual.score_count_up_secs;
+    if !settings.visual.score_count_up || count_up_secs <= 0. || target < *displayed {
+        *displayed = target; // The score only drops when a new run starts, so never count down
+    } else {
+        // Closes the same share of the gap every second, so big awards count up just as quickly.
+        // A third of the duration as the time constant leaves 5% of the gap after count_up_secs
+        let share = 1. - (-time.delta_seconds() * 3. / count_up_secs).exp();
+        *displayed += (target - *displayed) * share;
+        // Never trail rapid scoring by too much, and land on the exact score once it settles
+        *displayed = displayed.max(target - MAX_SCORE_LAG);
+        if target - *displayed < 0.5 {
+            *displayed = target;
+        }
+    }
+
     let mut text = text_q.single_mut();
-    // Update the empty section given the SCORE_COLOR
-    text.sections[1].value = scoreboard.score.to_string();
+    set_displayed_value(&mut text, (displayed.round() as usize).to_string());
 }
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    app_state::AppState,
+    breaker::{BrickDestroyed, CollisionEvent, CollisionKind},
+    settings::GameSettings,
+};
+
+// Trauma added by each destroyed brick and each lost ball, trauma is capped at 1
+const BRICK_TRAUMA: f32 = 0.15;
+const DRAIN_TRAUMA: f32 = 0.6;
+// Trauma lost per second, a full shake settles in just over a second
+const TRAUMA_DECAY: f32 = 0.8;
+// The camera moves at most this many pixels from its resting place, at full trauma
+const MAX_SHAKE_OFFSET: f32 = 12.;
+
+// Shakes the camera on heavy impacts. Only the camera moves, so it never affects the game itself
+pub struct ScreenShakePlugin;
+
+impl Plugin for ScreenShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>().add_systems(
+            Update,
+            (add_trauma, shake_camera.after(add_trauma))
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// How hard the camera is shaking, from 0 to 1. Decays back to 0 on its own
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+    trauma: f32,
+    // Whether the camera was moved last frame, so it is put back once the trauma runs out
+    shaken: bool,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.);
+    }
+}
+
+pub fn add_trauma(
+    mut shake: ResMut<ScreenShake>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    mut collision_events: EventReader<CollisionEvent>,
+    settings: Res<GameSettings>,
+) {
+    let destroyed = destroyed_events.iter().count();
+    let drained = collision_events
+        .iter()
+        .filter(|collision| matches!(collision.kind, CollisionKind::Drain))
+        .count();
+    if !settings.visual.screen_shake || settings.accessibility.reduce_motion {
+        return;
+    }
+    shake.add_trauma(destroyed as f32 * BRICK_TRAUMA + drained as f32 * DRAIN_TRAUMA);
+}
+
+// Offsets the camera from where the tilt puts it, by noise that grows with the square of the
+// trauma so small hits stay subtle. Once the trauma is gone it goes back exactly to that spot
+pub fn shake_camera(
+    mut shake: ResMut<ScreenShake>,
+    mut camera_q: Query<&mut Transform, With<Camera2d>>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if shake.trauma <= 0. && !shake.shaken {
+        return;
+    }
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_seconds()).max(0.);
+    shake.shaken = shake.trauma > 0.;
+
+    let strength = MAX_SHAKE_OFFSET * shake.trauma * shake.trauma;
+    let mut rng = rand::thread_rng();
+    for mut tform in camera_q.iter_mut() {
+        // Purely visual, so this doesn't draw from the seeded GameRng
+        let offset = Vec2::new(rng.gen_range(-1.0..=1.), rng.gen_range(-1.0..=1.)) * strength;
+        *tform = settings.camera_transform();
+        tform.translation += tform.rotation * offset.extend(0.);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        misc::test_utils::{advance_time, test_app},
+        tilt::game_camera,
+    };
+
+    fn shake_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<ScreenShake>()
+            .add_event::<BrickDestroyed>()
+            .add_event::<CollisionEvent>()
+            .add_systems(Update, (add_trauma, shake_camera).chain());
+        app.world.spawn(game_camera());
+        advance_time(&mut app, 0.);
+        app
+    }
+
+    fn lose_ball(app: &mut App) {
+        app.world.send_event(CollisionEvent {
+            contact: Vec2::ZERO,
+            side: None,
+            kind: CollisionKind::Drain,
+        });
+        advance_time(app, 0.1);
+        app.update();
+    }
+
+    fn camera_transform(app: &mut App) -> Transform {
+        let mut camera_q = app.world.query_filtered::<&Transform, With<Camera2d>>();
+        *camera_q.single(&app.world)
+    }
+
+    #[test]
+    fn the_camera_settles_exactly_where_it_started() {
+        let mut app = shake_app();
+        let resting = app.world.resource::<GameSettings>().camera_transform();
+        lose_ball(&mut app);
+        assert_ne!(camera_transform(&mut app), resting);
+
+        for _ in 0..20 {
+            advance_time(&mut app, 0.1);
+            app.update();
+        }
+        assert_eq!(camera_transform(&mut app), resting);
+    }
+
+    #[test]
+    fn turning_screen_shake_off_keeps_the_camera_still() {
+        let mut app = shake_app();
+        app.world.resource_mut::<GameSettings>().visual.screen_shake = false;
+        let before = camera_transform(&mut app);
+        lose_ball(&mut app);
+        assert_eq!(camera_transform(&mut app), before);
+    }
+
+    #[test]
+    fn reduce_motion_keeps_the_camera_still() {
+        let mut app = shake_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .accessibility
+            .reduce_motion = true;
+        let before = camera_transform(&mut app);
+        lose_ball(&mut app);
+        assert_eq!(camera_transform(&mut app), before);
+    }
+}
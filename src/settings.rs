@@ -0,0 +1,495 @@
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    breaker::{PaddleEdgeMode, PaddleInput, PaddleTiming},
+    brick_descent::{DEFAULT_BRICK_DESCENT_INTERVAL, MIN_BRICK_DESCENT_INTERVAL},
+    bricks::BrickPalette,
+    combo::ComboSettings,
+    crt::{CrtSettings, MAX_CRT_CURVATURE},
+    gamepad::GamepadBindings,
+    hardcore::HardcoreConfig,
+    highlight::{HighlightSettings, MAX_HIGHLIGHT_WINDOW},
+    key_bindings::KeyBindings,
+    misc::persist,
+    music::AudioSettings,
+    physics::MAX_BOUNCE_JITTER_DEGREES,
+    theme::Theme,
+    tilt::{TiltSettings, MAX_TILT_DEGREES},
+    walls::{ArenaShape, DrainLayout},
+};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// The catch radius can't grow the paddle's collision area more than this on each side
+pub const MAX_CATCH_RADIUS: f32 = 20.;
+const DEFAULT_SERVE_CONE_DEGREES: f32 = 60.;
+// Ball gravity is off by default, try around 1.0 for a lob heavy game
+const DEFAULT_BALL_GRAVITY: f32 = 0.;
+// The settings slider stops here, stronger gravity turns a served ball around almost at once
+const MAX_BALL_GRAVITY: f32 = 3.;
+// The settings slider stops here, any slower and the bricks hardly move within a level
+const MAX_BRICK_DESCENT_INTERVAL: f32 = 60.;
+const DEFAULT_SCORE_COUNT_UP_SECS: f32 = 0.3;
+// Slower count ups fall behind the next award and leave the score lagging
+const MAX_SCORE_COUNT_UP_SECS: f32 = 1.;
+// Up to this strength brick colors are easy to tell apart, so only stronger bricks get HP bars
+const DEFAULT_HP_BAR_MIN_STRENGTH: u8 = 4;
+// Stronger bricks all share the strongest color, so they always get a bar
+const MAX_HP_BAR_MIN_STRENGTH: f32 = 6.;
+// Past this the meter hardly drains between breaks and any combo keeps going
+const MAX_COMBO_WINDOW_SECS: f32 = 5.;
+// More held balls than this would hang off the ends of the paddle
+const MAX_CATCH_CAPACITY: f32 = 4.;
+// Past this a couple of near misses take the ball straight to its top speed
+const MAX_NEAR_MISS_SPEEDUP: f32 = 0.5;
+
+// Player facing options, grouped into the sections they appear under
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    pub assist: AssistSettings,
+    pub visual: VisualSettings,
+    pub controls: ControlSettings,
+    pub accessibility: AccessibilitySettings,
+    pub gameplay: GameplaySettings,
+    pub audio: AudioSettings,
+    pub hardcore: HardcoreConfig,
+    pub highlight: HighlightSettings,
+    pub combo: ComboSettings,
+}
+
+impl GameSettings {
+    pub fn load() -> Self {
+        persist::load_json(SETTINGS_FILE)
+    }
+
+    pub fn save(&self) {
+        persist::save_json(SETTINGS_FILE, self)
+    }
+
+    // The bounce jitter in play, ranked hardcore runs always bounce as perfect mirrors
+    pub fn bounce_jitter_degrees(&self) -> f32 {
+        if self.hardcore.enabled {
+            0.
+        } else {
+            self.gameplay.bounce_jitter_degrees
+        }
+    }
+
+    // Where the camera rests, looking straight on under reduce motion since the tilted view can
+    // be just as uncomfortable as motion
+    pub fn camera_transform(&self) -> Transform {
+        let mut tilt = self.visual.tilt.clone();
+        tilt.enabled &= !self.accessibility.reduce_motion;
+        tilt.camera_transform()
+    }
+}
+
+// Options that make the game easier, using them excludes a run from the leaderboard
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssistSettings {
+    // Extra collision margin around the paddle in pixels, 0 turns the assist off
+    pub catch_radius: f32,
+}
+
+impl AssistSettings {
+    pub fn catch_margin(&self) -> f32 {
+        self.catch_radius.clamp(0., MAX_CATCH_RADIUS)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VisualSettings {
+    pub theme: Theme,
+    // Slowly rotates the brick hues in a wave across the level, strength still sets the lightness
+    pub brick_color_cycle: bool,
+    // Gives every ball its own color from a palette, so each can be followed during multiball
+    pub ball_tints: bool,
+    // Counts the score display up to new values instead of snapping to them
+    pub score_count_up: bool,
+    // Roughly how long a count up takes, however large the award
+    pub score_count_up_secs: f32,
+    pub crt: CrtSettings,
+    pub tilt: TiltSettings,
+    // Shows a bar of the strength left above bricks at least this strong
+    pub hp_bars: bool,
+    pub hp_bar_min_strength: u8,
+    // Shows each brick's strength as a number on it
+    pub strength_numbers: bool,
+    // Draws each level's background scene behind the arena
+    pub level_backgrounds: bool,
+    // The brick colors by strength, the colorblind palette also differs in brightness
+    pub brick_palette: BrickPalette,
+    // Shakes the camera when bricks break and balls are lost, reduce_motion also turns it off
+    pub screen_shake: bool,
+    // Freezes the action for a moment when a brick breaks, reduce_motion also turns it off
+    pub hit_stop: bool,
+}
+
+impl Default for VisualSettings {
+    fn default() -> Self {
+        VisualSettings {
+            theme: Theme::default(),
+            brick_color_cycle: false,
+            ball_tints: false,
+            score_count_up: true,
+            score_count_up_secs: DEFAULT_SCORE_COUNT_UP_SECS,
+            crt: CrtSettings::default(),
+            tilt: TiltSettings::default(),
+            hp_bars: false,
+            hp_bar_min_strength: DEFAULT_HP_BAR_MIN_STRENGTH,
+            strength_numbers: true,
+            level_backgrounds: false,
+            brick_palette: BrickPalette::default(),
+            screen_shake: true,
+            hit_stop: true,
+        }
+    }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlSettings {
+    pub paddle_timing: PaddleTiming,
+    pub paddle_input: PaddleInput,
+    pub bindings: KeyBindings,
+    pub gamepad: GamepadBindings,
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    // Disables flashing and motion effects like hit-stop, every such effect must check this
+    pub reduce_motion: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplaySettings {
+    // Width of the cone, centered on straight up, that serves are randomly aimed within
+    pub serve_cone_degrees: f32,
+    // How fast the ball's direction bends downward, in units of its speed per second
+    pub ball_gravity: f32,
+    // Follows every cleared level with a short round of golden bricks worth extra points
+    pub bonus_round: bool,
+    // Shows each new level with physics frozen until the player presses the start key
+    pub level_preview: bool,
+    // Randomly turns wall and brick bounces by up to this many degrees (max 15), 0 keeps them exact
+    pub bounce_jitter_degrees: f32,
+    // Challenge modifier, the paddle slowly shrinks over each level so fast clears pay off
+    pub paddle_decay: bool,
+    // Arcade mode, the paddle wraps around from one wall to the other instead of stopping
+    pub paddle_edge: PaddleEdgeMode,
+    pub arena_shape: ArenaShape,
+    pub drain_layout: DrainLayout,
+    // Losing the ball shortly after a level starts or a serve costs no health
+    pub grace_period: bool,
+    // Challenge mode, the bricks move down a row every interval and breach if they reach the paddle
+    pub brick_descent: bool,
+    pub brick_descent_interval: f32, // In seconds
+    // Adds a turret under the paddle that launches extra balls on demand, with a cooldown
+    pub turret: bool,
+    // How many balls the paddle can catch and hold for launching one at a time, 0 turns it off
+    pub catch_capacity: usize,
+    // Risk and reward scoring, wall bounces in a row bank a bonus on the next brick hit
+    pub wall_bounce_bonus: bool,
+    // Served balls wait on the paddle until launched, giving a breather after losing health
+    pub docked_serve: bool,
+    // Counts down before a run or level starts and before play resumes from pause
+    pub countdown: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        GameplaySettings {
+            serve_cone_degrees: DEFAULT_SERVE_CONE_DEGREES,
+            ball_gravity: DEFAULT_BALL_GRAVITY,
+            bonus_round: false,
+            level_preview: false,
+            bounce_jitter_degrees: 0.,
+            paddle_decay: false,
+            paddle_edge: PaddleEdgeMode::default(),
+            arena_shape: ArenaShape::default(),
+            drain_layout: DrainLayout::default(),
+            grace_period: false,
+            brick_descent: false,
+            brick_descent_interval: DEFAULT_BRICK_DESCENT_INTERVAL,
+            turret: false,
+            catch_capacity: 0,
+            wall_bounce_bonus: false,
+            docked_serve: true,
+            countdown: true,
+        }
+    }
+}
+
+// The on/off options listed on the settings screen, each switching one of the settings above
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingToggle {
+    HitStop,
+    ReduceMotion,
+    BonusRound,
+    BrickColorCycle,
+    BallTints,
+    LevelPreview,
+    ScoreCountUp,
+    Crt,
+    CrtIncludeUi,
+    Music,
+    AdaptiveMusic,
+    PaddleDecay,
+    GracePeriod,
+    BrickDescent,
+    Highlight,
+    Tilt,
+    HpBars,
+    Turret,
+    ComboMeter,
+    LevelBackgrounds,
+    WallBounceBonus,
+    ScreenShake,
+    StrengthNumbers,
+    Countdown,
+    Hardcore,
+    DockedServe,
+}
+
+impl SettingToggle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingToggle::HitStop => "Hit-Stop",
+            SettingToggle::ReduceMotion => "Reduce Motion",
+            SettingToggle::BonusRound => "Bonus Round",
+            SettingToggle::BrickColorCycle => "Brick Color Cycle",
+            SettingToggle::BallTints => "Ball Tints",
+            SettingToggle::LevelPreview => "Level Preview",
+            SettingToggle::ScoreCountUp => "Score Count-Up",
+            SettingToggle::Crt => "CRT Effect",
+            SettingToggle::CrtIncludeUi => "CRT Over HUD",
+            SettingToggle::Music => "Music",
+            SettingToggle::AdaptiveMusic => "Adaptive Music",
+            SettingToggle::PaddleDecay => "Shrinking Paddle",
+            SettingToggle::GracePeriod => "Grace Period",
+            SettingToggle::BrickDescent => "Descending Bricks",
+            SettingToggle::Highlight => "Save Highlights",
+            SettingToggle::Tilt => "Arena Tilt",
+            SettingToggle::HpBars => "HP Bars",
+            SettingToggle::Turret => "Turret",
+            SettingToggle::ComboMeter => "Combo Meter",
+            SettingToggle::LevelBackgrounds => "Level Backgrounds",
+            SettingToggle::WallBounceBonus => "Wall Bounce Bonus",
+            SettingToggle::ScreenShake => "Screen Shake",
+            SettingToggle::StrengthNumbers => "Strength Numbers",
+            SettingToggle::Countdown => "Countdown",
+            SettingToggle::Hardcore => "Hardcore",
+            SettingToggle::DockedServe => "Docked Serve",
+        }
+    }
+
+    pub fn is_on(&self, settings: &GameSettings) -> bool {
+        match self {
+            SettingToggle::HitStop => settings.visual.hit_stop,
+            SettingToggle::ReduceMotion => settings.accessibility.reduce_motion,
+            SettingToggle::BonusRound => settings.gameplay.bonus_round,
+            SettingToggle::BrickColorCycle => settings.visual.brick_color_cycle,
+            SettingToggle::BallTints => settings.visual.ball_tints,
+            SettingToggle::LevelPreview => settings.gameplay.level_preview,
+            SettingToggle::ScoreCountUp => settings.visual.score_count_up,
+            SettingToggle::Crt => settings.visual.crt.enabled,
+            SettingToggle::CrtIncludeUi => settings.visual.crt.include_ui,
+            SettingToggle::Music => settings.audio.music,
+            SettingToggle::AdaptiveMusic => settings.audio.adaptive_music,
+            SettingToggle::PaddleDecay => settings.gameplay.paddle_decay,
+            SettingToggle::GracePeriod => settings.gameplay.grace_period,
+            SettingToggle::BrickDescent => settings.gameplay.brick_descent,
+            SettingToggle::Highlight => settings.highlight.enabled,
+            SettingToggle::Tilt => settings.visual.tilt.enabled,
+            SettingToggle::HpBars => settings.visual.hp_bars,
+            SettingToggle::Turret => settings.gameplay.turret,
+            SettingToggle::ComboMeter => settings.combo.meter,
+            SettingToggle::LevelBackgrounds => settings.visual.level_backgrounds,
+            SettingToggle::WallBounceBonus => settings.gameplay.wall_bounce_bonus,
+            SettingToggle::ScreenShake => settings.visual.screen_shake,
+            SettingToggle::StrengthNumbers => settings.visual.strength_numbers,
+            SettingToggle::Countdown => settings.gameplay.countdown,
+            SettingToggle::Hardcore => settings.hardcore.enabled,
+            SettingToggle::DockedServe => settings.gameplay.docked_serve,
+        }
+    }
+
+    pub fn set(&self, settings: &mut GameSettings, on: bool) {
+        match self {
+            SettingToggle::HitStop => settings.visual.hit_stop = on,
+            SettingToggle::ReduceMotion => settings.accessibility.reduce_motion = on,
+            SettingToggle::BonusRound => settings.gameplay.bonus_round = on,
+            SettingToggle::BrickColorCycle => settings.visual.brick_color_cycle = on,
+            SettingToggle::BallTints => settings.visual.ball_tints = on,
+            SettingToggle::LevelPreview => settings.gameplay.level_preview = on,
+            SettingToggle::ScoreCountUp => settings.visual.score_count_up = on,
+            SettingToggle::Crt => settings.visual.crt.enabled = on,
+            SettingToggle::CrtIncludeUi => settings.visual.crt.include_ui = on,
+            SettingToggle::Music => settings.audio.music = on,
+            SettingToggle::AdaptiveMusic => settings.audio.adaptive_music = on,
+            SettingToggle::PaddleDecay => settings.gameplay.paddle_decay = on,
+            SettingToggle::GracePeriod => settings.gameplay.grace_period = on,
+            SettingToggle::BrickDescent => settings.gameplay.brick_descent = on,
+            SettingToggle::Highlight => settings.highlight.enabled = on,
+            SettingToggle::Tilt => settings.visual.tilt.enabled = on,
+            SettingToggle::HpBars => settings.visual.hp_bars = on,
+            SettingToggle::Turret => settings.gameplay.turret = on,
+            SettingToggle::ComboMeter => settings.combo.meter = on,
+            SettingToggle::LevelBackgrounds => settings.visual.level_backgrounds = on,
+            SettingToggle::WallBounceBonus => settings.gameplay.wall_bounce_bonus = on,
+            SettingToggle::ScreenShake => settings.visual.screen_shake = on,
+            SettingToggle::StrengthNumbers => settings.visual.strength_numbers = on,
+            SettingToggle::Countdown => settings.gameplay.countdown = on,
+            SettingToggle::Hardcore => settings.hardcore.enabled = on,
+            SettingToggle::DockedServe => settings.gameplay.docked_serve = on,
+        }
+    }
+}
+
+// The numeric options on the settings screen, each adjusting one of the settings above
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingSlider {
+    CatchRadius,
+    ServeCone,
+    BallGravity,
+    ScoreCountUpSecs,
+    CrtScanlines,
+    CrtCurvature,
+    CrtVignette,
+    MusicVolume,
+    BounceJitter,
+    BrickDescentInterval,
+    HighlightWindow,
+    TiltDegrees,
+    HpBarMinStrength,
+    ComboWindow,
+    CatchCapacity,
+    NearMissSpeedup,
+    ComboWarning,
+}
+
+impl SettingSlider {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingSlider::CatchRadius => "Catch Radius",
+            SettingSlider::ServeCone => "Serve Cone Degrees",
+            SettingSlider::BallGravity => "Ball Gravity",
+            SettingSlider::ScoreCountUpSecs => "Score Count-Up Seconds",
+            SettingSlider::CrtScanlines => "CRT Scanlines",
+            SettingSlider::CrtCurvature => "CRT Curvature",
+            SettingSlider::CrtVignette => "CRT Vignette",
+            SettingSlider::MusicVolume => "Music Volume",
+            SettingSlider::BounceJitter => "Bounce Jitter Degrees",
+            SettingSlider::BrickDescentInterval => "Descent Interval Seconds",
+            SettingSlider::HighlightWindow => "Highlight Seconds",
+            SettingSlider::TiltDegrees => "Tilt Degrees",
+            SettingSlider::HpBarMinStrength => "HP Bars From Strength",
+            SettingSlider::ComboWindow => "Combo Window",
+            SettingSlider::CatchCapacity => "Balls Caught",
+            SettingSlider::NearMissSpeedup => "Near Miss Speedup",
+            SettingSlider::ComboWarning => "Combo Warning At",
+        }
+    }
+
+    pub fn range(&self) -> RangeInclusive<f32> {
+        match self {
+            SettingSlider::CatchRadius => 0.0..=MAX_CATCH_RADIUS,
+            SettingSlider::ServeCone => 0.0..=180.,
+            SettingSlider::BallGravity => 0.0..=MAX_BALL_GRAVITY,
+            SettingSlider::ScoreCountUpSecs => 0.0..=MAX_SCORE_COUNT_UP_SECS,
+            SettingSlider::CrtScanlines => 0.0..=1.,
+            SettingSlider::CrtCurvature => 0.0..=MAX_CRT_CURVATURE,
+            SettingSlider::CrtVignette => 0.0..=1.,
+            SettingSlider::MusicVolume => 0.0..=1.,
+            SettingSlider::BounceJitter => 0.0..=MAX_BOUNCE_JITTER_DEGREES,
+            SettingSlider::BrickDescentInterval => {
+                MIN_BRICK_DESCENT_INTERVAL..=MAX_BRICK_DESCENT_INTERVAL
+            }
+            SettingSlider::HighlightWindow => 1.0..=MAX_HIGHLIGHT_WINDOW,
+            SettingSlider::TiltDegrees => 0.0..=MAX_TILT_DEGREES,
+            SettingSlider::HpBarMinStrength => 1.0..=MAX_HP_BAR_MIN_STRENGTH,
+            SettingSlider::ComboWindow => 0.5..=MAX_COMBO_WINDOW_SECS,
+            SettingSlider::CatchCapacity => 0.0..=MAX_CATCH_CAPACITY,
+            SettingSlider::NearMissSpeedup => 0.0..=MAX_NEAR_MISS_SPEEDUP,
+            SettingSlider::ComboWarning => 0.0..=1.0,
+        }
+    }
+
+    pub fn step(&self) -> f32 {
+        match self {
+            SettingSlider::CatchRadius => 1.,
+            SettingSlider::ServeCone => 5.,
+            SettingSlider::BallGravity => 0.1,
+            SettingSlider::ScoreCountUpSecs => 0.1,
+            SettingSlider::CrtScanlines => 0.05,
+            SettingSlider::CrtCurvature => 0.05,
+            SettingSlider::CrtVignette => 0.05,
+            SettingSlider::MusicVolume => 0.05,
+            SettingSlider::BounceJitter => 1.,
+            SettingSlider::BrickDescentInterval => 1.,
+            SettingSlider::HighlightWindow => 1.,
+            SettingSlider::TiltDegrees => 1.,
+            SettingSlider::HpBarMinStrength => 1.,
+            SettingSlider::ComboWindow => 0.5,
+            SettingSlider::CatchCapacity => 1.,
+            SettingSlider::NearMissSpeedup => 0.05,
+            SettingSlider::ComboWarning => 0.05,
+        }
+    }
+
+    pub fn value(&self, settings: &GameSettings) -> f32 {
+        match self {
+            SettingSlider::CatchRadius => settings.assist.catch_radius,
+            SettingSlider::ServeCone => settings.gameplay.serve_cone_degrees,
+            SettingSlider::BallGravity => settings.gameplay.ball_gravity,
+            SettingSlider::ScoreCountUpSecs => settings.visual.score_count_up_secs,
+            SettingSlider::CrtScanlines => settings.visual.crt.scanline_intensity,
+            SettingSlider::CrtCurvature => settings.visual.crt.curvature,
+            SettingSlider::CrtVignette => settings.visual.crt.vignette,
+            SettingSlider::MusicVolume => settings.audio.music_volume,
+            SettingSlider::BounceJitter => settings.gameplay.bounce_jitter_degrees,
+            SettingSlider::BrickDescentInterval => settings.gameplay.brick_descent_interval,
+            SettingSlider::HighlightWindow => settings.highlight.window_secs,
+            SettingSlider::TiltDegrees => settings.visual.tilt.degrees,
+            SettingSlider::HpBarMinStrength => settings.visual.hp_bar_min_strength as f32,
+            SettingSlider::ComboWindow => settings.combo.window_secs,
+            SettingSlider::CatchCapacity => settings.gameplay.catch_capacity as f32,
+            SettingSlider::NearMissSpeedup => settings.hardcore.near_miss_speedup,
+            SettingSlider::ComboWarning => settings.combo.warning_fraction,
+        }
+    }
+
+    pub fn set(&self, settings: &mut GameSettings, value: f32) {
+        match self {
+            SettingSlider::CatchRadius => settings.assist.catch_radius = value,
+            SettingSlider::ServeCone => settings.gameplay.serve_cone_degrees = value,
+            SettingSlider::BallGravity => settings.gameplay.ball_gravity = value,
+            SettingSlider::ScoreCountUpSecs => settings.visual.score_count_up_secs = value,
+            SettingSlider::CrtScanlines => settings.visual.crt.scanline_intensity = value,
+            SettingSlider::CrtCurvature => settings.visual.crt.curvature = value,
+            SettingSlider::CrtVignette => settings.visual.crt.vignette = value,
+            SettingSlider::MusicVolume => settings.audio.music_volume = value,
+            SettingSlider::BounceJitter => settings.gameplay.bounce_jitter_degrees = value,
+            SettingSlider::BrickDescentInterval => settings.gameplay.brick_descent_interval = value,
+            SettingSlider::HighlightWindow => settings.highlight.window_secs = value,
+            SettingSlider::TiltDegrees => settings.visual.tilt.degrees = value,
+            SettingSlider::HpBarMinStrength => {
+                settings.visual.hp_bar_min_strength = value.round() as u8
+            }
+            SettingSlider::ComboWindow => settings.combo.window_secs = value,
+            SettingSlider::CatchCapacity => {
+                settings.gameplay.catch_capacity = value.round() as usize
+            }
+            SettingSlider::NearMissSpeedup => settings.hardcore.near_miss_speedup = value,
+            SettingSlider::ComboWarning => settings.combo.warning_fraction = value,
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{Ball, BallSpeed, GameStateTransition, Velocity},
+    hud::HudElement,
+    profile::Profile,
+    stats::RunStats,
+};
+
+const SPEED_FONT_SIZE: f32 = 30.;
+const SPEED_LABEL_COLOR: Color = Color::rgb(0.5, 0.5, 1.0);
+const SPEED_VALUE_COLOR: Color = Color::rgb(1.0, 0.5, 0.5);
+const SPEED_RECORD_FONT_SIZE: f32 = 36.;
+const SPEED_RECORD_COLOR: Color = Color::rgb(1., 1., 1.);
+// How long the level's fastest speed is shown once the level ends
+const SPEED_RECORD_DURATION: f32 = 2.5;
+
+// The fastest any ball has gone this level, as a multiple of the base ball speed
+#[derive(Resource, Default)]
+pub struct FastestSpeed {
+    level: f32,
+}
+
+// Marker for the HUD text showing the fastest ball's current speed
+#[derive(Component)]
+pub struct SpeedDisplay;
+
+// Shows the level's fastest speed after the level ends, until the timer runs out
+#[derive(Component)]
+pub struct SpeedRecordText(Timer);
+
+pub fn format_speed(speed: f32) -> String {
+    format!("{speed:.2}x")
+}
+
+pub fn speed_display(top_left_placement: Vec2) -> impl Bundle {
+    (
+        TextBundle::from_sections([
+            TextSection::new(
+                "Speed: ",
+                TextStyle {
+                    font_size: SPEED_FONT_SIZE,
+                    color: SPEED_LABEL_COLOR,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                format_speed(1.),
+                TextStyle {
+                    font_size: SPEED_FONT_SIZE,
+                    color: SPEED_VALUE_COLOR,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(top_left_placement.y),
+            left: Val::Px(top_left_placement.x),
+            ..default()
+        }),
+        HudElement::Speed,
+        SpeedDisplay,
+        Name::new("SpeedDisplay"),
+    )
+}
+
+// Shows the fastest ball in play and keeps the level and run records up to date
+pub fn update_speed_display(
+    ball_q: Query<&Velocity, With<Ball>>,
+    mut text_q: Query<&mut Text, With<SpeedDisplay>>,
+    mut fastest: ResMut<FastestSpeed>,
+    mut stats: ResMut<RunStats>,
+    ball_speed: Res<BallSpeed>,
+) {
+    let Some(fastest_ball) = ball_q.iter().map(|v| v.length()).reduce(f32::max) else {
+        return; // Keep showing the last speed until the next serve
+    };
+    // Velocities are directions scaled by power-ups, the level sets the speed they move at
+    let speed = fastest_ball * ball_speed.multiple();
+    fastest.level = fastest.level.max(speed);
+    stats.fastest_speed = stats.fastest_speed.max(speed);
+    for mut text in text_q.iter_mut() {
+        text.sections[1].value = format_speed(speed);
+    }
+}
+
+// Shows the level's fastest speed when it is cleared, and saves any new all-time record whenever
+// the level ends, however it ends
+pub fn finish_level_speed(
+    mut commands: Commands,
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut fastest: ResMut<FastestSpeed>,
+    mut profile: ResMut<Profile>,
+) {
+    for msg in game_msgs.iter() {
+        let cleared = match msg {
+            GameStateTransition::NextLevel => true,
+            GameStateTransition::RestartLevel
+            | GameStateTransition::RestartGame
+            | GameStateTransition::ToGameOver => false,
+            _ => continue,
+        };
+        if cleared {
+            commands.spawn((
+                TextBundle::from_section(
+                    format!("Fastest ball: {}", format_speed(fastest.level)),
+                    TextStyle {
+                        font_size: SPEED_RECORD_FONT_SIZE,
+                        color: SPEED_RECORD_COLOR,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(45.),
+                    left: Val::Percent(38.),
+                    ..default()
+                }),
+                SpeedRecordText(Timer::from_seconds(SPEED_RECORD_DURATION, TimerMode::Once)),
+                Name::new("SpeedRecord"),
+            ));
+        }
+        if fastest.level > profile.fastest_speed {
+            profile.fastest_speed = fastest.level;
+            profile.save();
+        }
+        *fastest = FastestSpeed::default();
+    }
+}
+
+pub fn tick_speed_record_text(
+    mut commands: Commands,
+    mut record_q: Query<(Entity, &mut SpeedRecordText)>,
+    time: Res<Time>,
+) {
+    for (ent, mut record) in record_q.iter_mut() {
+        if record.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speed_app() -> App {
+        let mut app = App::new();
+        app.insert_resource(BallSpeed::new(1, 1.))
+            .init_resource::<FastestSpeed>()
+            .init_resource::<RunStats>()
+            .add_systems(Update, update_speed_display);
+        app.world.spawn(speed_display(Vec2::ZERO));
+        app
+    }
+
+    fn shown_speed(app: &mut App) -> String {
+        let mut text_q = app.world.query_filtered::<&Text, With<SpeedDisplay>>();
+        text_q.single(&app.world).sections[1].value.clone()
+    }
+
+    #[test]
+    fn the_display_follows_the_level_speed() {
+        let mut app = speed_app();
+        app.world.spawn((Ball, Velocity(Vec2::Y)));
+        app.update();
+        assert_eq!(shown_speed(&mut app), "1.00x");
+
+        app.world.resource_mut::<BallSpeed>().set_level(3);
+        app.update();
+        assert_eq!(shown_speed(&mut app), "1.20x");
+    }
+
+    #[test]
+    fn the_record_keeps_the_fastest_ball() {
+        let mut app = speed_app();
+        let ball = app.world.spawn((Ball, Velocity(Vec2::Y * 1.5))).id();
+        app.world.spawn((Ball, Velocity(Vec2::Y)));
+        app.update();
+        assert_eq!(shown_speed(&mut app), "1.50x");
+
+        // Slowing back down doesn't lower the record
+        app.world.get_mut::<Velocity>(ball).unwrap().0 = Vec2::Y;
+        app.update();
+        assert_eq!(shown_speed(&mut app), "1.00x");
+        assert_eq!(app.world.resource::<FastestSpeed>().level, 1.5);
+        assert_eq!(app.world.resource::<RunStats>().fastest_speed, 1.5);
+    }
+}
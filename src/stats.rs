@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::breaker::BrickDestroyed;
+
+// Statistics gathered over a single run, read by the end-of-run screen
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub levels_cleared: usize,
+    pub bricks_destroyed: usize,
+    pub current_combo: usize, // Bricks destroyed since the ball last touched the paddle
+    pub max_combo: usize,
+    pub balls_lost: usize,
+    pub elapsed_secs: f32,
+    pub assisted: bool,     // An assist saved the ball at least once
+    pub hardcore: bool,     // Hardcore runs are kept off the normal high scores
+    pub fastest_speed: f32, // As a multiple of the base ball speed
+    pub won: bool,          // Every level was cleared
+}
+
+impl RunStats {
+    // Every brick hit scores this many times the base points, growing with each brick broken
+    // before the ball returns to the paddle
+    pub fn combo_multiplier(&self) -> usize {
+        self.current_combo + 1
+    }
+
+    // A one line summary of the run, meant to be copied and shared
+    pub fn summary(&self, score: usize) -> String {
+        let minutes = (self.elapsed_secs / 60.).floor() as u32;
+        let seconds = (self.elapsed_secs % 60.).floor() as u32;
+        let mode = if self.hardcore { " | HARDCORE" } else { "" };
+        format!(
+            "Brick Hero: {score} pts | {} levels | {} bricks | {}x combo | {} balls lost | {minutes}:{seconds:02}{mode}",
+            self.levels_cleared, self.bricks_destroyed, self.max_combo, self.balls_lost,
+        )
+    }
+}
+
+pub fn track_run_time(mut stats: ResMut<RunStats>, time: Res<Time>) {
+    stats.elapsed_secs += time.delta_seconds();
+}
+
+pub fn track_destroyed_bricks(
+    mut stats: ResMut<RunStats>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+) {
+    // The combo itself is counted as each brick breaks, so the next hit in the same tick already
+    // scores with the raised multiplier
+    for _ in destroyed_events.iter() {
+        stats.bricks_destroyed += 1;
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    ball_tints::{ball_color, BallTint},
+    breaker::{game_running, Ball},
+    elements::BallElement,
+    powerups::{ApplyPowerUp, PowerUpKind},
+    settings::GameSettings,
+    theme::ThemeColors,
+};
+
+const STRONG_BALL_DURATION: f32 = 8.;
+// Strong balls take this much strength off each brick they hit, instead of 1
+pub const STRONG_BALL_DAMAGE: u8 = 2;
+const STRONG_BALL_COLOR: Color = Color::rgb(1., 0.45, 0.1);
+
+pub struct StrongBallPlugin;
+
+impl Plugin for StrongBallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                grant_strong_balls,
+                tick_strong_balls
+                    .after(grant_strong_balls)
+                    .run_if(game_running),
+            )
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// While the timer runs the ball deals STRONG_BALL_DAMAGE to every brick it hits
+#[derive(Component)]
+pub struct StrongBall(pub Timer);
+
+impl Default for StrongBall {
+    fn default() -> Self {
+        StrongBall(Timer::from_seconds(STRONG_BALL_DURATION, TimerMode::Once))
+    }
+}
+
+// The damage a ball deals to each brick it hits
+pub fn ball_damage(strong: Option<&StrongBall>) -> u8 {
+    match strong {
+        Some(_) => STRONG_BALL_DAMAGE,
+        None => 1,
+    }
+}
+
+// The strong ball power-up makes every ball in play strong, restarting the timer of any that
+// already were
+pub fn grant_strong_balls(
+    mut commands: Commands,
+    mut power_ups: EventReader<ApplyPowerUp>,
+    ball_q: Query<(Entity, &Handle<ColorMaterial>), With<Ball>>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+) {
+    if !power_ups
+        .iter()
+        .any(|ApplyPowerUp(kind)| *kind == PowerUpKind::StrongBall)
+    {
+        return;
+    }
+    for (ball, handle) in ball_q.iter() {
+        commands.entity(ball).insert(StrongBall::default());
+        if let Some(mat) = mats.get_mut(handle) {
+            mat.color = STRONG_BALL_COLOR;
+        }
+    }
+}
+
+// Turns strong balls back into normal ones once their timer runs out, in the color they'd have
+// without it
+pub fn tick_strong_balls(
+    mut commands: Commands,
+    mut ball_q: Query<(
+        Entity,
+        &mut StrongBall,
+        &Handle<ColorMaterial>,
+        Option<&BallTint>,
+        Option<&BallElement>,
+    )>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    theme: Res<ThemeColors>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    for (ball, mut strong, handle, tint, element) in ball_q.iter_mut() {
+        if !strong.0.tick(time.delta()).finished() {
+            continue;
+        }
+        commands.entity(ball).remove::<StrongBall>();
+        if let Some(mat) = mats.get_mut(handle) {
+            mat.color = match element {
+                Some(element) => element.element.color(),
+                None => ball_color(tint, &settings, theme.ball),
+            };
+        }
+    }
+}
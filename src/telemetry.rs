@@ -0,0 +1,192 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use bevy::{app::AppExit, prelude::*};
+use serde::Serialize;
+
+use crate::{
+    breaker::{BrickDestroyed, CollisionEvent, CollisionKind, GameStateTransition, PlayerMessage},
+    stats::RunStats,
+};
+
+const DEFAULT_TELEMETRY_PATH: &str = "telemetry.jsonl";
+// Buffered lines are written out this often, keeping file IO out of the gameplay frames
+const FLUSH_INTERVAL: f32 = 1.0;
+
+// Opt-in playtest logging, enabled with `--telemetry [path]`
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(path) = telemetry_path_from_args() else {
+            return; // Nothing is registered without the flag, so there is no overhead
+        };
+        match TelemetrySink::create(path.clone()) {
+            Ok(sink) => {
+                info!("Writing telemetry to {}", path.display());
+                app.insert_resource(sink)
+                    .add_systems(
+                        Update,
+                        (
+                            record_collisions,
+                            record_brick_breaks,
+                            record_ball_losses,
+                            record_level_clears,
+                            flush_telemetry.after(record_level_clears),
+                        ),
+                    )
+                    .add_systems(Last, flush_telemetry_on_exit);
+            }
+            Err(e) => warn!("Failed to create {}, telemetry disabled: {e}", path.display()),
+        }
+    }
+}
+
+fn telemetry_path_from_args() -> Option<PathBuf> {
+    let args: Vec<String> = std::env::args().collect();
+    let flag = args.iter().position(|arg| arg == "--telemetry")?;
+    let path = args
+        .get(flag + 1)
+        .filter(|value| !value.starts_with("--"))
+        .map_or(DEFAULT_TELEMETRY_PATH, String::as_str);
+    Some(PathBuf::from(path))
+}
+
+// One line of the telemetry file
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum TelemetryEvent {
+    BrickHit { position: [f32; 2], strength: u8 },
+    BrickBreak { position: [f32; 2], kind: String },
+    PaddleHit { offset: f32 },
+    BallLost,
+    LevelClear { level_secs: f32 },
+}
+
+#[derive(Serialize)]
+struct TelemetryLine<'a> {
+    time: f32, // Seconds into the run
+    #[serde(flatten)]
+    event: &'a TelemetryEvent,
+}
+
+// Collects serialized events in memory and writes them out in batches
+#[derive(Resource)]
+pub struct TelemetrySink {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    pending: Vec<String>,
+    flush_timer: Timer,
+    last_level_clear: f32,
+}
+
+impl TelemetrySink {
+    fn create(path: PathBuf) -> std::io::Result<Self> {
+        Ok(TelemetrySink {
+            writer: BufWriter::new(File::create(&path)?),
+            path,
+            pending: vec![],
+            flush_timer: Timer::from_seconds(FLUSH_INTERVAL, TimerMode::Repeating),
+            last_level_clear: 0.,
+        })
+    }
+
+    fn record(&mut self, time: f32, event: TelemetryEvent) {
+        match serde_json::to_string(&TelemetryLine {
+            time,
+            event: &event,
+        }) {
+            Ok(line) => self.pending.push(line),
+            Err(e) => warn!("Failed to encode telemetry event: {e}"),
+        }
+    }
+
+    fn flush(&mut self) {
+        let result = self
+            .pending
+            .drain(..)
+            .try_for_each(|line| writeln!(self.writer, "{line}"))
+            .and_then(|_| self.writer.flush());
+        if let Err(e) = result {
+            warn!("Failed to write telemetry to {}: {e}", self.path.display());
+        }
+    }
+}
+
+fn record_collisions(
+    mut sink: ResMut<TelemetrySink>,
+    mut collision_events: EventReader<CollisionEvent>,
+    stats: Res<RunStats>,
+) {
+    for collision in collision_events.iter() {
+        let event = match collision.kind {
+            CollisionKind::Brick { position, strength } => TelemetryEvent::BrickHit {
+                position: position.truncate().to_array(),
+                strength,
+            },
+            CollisionKind::Paddle { offset } => TelemetryEvent::PaddleHit { offset },
+            CollisionKind::Wall | CollisionKind::Drain => continue,
+        };
+        sink.record(stats.elapsed_secs, event);
+    }
+}
+
+fn record_brick_breaks(
+    mut sink: ResMut<TelemetrySink>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    stats: Res<RunStats>,
+) {
+    for destroyed in destroyed_events.iter() {
+        sink.record(
+            stats.elapsed_secs,
+            TelemetryEvent::BrickBreak {
+                position: destroyed.position.truncate().to_array(),
+                kind: format!("{:?}", destroyed.kind),
+            },
+        );
+    }
+}
+
+fn record_ball_losses(
+    mut sink: ResMut<TelemetrySink>,
+    mut player_msgs: EventReader<PlayerMessage>,
+    stats: Res<RunStats>,
+) {
+    for msg in player_msgs.iter() {
+        match msg {
+            PlayerMessage::JustLostHealth => {
+                sink.record(stats.elapsed_secs, TelemetryEvent::BallLost)
+            }
+        }
+    }
+}
+
+fn record_level_clears(
+    mut sink: ResMut<TelemetrySink>,
+    mut game_msgs: EventReader<GameStateTransition>,
+    stats: Res<RunStats>,
+) {
+    for msg in game_msgs.iter() {
+        if let GameStateTransition::NextLevel = msg {
+            // A new run restarts the clock, so never measure from a clear in an older run
+            let level_secs = stats.elapsed_secs - sink.last_level_clear.min(stats.elapsed_secs);
+            sink.last_level_clear = stats.elapsed_secs;
+            sink.record(stats.elapsed_secs, TelemetryEvent::LevelClear { level_secs });
+        }
+    }
+}
+
+fn flush_telemetry(mut sink: ResMut<TelemetrySink>, time: Res<Time>) {
+    if sink.flush_timer.tick(time.delta()).just_finished() {
+        sink.flush();
+    }
+}
+
+fn flush_telemetry_on_exit(mut sink: ResMut<TelemetrySink>, exit_events: EventReader<AppExit>) {
+    if !exit_events.is_empty() {
+        sink.flush();
+    }
+}
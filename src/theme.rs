@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app_state::AppState,
+    ball_tints::{ball_color, BallTint},
+    breaker::{Ball, Paddle},
+    elements::BallElement,
+    hazards::Frozen,
+    settings::GameSettings,
+    ui::Rebinding,
+    walls::Wall,
+};
+
+pub const THEME_CYCLE_KEY: KeyCode = KeyCode::T;
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Theme::Classic.colors())
+            .add_systems(Startup, load_theme)
+            .add_systems(
+                Update,
+                (
+                    cycle_theme.run_if(
+                        state_exists_and_equals(AppState::MainMenu)
+                            .or_else(state_exists_and_equals(AppState::InGame)),
+                    ),
+                    apply_theme.after(cycle_theme),
+                ),
+            );
+    }
+}
+
+// The built in color themes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Classic,
+    Synthwave,
+    Mono,
+}
+
+impl Theme {
+    pub fn colors(&self) -> ThemeColors {
+        match self {
+            Theme::Classic => ThemeColors {
+                background: Color::rgb(0.9, 0.9, 0.9),
+                wall: Color::rgb(0.8, 0.8, 0.8),
+                paddle: Color::rgb(0.3, 0.3, 0.7),
+                ball: Color::rgb(1., 0.5, 0.5),
+            },
+            Theme::Synthwave => ThemeColors {
+                background: Color::rgb(0.08, 0.02, 0.15),
+                wall: Color::rgb(1., 0.2, 0.8),
+                paddle: Color::rgb(0.2, 0.9, 1.),
+                ball: Color::rgb(1., 0.85, 0.2),
+            },
+            Theme::Mono => ThemeColors {
+                background: Color::rgb(0.1, 0.1, 0.1),
+                wall: Color::rgb(0.6, 0.6, 0.6),
+                paddle: Color::rgb(0.9, 0.9, 0.9),
+                ball: Color::WHITE,
+            },
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Synthwave => "Synthwave",
+            Theme::Mono => "Mono",
+        }
+    }
+
+    pub fn next(&self) -> Theme {
+        match self {
+            Theme::Classic => Theme::Synthwave,
+            Theme::Synthwave => Theme::Mono,
+            Theme::Mono => Theme::Classic,
+        }
+    }
+}
+
+// The colors of everything in the arena that isn't a brick
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct ThemeColors {
+    pub background: Color,
+    pub wall: Color,
+    pub paddle: Color,
+    pub ball: Color,
+}
+
+fn load_theme(settings: Res<GameSettings>, mut colors: ResMut<ThemeColors>) {
+    *colors = settings.visual.theme.colors();
+}
+
+// Switches to the next theme and remembers the choice. The key is left alone while the controls
+// screen waits for a key to rebind, since it may be the key being bound
+fn cycle_theme(
+    keys: Res<Input<KeyCode>>,
+    rebinding: Res<Rebinding>,
+    mut settings: ResMut<GameSettings>,
+    mut colors: ResMut<ThemeColors>,
+) {
+    if keys.just_pressed(THEME_CYCLE_KEY) && !rebinding.is_waiting() {
+        settings.visual.theme = settings.visual.theme.next();
+        settings.save();
+        *colors = settings.visual.theme.colors();
+        info!("Theme changed to {:?}", settings.visual.theme);
+    }
+}
+
+// Recolors the background and any existing walls, paddles, and balls when the theme changes
+fn apply_theme(
+    colors: Res<ThemeColors>,
+    mut clear_color: ResMut<ClearColor>,
+    mut wall_q: Query<&mut Sprite, (With<Wall>, Without<Paddle>)>,
+    mut paddle_q: Query<&mut Sprite, (With<Paddle>, Without<Frozen>, Without<Wall>)>,
+    ball_q: Query<(&Handle<ColorMaterial>, Option<&BallTint>), (With<Ball>, Without<BallElement>)>,
+    mut mats: ResMut<Assets<ColorMaterial>>,
+    settings: Res<GameSettings>,
+) {
+    if !colors.is_changed() {
+        return;
+    }
+    clear_color.0 = colors.background;
+    for mut sprite in wall_q.iter_mut() {
+        sprite.color = colors.wall;
+    }
+    for mut sprite in paddle_q.iter_mut() {
+        sprite.color = colors.paddle; // Frozen paddles pick this up when they thaw
+    }
+    // Likewise elemental balls pick up the theme color once their element expires
+    for (handle, tint) in ball_q.iter() {
+        if let Some(mat) = mats.get_mut(handle) {
+            mat.color = ball_color(tint, &settings, colors.ball);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{key_bindings::Action, misc::test_utils::test_app};
+
+    fn theme_app() -> App {
+        let mut app = test_app();
+        app.add_state::<AppState>()
+            .init_resource::<ClearColor>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<Rebinding>()
+            .add_plugins(ThemePlugin);
+        app.update();
+        app
+    }
+
+    fn press_theme_key(app: &mut App) {
+        let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+        keys.release(THEME_CYCLE_KEY);
+        keys.clear();
+        keys.press(THEME_CYCLE_KEY);
+        app.update();
+    }
+
+    #[test]
+    fn switching_themes_updates_the_clear_color() {
+        let mut app = theme_app();
+        assert_eq!(
+            app.world.resource::<ClearColor>().0,
+            Theme::Classic.colors().background
+        );
+
+        press_theme_key(&mut app);
+        assert_eq!(
+            app.world.resource::<GameSettings>().visual.theme,
+            Theme::Synthwave
+        );
+        assert_eq!(
+            app.world.resource::<ClearColor>().0,
+            Theme::Synthwave.colors().background
+        );
+    }
+
+    #[test]
+    fn theme_key_is_ignored_while_waiting_to_rebind() {
+        let mut app = theme_app();
+        app.world
+            .resource_mut::<Rebinding>()
+            .start(Action::MoveLeft, 0);
+        press_theme_key(&mut app);
+        assert_eq!(
+            app.world.resource::<GameSettings>().visual.theme,
+            Theme::Classic
+        );
+    }
+}
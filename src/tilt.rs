@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::GameSettings;
+
+const DEFAULT_TILT_DEGREES: f32 = 12.;
+pub const MAX_TILT_DEGREES: f32 = 30.;
+// The camera orbits the center of the arena at this distance, far enough that the tilted arena
+// stays between its near and far planes
+const CAMERA_DISTANCE: f32 = 1000.;
+
+// An optional pseudo 3D look, the arena is viewed from a little below its bottom edge. Only the
+// camera moves, so the game itself stays flat and collisions are exactly the same either way
+pub struct TiltPlugin;
+
+impl Plugin for TiltPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_camera_tilt);
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TiltSettings {
+    pub enabled: bool,
+    // How far the arena leans back, in degrees up to 30
+    pub degrees: f32,
+}
+
+impl Default for TiltSettings {
+    fn default() -> Self {
+        TiltSettings {
+            enabled: false,
+            degrees: DEFAULT_TILT_DEGREES,
+        }
+    }
+}
+
+impl TiltSettings {
+    // Looks at the arena's center from the tilt angle, straight on when the tilt is off
+    pub fn camera_transform(&self) -> Transform {
+        let degrees = if self.enabled {
+            self.degrees.clamp(0., MAX_TILT_DEGREES)
+        } else {
+            0.
+        };
+        let rotation = Quat::from_rotation_x(degrees.to_radians());
+        Transform::from_translation(rotation * Vec3::new(0., 0., CAMERA_DISTANCE))
+            .with_rotation(rotation)
+    }
+}
+
+// The 2D camera the game is drawn with, far enough back for any tilt
+pub fn game_camera() -> Camera2dBundle {
+    Camera2dBundle::new_with_far(2. * CAMERA_DISTANCE)
+}
+
+// Keeps every 2D camera at the tilt in the settings, cameras are respawned with each run
+pub fn apply_camera_tilt(
+    settings: Res<GameSettings>,
+    mut camera_q: Query<(&mut Transform, Ref<Camera2d>)>,
+) {
+    for (mut tform, camera) in camera_q.iter_mut() {
+        if camera.is_added() || settings.is_changed() {
+            *tform = settings.camera_transform();
+        }
+    }
+}
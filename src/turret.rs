@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::{
+    breaker::{serve_direction, Ball, BallSpawner, PADDLE_DIST_FROM_BOTTOM_WALL},
+    key_bindings::Action,
+    misc::rng::GameRng,
+    settings::GameSettings,
+    theme::ThemeColors,
+    walls::{BOTTOM_WALL, WALL_THICKNESS},
+};
+
+const TURRET_SIZE: Vec3 = Vec3::new(40., 16., 0.);
+const TURRET_COOLDOWN: f32 = 3.;
+// Balls are launched from just above the paddle, so they don't start out tangled with it
+const TURRET_LAUNCH_Y: f32 = BOTTOM_WALL + PADDLE_DIST_FROM_BOTTOM_WALL + 40.;
+// How bright the turret is while it is cooling down, relative to when it is ready
+const TURRET_COOLING_ALPHA: f32 = 0.35;
+
+// Turret mode's launcher, sitting on the bottom wall under the paddle. It fires a fresh ball up
+// into play on demand, then has to cool down before it can fire again
+#[derive(Component)]
+pub struct Turret {
+    cooldown: Timer,
+}
+
+impl Default for Turret {
+    fn default() -> Self {
+        // Starts ready to fire
+        let mut cooldown = Timer::from_seconds(TURRET_COOLDOWN, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+        Turret { cooldown }
+    }
+}
+
+impl Turret {
+    pub fn ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+}
+
+pub fn turret_bundle(color: Color) -> impl Bundle {
+    (
+        SpriteBundle {
+            transform: Transform {
+                translation: Vec3::new(0., BOTTOM_WALL + (WALL_THICKNESS + TURRET_SIZE.y) / 2., 0.),
+                scale: TURRET_SIZE,
+                ..default()
+            },
+            sprite: Sprite { color, ..default() },
+            ..default()
+        },
+        Turret::default(),
+        Name::new("Turret"),
+    )
+}
+
+// Launches a ball within the serve cone when the fire key is pressed and the turret is ready. A
+// shot that would go over the ball limit is not taken, leaving the turret ready
+#[allow(clippy::too_many_arguments)]
+pub fn fire_turret(
+    mut commands: Commands,
+    mut balls: BallSpawner,
+    mut turret_q: Query<(&mut Turret, &Transform, &mut Sprite)>,
+    ball_q: Query<(), With<Ball>>,
+    keys: Res<Input<KeyCode>>,
+    theme: Res<ThemeColors>,
+    mut rng: ResMut<GameRng>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let fire = settings
+        .controls
+        .bindings
+        .just_pressed(Action::FireTurret, &keys);
+    for (mut turret, tform, mut sprite) in turret_q.iter_mut() {
+        turret.cooldown.tick(time.delta());
+        if fire && turret.ready() {
+            let position = Vec3::new(tform.translation.x, TURRET_LAUNCH_Y, 1.);
+            let launched = balls.spawn_ball(
+                &mut commands,
+                ball_q.iter().count(),
+                theme.ball,
+                position,
+                serve_direction(&mut rng, &settings),
+            );
+            if launched.is_some() {
+                turret.cooldown.reset();
+            }
+        }
+        let alpha = if turret.ready() {
+            1.
+        } else {
+            TURRET_COOLING_ALPHA
+        };
+        sprite.color = theme.paddle.with_a(alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ball_tints::BallTints,
+        breaker::BallConfig,
+        misc::test_utils::{advance_time, test_app},
+        theme::Theme,
+    };
+
+    fn turret_app(max_balls: usize) -> App {
+        let mut app = test_app();
+        app.insert_resource(Theme::Classic.colors())
+            .insert_resource(BallConfig {
+                max_balls,
+                ..default()
+            })
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<BallTints>()
+            .add_systems(Update, fire_turret);
+        app.world.spawn(turret_bundle(Color::WHITE));
+        app
+    }
+
+    // Presses fire and runs a frame, secs after the last one
+    fn fire(app: &mut App, secs: f32) {
+        let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+        keys.reset_all();
+        keys.press(KeyCode::Space);
+        advance_time(app, secs);
+        app.update();
+    }
+
+    fn ball_count(app: &mut App) -> usize {
+        let mut ball_q = app.world.query_filtered::<(), With<Ball>>();
+        ball_q.iter(&app.world).count()
+    }
+
+    #[test]
+    fn the_turret_waits_out_its_cooldown_between_shots() {
+        let mut app = turret_app(3);
+        fire(&mut app, 0.);
+        assert_eq!(ball_count(&mut app), 1);
+
+        fire(&mut app, TURRET_COOLDOWN / 2.);
+        assert_eq!(ball_count(&mut app), 1);
+
+        fire(&mut app, TURRET_COOLDOWN / 2.);
+        assert_eq!(ball_count(&mut app), 2);
+    }
+
+    #[test]
+    fn the_turret_holds_its_shot_at_the_ball_limit() {
+        let mut app = turret_app(1);
+        fire(&mut app, 0.);
+        fire(&mut app, TURRET_COOLDOWN);
+        assert_eq!(ball_count(&mut app), 1);
+
+        let mut turret_q = app.world.query::<&Turret>();
+        assert!(turret_q.single(&app.world).ready());
+    }
+}
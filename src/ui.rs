@@ -1,56 +1,407 @@
-use bevy::prelude::*;
+use bevy::{
+    input::{keyboard::KeyboardInput, ButtonState},
+    prelude::*,
+};
 use bevy_iced::iced::{
     alignment::{Horizontal, Vertical},
-    widget::{text, Button, Column, Container},
+    widget::{scrollable, slider, text, text_input, Button, Column, Container, Row},
     Alignment, Length,
 };
 use bevy_iced::{IcedContext, IcedPlugin};
 
-use crate::app_state::{AppState, AppStateTransition};
+use crate::{
+    app_state::{AppState, AppStateTransition},
+    breaker::{
+        CurrentState, GameState, GameStateTransition, PaddleEdgeMode, PaddleInput, PaddleTiming,
+    },
+    bricks::BrickPalette,
+    difficulty::Difficulty,
+    gamepad::GamepadBindings,
+    high_scores::HighScores,
+    highlight::HighlightPlayer,
+    infinite::GameMode,
+    key_bindings::{Action, BindError, KeyBindings, BINDING_SLOTS},
+    misc::{clipboard::Clipboard, rng::GameRng},
+    profile::Profile,
+    scoreboard::Scoreboard,
+    settings::{ControlSettings, GameSettings, SettingSlider, SettingToggle},
+    speed::format_speed,
+    stats::RunStats,
+    theme::{Theme, ThemeColors},
+    walls::{ArenaShape, DrainLayout},
+};
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(IcedPlugin::default()).add_systems(
-            Update,
-            (menu_sys.run_if(
-                state_exists_and_equals(AppState::MainMenu)
-                    .or_else(state_exists_and_equals(AppState::GameOver)),
-            ),),
-        );
+        app.add_plugins(IcedPlugin::default())
+            .add_event::<MenuMessage>()
+            .init_resource::<SeedInput>()
+            .init_resource::<MenuScreen>()
+            .init_resource::<Rebinding>()
+            .init_non_send_resource::<Clipboard>()
+            .add_systems(
+                Update,
+                (
+                    handle_menu_messages,
+                    capture_rebind
+                        .after(handle_menu_messages)
+                        .run_if(state_exists_and_equals(AppState::MainMenu)),
+                    menu_sys.run_if(
+                        state_exists_and_equals(AppState::MainMenu)
+                            .or_else(state_exists_and_equals(AppState::GameOver)),
+                    ),
+                    pause_menu
+                        .run_if(state_exists_and_equals(AppState::InGame))
+                        .run_if(resource_equals(CurrentState(GameState::Paused))),
+                ),
+            );
+    }
+}
+
+// Messages sent by the menus, app and game state changes are passed on as AppStateTransitions
+// and GameStateTransitions
+#[derive(Event, Clone, Debug)]
+pub enum MenuMessage {
+    Transition(AppStateTransition),
+    Game(GameStateTransition),
+    SeedChanged(String),
+    ShowScreen(MenuScreen),
+    Rebind(Action, usize), // Waits for a key to bind to the action's slot
+    ResetBindings,
+    WatchHighlight,
+    StopHighlight,
+    VolumeChanged(f32),
+    SetDifficulty(Difficulty),
+    SetGameMode(GameMode),
+    SetTheme(Theme),
+    SetBrickPalette(BrickPalette),
+    SetPaddleEdgeMode(PaddleEdgeMode),
+    SetPaddleTiming(PaddleTiming),
+    SetPaddleInput(PaddleInput),
+    SetArenaShape(ArenaShape),
+    SetDrainLayout(DrainLayout),
+    SetGamepadButton(Action, GamepadButtonType),
+    ToggleSetting(SettingToggle),
+    SliderChanged(SettingSlider, f32),
+    SaveSettings, // Sent once a slider is let go, rather than saving on every step of a drag
+    CopySeed,
+}
+
+// The screen shown while in the main menu
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuScreen {
+    #[default]
+    Main,
+    Controls,
+    Settings,
+}
+
+// The binding slot waiting for the next key press, and the outcome of the last rebind
+#[derive(Resource, Default)]
+pub struct Rebinding {
+    waiting: Option<(Action, usize)>,
+    status: String,
+}
+
+impl Rebinding {
+    // Waits for the next key press to bind to the action's slot
+    pub fn start(&mut self, action: Action, slot: usize) {
+        self.waiting = Some((action, slot));
+        self.status = format!("Press a key for {}", action.label());
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.waiting.is_some()
     }
 }
 
+// The seed typed into the main menu, an empty input starts a run with a random seed
+#[derive(Resource, Default)]
+pub struct SeedInput(String);
+
+impl SeedInput {
+    // Ok(None) when the input is empty
+    fn parse(&self) -> Result<Option<u64>, std::num::ParseIntError> {
+        let input = self.0.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        input.parse().map(Some)
+    }
+}
+
+// Applies menu input, seeding the GameRng right before a run starts so the run can be reproduced
+#[allow(clippy::too_many_arguments)]
+pub fn handle_menu_messages(
+    mut menu_msgs: EventReader<MenuMessage>,
+    mut app_state_msgs: EventWriter<AppStateTransition>,
+    mut game_state_msgs: EventWriter<GameStateTransition>,
+    mut seed_input: ResMut<SeedInput>,
+    mut rng: ResMut<GameRng>,
+    mut screen: ResMut<MenuScreen>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<GameSettings>,
+    mut highlight: ResMut<HighlightPlayer>,
+    mut difficulty: ResMut<Difficulty>,
+    mut mode: ResMut<GameMode>,
+    mut theme_colors: ResMut<ThemeColors>,
+    mut clipboard: NonSendMut<Clipboard>,
+) {
+    for msg in menu_msgs.iter() {
+        match msg {
+            MenuMessage::Transition(transition) => {
+                if let AppStateTransition::ToInGame = transition {
+                    *rng = match seed_input.parse() {
+                        Ok(Some(seed)) => GameRng::from_seed(seed),
+                        _ => GameRng::default(),
+                    };
+                    info!("Starting run with seed {}", rng.seed());
+                }
+                app_state_msgs.send(transition.clone());
+            }
+            MenuMessage::Game(transition) => game_state_msgs.send(transition.clone()),
+            MenuMessage::SeedChanged(input) => seed_input.0 = input.clone(),
+            MenuMessage::ShowScreen(shown) => {
+                *screen = *shown;
+                *rebinding = Rebinding::default();
+            }
+            MenuMessage::Rebind(action, slot) => rebinding.start(*action, *slot),
+            MenuMessage::ResetBindings => {
+                settings.controls.bindings = KeyBindings::default();
+                settings.controls.gamepad = GamepadBindings::default();
+                settings.save();
+                *rebinding = Rebinding {
+                    waiting: None,
+                    status: "Controls reset to defaults".to_string(),
+                };
+            }
+            MenuMessage::WatchHighlight => highlight.play(),
+            MenuMessage::StopHighlight => highlight.stop(),
+            MenuMessage::VolumeChanged(volume) => settings.audio.master_volume = *volume,
+            MenuMessage::SetDifficulty(chosen) => *difficulty = *chosen,
+            MenuMessage::SetGameMode(chosen) => *mode = *chosen,
+            MenuMessage::SetTheme(theme) => {
+                settings.visual.theme = *theme;
+                settings.save();
+                *theme_colors = theme.colors();
+            }
+            MenuMessage::SetBrickPalette(palette) => {
+                settings.visual.brick_palette = *palette;
+                settings.save();
+            }
+            MenuMessage::SetPaddleEdgeMode(edge_mode) => {
+                settings.gameplay.paddle_edge = *edge_mode;
+                settings.save();
+            }
+            MenuMessage::SetPaddleTiming(timing) => {
+                settings.controls.paddle_timing = *timing;
+                settings.save();
+            }
+            MenuMessage::SetPaddleInput(input) => {
+                settings.controls.paddle_input = *input;
+                settings.save();
+            }
+            MenuMessage::SetArenaShape(shape) => {
+                settings.gameplay.arena_shape = *shape;
+                settings.save();
+            }
+            MenuMessage::SetDrainLayout(layout) => {
+                settings.gameplay.drain_layout = *layout;
+                settings.save();
+            }
+            MenuMessage::SetGamepadButton(action, pad_button) => {
+                settings.controls.gamepad.set(*action, *pad_button);
+                settings.save();
+            }
+            MenuMessage::ToggleSetting(toggle) => {
+                let on = toggle.is_on(&settings);
+                toggle.set(&mut settings, !on);
+                settings.save();
+            }
+            MenuMessage::SliderChanged(setting, value) => setting.set(&mut settings, *value),
+            MenuMessage::SaveSettings => settings.save(),
+            MenuMessage::CopySeed => clipboard.copy(rng.seed().to_string()),
+        }
+    }
+}
+
+// Binds the next pressed key to the slot waiting on the controls screen, unless it is reserved
+// or already bound elsewhere
+pub fn capture_rebind(
+    mut key_events: EventReader<KeyboardInput>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<GameSettings>,
+) {
+    let Some((action, slot)) = rebinding.waiting else {
+        key_events.clear(); // Keys pressed before a slot was picked never count
+        return;
+    };
+    let pressed = key_events
+        .iter()
+        .filter(|input| input.state == ButtonState::Pressed)
+        .find_map(|input| input.key_code);
+    let Some(key) = pressed else {
+        return;
+    };
+
+    rebinding.waiting = None;
+    rebinding.status = match settings.controls.bindings.bind(action, slot, key) {
+        Ok(()) => {
+            settings.save();
+            format!("{} bound to {key:?}", action.label())
+        }
+        Err(BindError::Reserved) => format!("{key:?} is reserved and can't be bound"),
+        Err(BindError::Duplicate(other)) => {
+            format!("{key:?} is already bound to {}", other.label())
+        }
+    };
+}
+
 // This is registered to run only if MainMenuToggle has a true value
-pub fn menu_sys(mut ctx: IcedContext<AppStateTransition>, state: Res<State<AppState>>) {
+#[allow(clippy::too_many_arguments)]
+pub fn menu_sys(
+    mut ctx: IcedContext<MenuMessage>,
+    state: Res<State<AppState>>,
+    stats: Res<RunStats>,
+    scoreboard: Res<Scoreboard>,
+    seed_input: Res<SeedInput>,
+    screen: Res<MenuScreen>,
+    rebinding: Res<Rebinding>,
+    settings: Res<GameSettings>,
+    profile: Res<Profile>,
+    highlight: Res<HighlightPlayer>,
+    high_scores: Res<HighScores>,
+    difficulty: Res<Difficulty>,
+    mode: Res<GameMode>,
+) {
     let curr_state = state.get();
     match curr_state {
         AppState::InGame => panic!("menu_sys executed while playing"),
-        AppState::MainMenu => main_menu(&mut ctx),
-        _ => {} // TODO: Implement Game over
+        AppState::MainMenu => match *screen {
+            MenuScreen::Main => main_menu(&mut ctx, &seed_input, &high_scores, *difficulty, *mode),
+            MenuScreen::Controls => controls_menu(&mut ctx, &settings.controls, &rebinding),
+            MenuScreen::Settings => settings_menu(&mut ctx, &settings),
+        },
+        AppState::GameOver if highlight.playing() => highlight_controls(&mut ctx),
+        AppState::GameOver => game_over(
+            &mut ctx,
+            &stats,
+            &scoreboard,
+            &profile,
+            highlight.available(),
+        ),
+        _ => {}
     };
 }
 
-fn main_menu(ctx: &mut IcedContext<AppStateTransition>) {
+fn main_menu(
+    ctx: &mut IcedContext<MenuMessage>,
+    seed_input: &SeedInput,
+    high_scores: &HighScores,
+    difficulty: Difficulty,
+    mode: GameMode,
+) {
+    let valid_seed = seed_input.parse().is_ok();
+
     // Make all buttons
-    let start_button = Button::new(
+    let mut start_button = Button::new(
         text("Start Game")
             .horizontal_alignment(Horizontal::Center)
             .vertical_alignment(Vertical::Center),
     )
-    .on_press(AppStateTransition::ToInGame)
     .width(150.)
     .height(50.);
+    // Stays disabled until the seed is fixed or cleared
+    if valid_seed {
+        start_button = start_button.on_press(MenuMessage::Transition(AppStateTransition::ToInGame));
+    }
     let quit_button = Button::new(
         text("Exit")
             .horizontal_alignment(Horizontal::Center)
             .vertical_alignment(Vertical::Center),
     )
-    .on_press(AppStateTransition::ToExit)
+    .on_press(MenuMessage::Transition(AppStateTransition::ToExit))
+    .width(150.)
+    .height(50.);
+    let controls_button = Button::new(
+        text("Controls")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .on_press(MenuMessage::ShowScreen(MenuScreen::Controls))
+    .width(150.)
+    .height(50.);
+    let settings_button = Button::new(
+        text("Settings")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .on_press(MenuMessage::ShowScreen(MenuScreen::Settings))
     .width(150.)
     .height(50.);
 
+    // The chosen difficulty can't be pressed again, showing which one is picked
+    let mut difficulty_row = Row::new().spacing(10);
+    for choice in Difficulty::ALL {
+        let mut button = Button::new(
+            text(choice.label())
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .width(100.)
+        .height(40.);
+        if choice != difficulty {
+            button = button.on_press(MenuMessage::SetDifficulty(choice));
+        }
+        difficulty_row = difficulty_row.push(button);
+    }
+
+    // Same for the chosen mode
+    let mut mode_row = Row::new().spacing(10);
+    for choice in GameMode::ALL {
+        let mut button = Button::new(
+            text(choice.label())
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .width(100.)
+        .height(40.);
+        if choice != mode {
+            button = button.on_press(MenuMessage::SetGameMode(choice));
+        }
+        mode_row = mode_row.push(button);
+    }
+
+    // Entering a seed shared by another player replays their run's randomness
+    let seed_field = text_input("Seed (random if empty)", &seed_input.0)
+        .on_input(MenuMessage::SeedChanged)
+        .width(250.);
+    let seed_status = if valid_seed {
+        text("")
+    } else {
+        text("Seeds must be whole numbers").size(16)
+    };
+
+    // The best runs so far, scrolling once the list outgrows its space
+    let mut high_score_list = Column::new().spacing(5);
+    for (rank, entry) in high_scores.entries.iter().enumerate() {
+        high_score_list = high_score_list.push(
+            text(format!(
+                "{}. {} - Level {} - {}",
+                rank + 1,
+                entry.score,
+                entry.level,
+                entry.date
+            ))
+            .size(16),
+        );
+    }
+    if high_scores.entries.is_empty() {
+        high_score_list = high_score_list.push(text("No high scores yet").size(16));
+    }
+
     // let handle = image::Handle::from_path("assets/images/test.png");
     // let image = image::viewer(handle);
     // let image_container = Container::new(Container::new(image).children().push(text("text")));
@@ -60,8 +411,18 @@ fn main_menu(ctx: &mut IcedContext<AppStateTransition>) {
         .spacing(10)
         .align_items(Alignment::Center)
         .push(start_button)
+        .push(text(format!("Difficulty: {}", difficulty.label())))
+        .push(difficulty_row)
+        .push(text(format!("Mode: {}", mode.label())))
+        .push(mode_row)
+        .push(seed_field)
+        .push(seed_status)
+        .push(controls_button)
+        .push(settings_button)
         // .push(image_container)
-        .push(quit_button);
+        .push(quit_button)
+        .push(text("High Scores").size(30))
+        .push(scrollable(high_score_list).height(Length::Fixed(150.)));
 
     // Put the column in a container which is on the left side
     let cont = Container::new(column)
@@ -72,3 +433,542 @@ fn main_menu(ctx: &mut IcedContext<AppStateTransition>) {
 
     ctx.display(cont);
 }
+
+// Lists every action's bound keys, clicking one waits for the next key press to replace it
+fn controls_menu(
+    ctx: &mut IcedContext<MenuMessage>,
+    controls: &ControlSettings,
+    rebinding: &Rebinding,
+) {
+    let button = |label: String, msg: MenuMessage| {
+        Button::new(
+            text(label)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .on_press(msg)
+        .width(150.)
+        .height(50.)
+    };
+
+    let mut column = Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(text("Controls").size(50));
+    for action in Action::ALL {
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(text(action.label()).width(150.));
+        for slot in 0..BINDING_SLOTS {
+            let label = if rebinding.waiting == Some((action, slot)) {
+                "...".to_string()
+            } else {
+                match controls.bindings.keys(action).get(slot) {
+                    Some(key) => format!("{key:?}"),
+                    None => "-".to_string(), // An empty slot
+                }
+            };
+            row = row.push(button(label, MenuMessage::Rebind(action, slot)));
+        }
+        // Pressing it switches to the next free gamepad button
+        let gamepad = &controls.gamepad;
+        if let Some(next) = gamepad.next_button(action) {
+            let label = match gamepad.buttons(action).first() {
+                Some(pad_button) => format!("Pad: {pad_button:?}"),
+                None => "Pad: -".to_string(),
+            };
+            row = row.push(button(label, MenuMessage::SetGamepadButton(action, next)));
+        }
+        column = column.push(row);
+    }
+    let column = column
+        .push(text(rebinding.status.clone()).size(16))
+        .push(button(
+            "Reset to Defaults".to_string(),
+            MenuMessage::ResetBindings,
+        ))
+        .push(button(
+            "Back".to_string(),
+            MenuMessage::ShowScreen(MenuScreen::Main),
+        ));
+
+    let cont = Container::new(column)
+        .center_x()
+        .width(Length::Fill)
+        .center_y()
+        .height(Length::Fill);
+
+    ctx.display(cont);
+}
+
+// Adjusts the game's settings, each change applies right away and is saved. The settings are
+// grouped in sections, scrolling once they outgrow the window
+fn settings_menu(ctx: &mut IcedContext<MenuMessage>, settings: &GameSettings) {
+    let button = |label: String, msg: MenuMessage| {
+        Button::new(
+            text(label)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .on_press(msg)
+        .width(250.)
+        .height(40.)
+    };
+    // Shows whether the setting is on, pressing it switches the setting
+    let toggle_button = |toggle: SettingToggle| {
+        let state = if toggle.is_on(settings) { "On" } else { "Off" };
+        button(
+            format!("{}: {state}", toggle.label()),
+            MenuMessage::ToggleSetting(toggle),
+        )
+    };
+    let section = |title: &'static str| text(title).size(30);
+    // The setting's current value above a slider that adjusts it, saved once the slider is let go
+    let setting_slider = |setting: SettingSlider| {
+        let value = setting.value(settings);
+        let decimals = match setting.step() {
+            step if step >= 1. => 0,
+            step if step >= 0.1 => 1,
+            _ => 2,
+        };
+        Column::new()
+            .spacing(5)
+            .align_items(Alignment::Center)
+            .push(text(format!("{}: {value:.decimals$}", setting.label())))
+            .push(
+                slider(setting.range(), value, move |value| {
+                    MenuMessage::SliderChanged(setting, value)
+                })
+                .step(setting.step())
+                .on_release(MenuMessage::SaveSettings)
+                .width(250.),
+            )
+    };
+
+    let volume = settings.audio.master_volume;
+    let volume_slider = slider(0.0..=1.0, volume, MenuMessage::VolumeChanged)
+        .step(0.05)
+        .on_release(MenuMessage::SaveSettings)
+        .width(250.);
+
+    // Pressing it switches to the next theme, same as the theme key
+    let theme = settings.visual.theme;
+    let theme_button = button(
+        format!("Theme: {}", theme.label()),
+        MenuMessage::SetTheme(theme.next()),
+    );
+
+    // Pressing it switches to the next palette
+    let palette = settings.visual.brick_palette;
+    let palette_button = button(
+        format!("Brick Colors: {}", palette.label()),
+        MenuMessage::SetBrickPalette(palette.next()),
+    );
+
+    let edge_mode = settings.gameplay.paddle_edge;
+    let edge_mode_button = button(
+        format!("Paddle Edges: {}", edge_mode.label()),
+        MenuMessage::SetPaddleEdgeMode(edge_mode.next()),
+    );
+
+    // Takes effect from the next run, the walls are built when a run starts
+    let arena_shape = settings.gameplay.arena_shape;
+    let arena_shape_button = button(
+        format!("Arena: {}", arena_shape.label()),
+        MenuMessage::SetArenaShape(arena_shape.next()),
+    );
+    let drain_layout = settings.gameplay.drain_layout;
+    let drain_layout_button = button(
+        format!("Drains: {}", drain_layout.label()),
+        MenuMessage::SetDrainLayout(drain_layout.next()),
+    );
+
+    let timing = settings.controls.paddle_timing;
+    let timing_button = button(
+        format!("Paddle Moves: {}", timing.label()),
+        MenuMessage::SetPaddleTiming(timing.next()),
+    );
+
+    let paddle_input = settings.controls.paddle_input;
+    let paddle_input_button = button(
+        format!("Paddle Input: {}", paddle_input.label()),
+        MenuMessage::SetPaddleInput(paddle_input.next()),
+    );
+
+    let mut column = Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(text("Settings").size(50))
+        .push(section("Audio"))
+        .push(text(format!(
+            "Master Volume: {}%",
+            (volume * 100.).round() as u32
+        )))
+        .push(volume_slider);
+    for toggle in [SettingToggle::Music, SettingToggle::AdaptiveMusic] {
+        column = column.push(toggle_button(toggle));
+    }
+    column = column
+        .push(setting_slider(SettingSlider::MusicVolume))
+        .push(section("Visual"))
+        .push(theme_button)
+        .push(palette_button);
+    for toggle in [
+        SettingToggle::HitStop,
+        SettingToggle::BrickColorCycle,
+        SettingToggle::BallTints,
+        SettingToggle::ScoreCountUp,
+        SettingToggle::Crt,
+        SettingToggle::CrtIncludeUi,
+        SettingToggle::Tilt,
+        SettingToggle::HpBars,
+        SettingToggle::LevelBackgrounds,
+        SettingToggle::StrengthNumbers,
+    ] {
+        column = column.push(toggle_button(toggle));
+    }
+    for setting in [
+        SettingSlider::ScoreCountUpSecs,
+        SettingSlider::CrtScanlines,
+        SettingSlider::CrtCurvature,
+        SettingSlider::CrtVignette,
+        SettingSlider::TiltDegrees,
+        SettingSlider::HpBarMinStrength,
+    ] {
+        column = column.push(setting_slider(setting));
+    }
+    column = column
+        .push(section("Controls"))
+        .push(timing_button)
+        .push(paddle_input_button);
+    column = column.push(section("Accessibility"));
+    for toggle in [SettingToggle::ReduceMotion, SettingToggle::ScreenShake] {
+        column = column.push(toggle_button(toggle));
+    }
+    column = column
+        .push(section("Gameplay"))
+        .push(edge_mode_button)
+        .push(arena_shape_button)
+        .push(drain_layout_button);
+    for toggle in [
+        SettingToggle::BonusRound,
+        SettingToggle::LevelPreview,
+        SettingToggle::PaddleDecay,
+        SettingToggle::GracePeriod,
+        SettingToggle::BrickDescent,
+        SettingToggle::Highlight,
+        SettingToggle::Turret,
+        SettingToggle::ComboMeter,
+        SettingToggle::WallBounceBonus,
+        SettingToggle::Countdown,
+        SettingToggle::Hardcore,
+        SettingToggle::DockedServe,
+    ] {
+        column = column.push(toggle_button(toggle));
+    }
+    for setting in [
+        SettingSlider::ServeCone,
+        SettingSlider::BallGravity,
+        SettingSlider::BounceJitter,
+        SettingSlider::BrickDescentInterval,
+        SettingSlider::HighlightWindow,
+        SettingSlider::ComboWindow,
+        SettingSlider::ComboWarning,
+        SettingSlider::CatchCapacity,
+        SettingSlider::NearMissSpeedup,
+    ] {
+        column = column.push(setting_slider(setting));
+    }
+    column = column.push(section("Assist"));
+    for setting in [SettingSlider::CatchRadius] {
+        column = column.push(setting_slider(setting));
+    }
+    let column = column.push(
+        Button::new(
+            text("Back")
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .on_press(MenuMessage::ShowScreen(MenuScreen::Main))
+        .width(150.)
+        .height(50.),
+    );
+
+    let cont = Container::new(scrollable(column))
+        .center_x()
+        .width(Length::Fill)
+        .center_y()
+        .height(Length::Fill);
+
+    ctx.display(cont);
+}
+
+// Summarizes the run that just ended
+fn game_over(
+    ctx: &mut IcedContext<MenuMessage>,
+    stats: &RunStats,
+    scoreboard: &Scoreboard,
+    profile: &Profile,
+    highlight_available: bool,
+) {
+    let minutes = (stats.elapsed_secs / 60.).floor() as u32;
+    let seconds = (stats.elapsed_secs % 60.).floor() as u32;
+
+    // Starts a fresh run with the seed from the main menu
+    let restart_button = Button::new(
+        text("Restart")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .on_press(MenuMessage::Transition(AppStateTransition::ToInGame))
+    .width(150.)
+    .height(50.);
+
+    let menu_button = Button::new(
+        text("Main Menu")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .on_press(MenuMessage::Transition(AppStateTransition::ToMainMenu))
+    .width(150.)
+    .height(50.);
+
+    // Only offered when highlights are turned on and something was recorded
+    let mut highlight_button = Button::new(
+        text("Watch Highlight")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .width(150.)
+    .height(50.);
+    if highlight_available {
+        highlight_button = highlight_button.on_press(MenuMessage::WatchHighlight);
+    }
+
+    let column = Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(text(if stats.won { "You Win!" } else { "Game Over" }).size(50))
+        .push(text(format!("Final Score: {}", scoreboard.score)))
+        .push(text(format!("Levels Cleared: {}", stats.levels_cleared)))
+        .push(text(format!("Bricks Destroyed: {}", stats.bricks_destroyed)))
+        .push(text(format!("Max Combo: {}", stats.max_combo)))
+        .push(text(format!("Balls Lost: {}", stats.balls_lost)))
+        .push(text(format!(
+            "Fastest Ball: {} (Record: {})",
+            format_speed(stats.fastest_speed),
+            format_speed(profile.fastest_speed)
+        )))
+        .push(text(format!("Time: {minutes}:{seconds:02}")))
+        // Shown in full so players can copy it by hand
+        .push(text(stats.summary(scoreboard.score)).size(16))
+        .push(
+            Row::new()
+                .spacing(10)
+                .push(restart_button)
+                .push(highlight_button)
+                .push(menu_button),
+        );
+
+    let cont = Container::new(column)
+        .center_x()
+        .width(Length::Fill)
+        .center_y()
+        .height(Length::Fill);
+
+    ctx.display(cont);
+}
+
+// Shown along the bottom while the highlight plays, so the arena stays in view
+fn highlight_controls(ctx: &mut IcedContext<MenuMessage>) {
+    let stop_button = Button::new(
+        text("Stop")
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .on_press(MenuMessage::StopHighlight)
+    .width(150.)
+    .height(50.);
+
+    let cont = Container::new(stop_button)
+        .center_x()
+        .width(Length::Fill)
+        .align_y(Vertical::Bottom)
+        .height(Length::Fill)
+        .padding(20);
+
+    ctx.display(cont);
+}
+
+// Shown over the frozen game while it is paused
+pub fn pause_menu(mut ctx: IcedContext<MenuMessage>, rng: Res<GameRng>) {
+    let button = |label: &'static str, msg: MenuMessage| {
+        Button::new(
+            text(label)
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .on_press(msg)
+        .width(150.)
+        .height(50.)
+    };
+
+    let column = Column::new()
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(text("Paused").size(50))
+        .push(button(
+            "Resume",
+            MenuMessage::Game(GameStateTransition::ToPlayGame),
+        ))
+        // Keeps the score, health, and level
+        .push(button(
+            "Restart Level",
+            MenuMessage::Game(GameStateTransition::RestartLevel),
+        ))
+        // Resets the score, health, and level
+        .push(button(
+            "Restart Game",
+            MenuMessage::Game(GameStateTransition::RestartGame),
+        ))
+        // The run stays frozen behind the main menu, starting a game from there begins a new one
+        .push(button(
+            "Main Menu",
+            MenuMessage::Transition(AppStateTransition::ToMainMenu),
+        ))
+        // Shown in full with a copy button, so players can share the run
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(text(format!("Seed: {}", rng.seed())).size(16))
+                .push(Button::new(text("Copy").size(16)).on_press(MenuMessage::CopySeed)),
+        );
+
+    let cont = Container::new(column)
+        .center_x()
+        .width(Length::Fill)
+        .center_y()
+        .height(Length::Fill);
+
+    ctx.display(cont);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::test_app;
+
+    fn rebind_app() -> App {
+        let mut app = test_app();
+        app.init_resource::<Rebinding>()
+            .add_event::<KeyboardInput>()
+            .add_systems(Update, capture_rebind);
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    fn menu_app() -> App {
+        let mut app = test_app();
+        app.add_event::<MenuMessage>()
+            .add_event::<AppStateTransition>()
+            .add_event::<GameStateTransition>()
+            .init_resource::<SeedInput>()
+            .init_resource::<MenuScreen>()
+            .init_resource::<Rebinding>()
+            .init_resource::<HighlightPlayer>()
+            .init_resource::<Difficulty>()
+            .init_resource::<GameMode>()
+            .insert_resource(Theme::Classic.colors())
+            .init_non_send_resource::<Clipboard>()
+            .add_systems(Update, handle_menu_messages);
+        app
+    }
+
+    fn bindings(app: &App) -> &KeyBindings {
+        &app.world.resource::<GameSettings>().controls.bindings
+    }
+
+    #[test]
+    fn the_next_key_pressed_is_bound_to_the_waiting_slot() {
+        let mut app = rebind_app();
+        // Keys pressed before a slot is picked are ignored
+        press(&mut app, KeyCode::J);
+        assert_eq!(bindings(&app).move_left, [KeyCode::A, KeyCode::Left]);
+
+        app.world
+            .resource_mut::<Rebinding>()
+            .start(Action::MoveLeft, 0);
+        press(&mut app, KeyCode::J);
+        assert_eq!(bindings(&app).move_left, [KeyCode::J, KeyCode::Left]);
+        assert!(!app.world.resource::<Rebinding>().is_waiting());
+    }
+
+    #[test]
+    fn reserved_keys_are_rejected() {
+        let mut app = rebind_app();
+        app.world
+            .resource_mut::<Rebinding>()
+            .start(Action::MoveLeft, 0);
+        press(&mut app, KeyCode::F3);
+        assert_eq!(bindings(&app).move_left, [KeyCode::A, KeyCode::Left]);
+        assert_eq!(
+            app.world.resource::<Rebinding>().status,
+            "F3 is reserved and can't be bound"
+        );
+    }
+
+    #[test]
+    fn keys_bound_to_another_action_are_rejected() {
+        let mut app = rebind_app();
+        app.world
+            .resource_mut::<Rebinding>()
+            .start(Action::MoveLeft, 0);
+        press(&mut app, KeyCode::D);
+        assert_eq!(bindings(&app).move_left, [KeyCode::A, KeyCode::Left]);
+        assert_eq!(bindings(&app).move_right, [KeyCode::D, KeyCode::Right]);
+        assert_eq!(
+            app.world.resource::<Rebinding>().status,
+            "D is already bound to Move Right"
+        );
+    }
+
+    #[test]
+    fn picking_a_theme_recolors_the_arena() {
+        let mut app = menu_app();
+        app.world.send_event(MenuMessage::SetTheme(Theme::Mono));
+        app.update();
+        assert_eq!(
+            app.world.resource::<GameSettings>().visual.theme,
+            Theme::Mono
+        );
+        assert_eq!(*app.world.resource::<ThemeColors>(), Theme::Mono.colors());
+    }
+
+    #[test]
+    fn gamepad_buttons_are_rebound_and_reset_with_the_keys() {
+        let mut app = menu_app();
+        app.world.send_event(MenuMessage::SetGamepadButton(
+            Action::Launch,
+            GamepadButtonType::East,
+        ));
+        app.update();
+        let gamepad = &app.world.resource::<GameSettings>().controls.gamepad;
+        assert_eq!(gamepad.launch, [GamepadButtonType::East]);
+
+        app.world.send_event(MenuMessage::ResetBindings);
+        app.update();
+        let gamepad = &app.world.resource::<GameSettings>().controls.gamepad;
+        assert_eq!(gamepad.launch, [GamepadButtonType::South]);
+    }
+}
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    breaker::{game_running, CollisionEvent, CollisionKind, GameStateTransition},
+    hud::HudElement,
+    scoreboard::Scoreboard,
+    settings::GameSettings,
+};
+
+// Each wall bounce in a row adds this much to the bonus banked by the next brick hit
+const WALL_BOUNCE_BONUS: usize = 5;
+const WALL_BONUS_FONT_SIZE: f32 = 30.;
+const WALL_BONUS_COLOR: Color = Color::rgb(0.5, 1., 0.8);
+
+pub struct WallBonusPlugin;
+
+impl Plugin for WallBonusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallBounces>().add_systems(
+            Update,
+            (
+                reset_wall_bounces,
+                (
+                    track_wall_bounces,
+                    update_wall_bonus_display.after(track_wall_bounces),
+                )
+                    .run_if(game_running),
+            )
+                .run_if(state_exists_and_equals(AppState::InGame)),
+        );
+    }
+}
+
+// Wall bounces since the ball last touched a brick or the paddle. The next brick hit banks a
+// bonus for every one, touching the paddle or losing the ball forfeits them
+#[derive(Resource, Default, Deref)]
+pub struct WallBounces(usize);
+
+impl WallBounces {
+    pub fn pending_bonus(&self) -> usize {
+        self.0 * WALL_BOUNCE_BONUS
+    }
+}
+
+// Marker for the HUD text showing the pending bonus
+#[derive(Component)]
+pub struct WallBonusDisplay;
+
+pub fn wall_bonus_display(top_left_placement: Vec2) -> impl Bundle {
+    (
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: WALL_BONUS_FONT_SIZE,
+                color: WALL_BONUS_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(top_left_placement.y),
+            left: Val::Px(top_left_placement.x),
+            ..default()
+        }),
+        HudElement::WallBonus,
+        WallBonusDisplay,
+        Name::new("WallBonusDisplay"),
+    )
+}
+
+// Every level starts without a pending bonus
+pub fn reset_wall_bounces(
+    mut game_msgs: EventReader<GameStateTransition>,
+    mut bounces: ResMut<WallBounces>,
+) {
+    for msg in game_msgs.iter() {
+        if matches!(
+            msg,
+            GameStateTransition::NextLevel
+                | GameStateTransition::RestartLevel
+                | GameStateTransition::RestartGame
+        ) {
+            bounces.0 = 0;
+        }
+    }
+}
+
+pub fn track_wall_bounces(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut bounces: ResMut<WallBounces>,
+    mut scoreboard: ResMut<Scoreboard>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.gameplay.wall_bounce_bonus {
+        collision_events.clear();
+        return;
+    }
+    for collision in collision_events.iter() {
+        match collision.kind {
+            CollisionKind::Wall => bounces.0 += 1,
+            CollisionKind::Brick { .. } => {
+                scoreboard.score += bounces.pending_bonus();
+                bounces.0 = 0;
+            }
+            CollisionKind::Paddle { .. } | CollisionKind::Drain => bounces.0 = 0,
+        }
+    }
+}
+
+pub fn update_wall_bonus_display(
+    bounces: Res<WallBounces>,
+    mut text_q: Query<&mut Text, With<WallBonusDisplay>>,
+) {
+    if !bounces.is_changed() {
+        return;
+    }
+    for mut text in text_q.iter_mut() {
+        text.sections[0].value = match bounces.pending_bonus() {
+            0 => String::new(),
+            bonus => format!("Bounce Bonus: +{bonus}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::test_utils::test_app;
+
+    const BRICK: CollisionKind = CollisionKind::Brick {
+        position: Vec3::ZERO,
+        strength: 1,
+    };
+
+    fn wall_bonus_app() -> App {
+        let mut app = test_app();
+        app.world
+            .resource_mut::<GameSettings>()
+            .gameplay
+            .wall_bounce_bonus = true;
+        app.insert_resource(Scoreboard { score: 0 })
+            .init_resource::<WallBounces>()
+            .add_event::<CollisionEvent>()
+            .add_systems(Update, track_wall_bounces);
+        app
+    }
+
+    fn hit(app: &mut App, kind: CollisionKind) {
+        app.world.send_event(CollisionEvent {
+            contact: Vec2::ZERO,
+            side: None,
+            kind,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn a_brick_hit_banks_the_bonus_for_every_wall_bounce() {
+        let mut app = wall_bonus_app();
+        for _ in 0..3 {
+            hit(&mut app, CollisionKind::Wall);
+        }
+        let bonus = app.world.resource::<WallBounces>().pending_bonus();
+        assert_eq!(bonus, 3 * WALL_BOUNCE_BONUS);
+        hit(&mut app, BRICK);
+        assert_eq!(app.world.resource::<Scoreboard>().score, bonus);
+        assert_eq!(**app.world.resource::<WallBounces>(), 0);
+    }
+
+    #[test]
+    fn touching_the_paddle_forfeits_the_bonus() {
+        let mut app = wall_bonus_app();
+        for _ in 0..3 {
+            hit(&mut app, CollisionKind::Wall);
+        }
+        hit(&mut app, CollisionKind::Paddle { offset: 0. });
+        hit(&mut app, BRICK);
+        assert_eq!(app.world.resource::<Scoreboard>().score, 0);
+    }
+}
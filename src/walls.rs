@@ -1,13 +1,154 @@
-use crate::breaker::{ball_ricochet, Ball, Collider, CollisionEvent, PlayerMessage, Velocity};
+use std::f32::consts::FRAC_PI_4;
+
+use crate::breaker::{
+    aabb_contact_point, ball_reflect, Ball, Collider, CollisionEvent, CollisionKind, PlayerMessage,
+    Velocity,
+};
+use crate::grace::GracePeriod;
 use bevy::{prelude::*, sprite::collide_aabb::collide};
+use serde::{Deserialize, Serialize};
 
 pub const WALL_THICKNESS: f32 = 10.0;
 pub const LEFT_WALL: f32 = -450.0;
 pub const RIGHT_WALL: f32 = 450.0;
 pub const BOTTOM_WALL: f32 = -300.;
 pub const TOP_WALL: f32 = 300.;
+// How far along the top and side walls an angled corner reaches
+const CORNER_SIZE: f32 = 70.;
+// The share of the floor's width each side drain takes up
+const SIDE_DRAIN_FRACTION: f32 = 0.25;
+const DRAIN_COLOR: Color = Color::rgb(0.45, 0.1, 0.15);
+
+// The outline of the arena the walls enclose
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArenaShape {
+    #[default]
+    Box,
+    Chamfered, // The top corners are cut off at 45 degrees, so the ball caroms off them diagonally
+}
+
+impl ArenaShape {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArenaShape::Box => "Box",
+            ArenaShape::Chamfered => "Angled Corners",
+        }
+    }
+
+    pub fn next(&self) -> ArenaShape {
+        match self {
+            ArenaShape::Box => ArenaShape::Chamfered,
+            ArenaShape::Chamfered => ArenaShape::Box,
+        }
+    }
+
+    // The angled wall segments the shape adds to the box
+    fn corners(&self) -> Vec<CornerWall> {
+        match self {
+            ArenaShape::Box => vec![],
+            ArenaShape::Chamfered => vec![
+                CornerWall {
+                    normal: Vec2::new(1., -1.).normalize(),
+                },
+                CornerWall {
+                    normal: Vec2::new(-1., -1.).normalize(),
+                },
+            ],
+        }
+    }
+
+    // Whether a rectangle lies wholly inside the playable region, clear of the angled corners
+    pub fn fits(&self, center: Vec2, size: Vec2) -> bool {
+        let half = size / 2.;
+        self.corners().iter().all(|corner| {
+            let face = corner.center() + corner.normal * WALL_THICKNESS / 2.;
+            // Only the rectangle's corner nearest the wall can cross it
+            let nearest = center - half * corner.normal.signum();
+            (nearest - face).dot(corner.normal) >= 0.
+        })
+    }
+}
+
+// Where balls fall out of play
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrainLayout {
+    #[default]
+    Bottom, // The whole floor drains
+    Sides, // Only the ends of the floor drain, the middle is a wall the ball bounces off
+}
 
-const WALL_COLOR: Color = Color::rgb(0.8, 0.8, 0.8);
+impl DrainLayout {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DrainLayout::Bottom => "Bottom",
+            DrainLayout::Sides => "Sides",
+        }
+    }
+
+    pub fn next(&self) -> DrainLayout {
+        match self {
+            DrainLayout::Bottom => DrainLayout::Sides,
+            DrainLayout::Sides => DrainLayout::Bottom,
+        }
+    }
+
+    // The floor's segments, left to right, as center, size, and whether it drains
+    fn floor_segments(&self) -> Vec<(Vec2, Vec2, bool)> {
+        let floor = WallLocation::Bottom;
+        match self {
+            DrainLayout::Bottom => vec![(floor.position(), floor.size(), true)],
+            DrainLayout::Sides => {
+                let size = floor.size();
+                let drain_size = Vec2::new(size.x * SIDE_DRAIN_FRACTION, size.y);
+                let wall_size = Vec2::new(size.x - 2. * drain_size.x, size.y);
+                let drain_offset = Vec2::new((size.x - drain_size.x) / 2., 0.);
+                vec![
+                    (floor.position() - drain_offset, drain_size, true),
+                    (floor.position(), wall_size, false),
+                    (floor.position() + drain_offset, drain_size, true),
+                ]
+            }
+        }
+    }
+}
+
+// An angled wall segment cutting off one of the arena's top corners. The normal points into the
+// arena, so it also tells the corners apart: it leans right for the top left corner
+#[derive(Component, Clone, Copy)]
+pub struct CornerWall {
+    normal: Vec2,
+}
+
+impl CornerWall {
+    // The corner where the inner faces of the top and side walls meet
+    fn inner_corner(&self) -> Vec2 {
+        let x = if self.normal.x > 0. {
+            LEFT_WALL + WALL_THICKNESS / 2.
+        } else {
+            RIGHT_WALL - WALL_THICKNESS / 2.
+        };
+        Vec2::new(x, TOP_WALL - WALL_THICKNESS / 2.)
+    }
+
+    fn center(&self) -> Vec2 {
+        // Halfway between the points CORNER_SIZE along each wall from the inner corner
+        let inner = self.inner_corner();
+        inner + Vec2::new(self.normal.x.signum() * CORNER_SIZE, -CORNER_SIZE) / 2.
+    }
+
+    fn transform(&self) -> Transform {
+        let angle = if self.normal.x > 0. {
+            FRAC_PI_4
+        } else {
+            -FRAC_PI_4
+        };
+        Transform {
+            translation: self.center().extend(0.),
+            rotation: Quat::from_rotation_z(angle),
+            scale: Vec3::new(CORNER_SIZE * 2_f32.sqrt(), WALL_THICKNESS, 1.),
+        }
+    }
+}
 
 #[derive(Bundle)]
 pub struct WallBundle {
@@ -20,8 +161,9 @@ pub struct WallBundle {
 #[derive(Component)]
 pub struct Wall;
 
+// Balls touching a drain fall out of play
 #[derive(Component)]
-pub struct BottomWall;
+pub struct DrainZone;
 
 pub enum WallLocation {
     Left,
@@ -56,58 +198,292 @@ impl WallLocation {
 }
 
 impl WallBundle {
-    fn new(location: WallLocation) -> WallBundle {
+    fn new(location: WallLocation, color: Color) -> WallBundle {
+        WallBundle::from_rect(location.position(), location.size(), color)
+    }
+
+    fn from_rect(position: Vec2, size: Vec2, color: Color) -> WallBundle {
         WallBundle {
-            sprite_bundle: SpriteBundle {
-                transform: Transform {
-                    translation: location.position().extend(0.),
-                    // Sprite scales must ALWAYS have a z value of to avoid ordering issues
-                    scale: location.size().extend(1.),
-                    ..default()
-                },
-                sprite: Sprite {
-                    color: WALL_COLOR,
-                    ..default()
-                },
-                ..default()
-            },
+            sprite_bundle: wall_sprite(position, size, color),
             collider: Collider,
             marker: Wall,
         }
     }
 }
 
-pub fn setup(commands: &mut Commands) {
-    commands.spawn((WallBundle::new(WallLocation::Left), Name::new("WallLeft")));
-    commands.spawn((WallBundle::new(WallLocation::Right), Name::new("WallRight")));
-    commands.spawn((WallBundle::new(WallLocation::Top), Name::new("WallTop")));
+fn wall_sprite(position: Vec2, size: Vec2, color: Color) -> SpriteBundle {
+    SpriteBundle {
+        transform: Transform {
+            translation: position.extend(0.),
+            // Sprite scales must ALWAYS have a z value of to avoid ordering issues
+            scale: size.extend(1.),
+            ..default()
+        },
+        sprite: Sprite {
+            color,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+pub fn setup(commands: &mut Commands, color: Color, shape: ArenaShape, drain_layout: DrainLayout) {
+    commands.spawn((
+        WallBundle::new(WallLocation::Left, color),
+        Name::new("WallLeft"),
+    ));
     commands.spawn((
-        WallBundle::new(WallLocation::Bottom),
-        BottomWall,
-        Name::new("WallBottom"),
+        WallBundle::new(WallLocation::Right, color),
+        Name::new("WallRight"),
     ));
+    commands.spawn((
+        WallBundle::new(WallLocation::Top, color),
+        Name::new("WallTop"),
+    ));
+    // A floor that drains all along is themed like the other walls, side drains stand out from
+    // the wall between them instead. Walls that drain are left out of the bounce checks
+    for (position, size, drains) in drain_layout.floor_segments() {
+        if !drains {
+            commands.spawn((
+                WallBundle::from_rect(position, size, color),
+                Name::new("WallBottom"),
+            ));
+        } else if drain_layout == DrainLayout::Bottom {
+            commands.spawn((
+                WallBundle::from_rect(position, size, color),
+                DrainZone,
+                Name::new("WallBottom"),
+            ));
+        } else {
+            commands.spawn((
+                wall_sprite(position, size, DRAIN_COLOR),
+                Collider,
+                DrainZone,
+                Name::new("Drain"),
+            ));
+        }
+    }
+    // Corners are marked as walls for theming, but collide separately as they aren't axis aligned
+    for corner in shape.corners() {
+        commands.spawn((
+            SpriteBundle {
+                transform: corner.transform(),
+                sprite: Sprite {
+                    color,
+                    ..default()
+                },
+                ..default()
+            },
+            corner,
+            Wall,
+            Name::new("WallCorner"),
+        ));
+    }
 }
 
-pub fn check_bottom_wall_collision(
+// Bounces balls off the angled corners about the corner's normal
+pub fn check_corner_collision(
     mut ball_q: Query<(&mut Velocity, &Transform), With<Ball>>,
-    mut collider_q: Query<&Transform, (With<BottomWall>, With<Collider>)>,
+    corner_q: Query<(&Transform, &CornerWall)>,
+    mut collision_events: EventWriter<CollisionEvent>,
+) {
+    for (mut ball_v, ball_t) in ball_q.iter_mut() {
+        let ball_pos = ball_t.translation.truncate();
+        let ball_radius = ball_t.scale.x / 2.;
+
+        for (tform, corner) in corner_q.iter() {
+            let center = tform.translation.truncate();
+            let along = (tform.rotation * Vec3::X).truncate();
+            let half_length = tform.scale.x / 2.;
+            let along_dist = (ball_pos - center).dot(along).clamp(-half_length, half_length);
+            let contact = center + along * along_dist;
+            // How far the ball's center is in front of the corner's inner face
+            let gap = (ball_pos - contact).dot(corner.normal) - WALL_THICKNESS / 2.;
+            if gap < ball_radius && ball_v.dot(corner.normal) < 0. {
+                collision_events.send(CollisionEvent {
+                    contact,
+                    side: None,
+                    kind: CollisionKind::Wall,
+                });
+                ball_reflect(corner.normal, &mut ball_v);
+            }
+        }
+    }
+}
+
+// Balls touching a drain fall out of play. Health is only lost once the last ball is gone, and
+// not during a grace period, after which the breaker re-serves a fresh ball if any health remains
+pub fn check_drain_collision(
+    mut commands: Commands,
+    ball_q: Query<(Entity, &Transform), With<Ball>>,
+    collider_q: Query<&Transform, (With<DrainZone>, With<Collider>)>,
     mut collision_events: EventWriter<CollisionEvent>,
     mut player_events: EventWriter<PlayerMessage>,
+    grace: Res<GracePeriod>,
 ) {
-    let (mut ball_v, ball_t) = ball_q.single_mut();
-    let ball_size = ball_t.scale.truncate();
-
-    for tform in collider_q.iter_mut() {
-        let collision = collide(
-            ball_t.translation,
-            ball_size,
-            tform.translation,
-            tform.scale.truncate(),
-        );
-        if let Some(collision) = collision {
-            collision_events.send_default();
-            player_events.send(PlayerMessage::JustLostHealth);
-            ball_ricochet(collision, &mut ball_v);
+    let mut remaining = ball_q.iter().count();
+    if remaining == 0 {
+        return;
+    }
+
+    for (ball, ball_t) in ball_q.iter() {
+        let ball_size = ball_t.scale.truncate();
+        let fell = collider_q.iter().find_map(|tform| {
+            collide(
+                ball_t.translation,
+                ball_size,
+                tform.translation,
+                tform.scale.truncate(),
+            )
+            .map(|collision| (collision, tform))
+        });
+        if let Some((collision, tform)) = fell {
+            collision_events.send(CollisionEvent {
+                contact: aabb_contact_point(
+                    ball_t.translation.truncate(),
+                    tform.translation.truncate(),
+                    tform.scale.truncate(),
+                ),
+                side: Some(collision),
+                kind: CollisionKind::Drain,
+            });
+            commands.entity(ball).despawn_recursive();
+            remaining -= 1;
+        }
+    }
+
+    if remaining == 0 && !grace.active() {
+        player_events.send(PlayerMessage::JustLostHealth);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grace::tick_grace_period,
+        misc::test_utils::{advance_time, sent_events, test_app},
+        settings::GameSettings,
+    };
+
+    const TEST_BALL_SIZE: f32 = 20.;
+
+    // A ball just touching the top right corner's face, moving with the given velocity
+    fn corner_bounce(velocity: Vec2) -> Vec2 {
+        let mut app = App::new();
+        app.add_event::<CollisionEvent>()
+            .add_systems(Update, check_corner_collision);
+        let corner = ArenaShape::Chamfered.corners()[1];
+        app.world.spawn((corner.transform(), corner));
+        let touching = WALL_THICKNESS / 2. + TEST_BALL_SIZE / 2. - 1.;
+        let position = corner.center() + corner.normal * touching;
+        let ball = app
+            .world
+            .spawn((
+                Transform::from_translation(position.extend(0.))
+                    .with_scale(Vec3::splat(TEST_BALL_SIZE)),
+                Velocity(velocity),
+                Ball,
+            ))
+            .id();
+        app.update();
+        app.world.get::<Velocity>(ball).unwrap().0
+    }
+
+    #[test]
+    fn a_ball_heading_into_a_45_degree_corner_comes_straight_back() {
+        let bounced = corner_bounce(Vec2::new(1., 1.));
+        assert!(bounced.abs_diff_eq(Vec2::new(-1., -1.), 1e-5));
+    }
+
+    #[test]
+    fn a_ball_running_along_the_top_is_turned_straight_down_by_a_corner() {
+        let bounced = corner_bounce(Vec2::X);
+        assert!(bounced.abs_diff_eq(Vec2::NEG_Y, 1e-5));
+    }
+
+    // The layout's drains, with grace periods turned on
+    fn drain_app(layout: DrainLayout) -> App {
+        let mut app = test_app();
+        app.init_resource::<GracePeriod>()
+            .add_event::<CollisionEvent>()
+            .add_event::<PlayerMessage>()
+            .add_systems(Update, (tick_grace_period, check_drain_collision).chain());
+        app.world
+            .resource_mut::<GameSettings>()
+            .gameplay
+            .grace_period = true;
+        for (position, size, drains) in layout.floor_segments() {
+            if drains {
+                app.world.spawn((
+                    Transform::from_translation(position.extend(0.)).with_scale(size.extend(1.)),
+                    Collider,
+                    DrainZone,
+                ));
+            }
         }
+        app
+    }
+
+    fn drop_ball_at(app: &mut App, x: f32) {
+        app.world.spawn((
+            Transform::from_xyz(x, BOTTOM_WALL, 0.).with_scale(Vec3::splat(TEST_BALL_SIZE)),
+            Ball,
+        ));
+    }
+
+    fn drop_ball(app: &mut App) {
+        drop_ball_at(app, 0.);
+    }
+
+    fn lost_health(app: &App) -> bool {
+        !sent_events::<PlayerMessage>(app).is_empty()
+    }
+
+    #[test]
+    fn losing_the_ball_during_grace_costs_no_health() {
+        let mut app = drain_app(DrainLayout::Bottom);
+        app.world
+            .resource_scope(|world, mut grace: Mut<GracePeriod>| {
+                grace.start(world.resource::<GameSettings>());
+            });
+        drop_ball(&mut app);
+        advance_time(&mut app, 1.);
+        app.update();
+        // The ball is gone for the breaker to re-serve, but no health is lost
+        let mut ball_q = app.world.query_filtered::<(), With<Ball>>();
+        assert_eq!(ball_q.iter(&app.world).count(), 0);
+        assert!(!lost_health(&app));
+
+        // Well past the grace period, the next lost ball costs health
+        advance_time(&mut app, 10.);
+        app.update();
+        drop_ball(&mut app);
+        app.update();
+        assert!(lost_health(&app));
+    }
+
+    #[test]
+    fn the_bottom_layout_drains_the_whole_floor() {
+        let mut app = drain_app(DrainLayout::Bottom);
+        drop_ball_at(&mut app, RIGHT_WALL * 0.9);
+        app.update();
+        assert!(lost_health(&app));
+    }
+
+    #[test]
+    fn a_ball_entering_a_side_drain_costs_health() {
+        let mut app = drain_app(DrainLayout::Sides);
+        drop_ball_at(&mut app, LEFT_WALL * 0.9);
+        app.update();
+        assert!(lost_health(&app));
+    }
+
+    #[test]
+    fn the_middle_of_the_floor_does_not_drain_with_side_drains() {
+        let mut app = drain_app(DrainLayout::Sides);
+        drop_ball(&mut app);
+        app.update();
+        assert!(!lost_health(&app));
     }
 }